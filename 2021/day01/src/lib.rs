@@ -0,0 +1,84 @@
+use std::{fmt::Display, num::ParseIntError};
+
+use aoc::{Error, Solution};
+
+/// The depth measurements of the sonar sweep, in order.
+pub struct SonarSweep {
+    depths: Vec<i32>,
+}
+
+impl SonarSweep {
+    pub fn parse(contents: &str) -> Result<Self, ParseIntError> {
+        let depths = contents
+            .lines()
+            .map(|line| line.trim().parse())
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self { depths })
+    }
+
+    pub fn count_increases(&self) -> usize {
+        let mut last_number = 0;
+        let mut increase_counter = 0;
+
+        for (i, &number) in self.depths.iter().enumerate() {
+            if i > 0 && number > last_number {
+                increase_counter += 1;
+            }
+
+            last_number = number;
+        }
+
+        increase_counter
+    }
+
+    pub fn count_increases_with_moving_average(&self) -> usize {
+        let windows = self
+            .depths
+            .windows(3)
+            .map(|window| window.iter().sum())
+            .collect::<Vec<i32>>();
+
+        windows.windows(2).filter(|x| x[1] > x[0]).count()
+    }
+}
+
+impl Solution for SonarSweep {
+    fn parse(input: &str) -> Result<Self, Error> {
+        Ok(Self::parse(input)?)
+    }
+
+    fn part1(&self) -> Result<impl Display, Error> {
+        Ok(self.count_increases())
+    }
+
+    fn part2(&self) -> Result<impl Display, Error> {
+        Ok(self.count_increases_with_moving_average())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_counts_correctly() {
+        let input = "199
+200
+208
+210
+200
+207
+240
+269
+260
+263";
+
+        let sweep = SonarSweep::parse(input).unwrap();
+        let count = sweep.count_increases();
+        assert_eq!(count, 7);
+
+        let count = sweep.count_increases_with_moving_average();
+        assert_eq!(count, 5);
+    }
+}
@@ -0,0 +1,513 @@
+//! Submarine commands, plus a small scripting language on top of them:
+//!
+//! ```text
+//! # comments run to the end of the line
+//! macro zigzag
+//!     down 2
+//!     forward 1
+//!     up 2
+//! end
+//!
+//! forward 5
+//! repeat 3
+//!     zigzag
+//! end
+//! ```
+//!
+//! Macros have to be defined at the top level before they are used, so they can't recurse.
+
+use std::{fmt::Display, str::FromStr};
+
+use aoc::{Error, Solution};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Command {
+    Foward(i64),
+    Down(i64),
+    Up(i64),
+}
+
+impl Command {
+    fn name(&self) -> &'static str {
+        match self {
+            Command::Foward(_) => "forward",
+            Command::Down(_) => "down",
+            Command::Up(_) => "up",
+        }
+    }
+
+    fn amount(&self) -> i64 {
+        match *self {
+            Command::Foward(amount) | Command::Down(amount) | Command::Up(amount) => amount,
+        }
+    }
+}
+
+impl Display for Command {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {}", self.name(), self.amount())
+    }
+}
+
+impl FromStr for Command {
+    type Err = ScriptErrorKind;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (name, amount) = s
+            .trim()
+            .split_once(' ')
+            .ok_or_else(|| ScriptErrorKind::MissingAmount(s.trim().to_string()))?;
+        let amount = amount
+            .trim()
+            .parse()
+            .map_err(|_| ScriptErrorKind::InvalidAmount(amount.trim().to_string()))?;
+
+        match name {
+            "forward" => Ok(Command::Foward(amount)),
+            "down" => Ok(Command::Down(amount)),
+            "up" => Ok(Command::Up(amount)),
+            _ => Err(ScriptErrorKind::UnknownCommand(name.to_string())),
+        }
+    }
+}
+
+pub trait Ship {
+    fn new() -> Self;
+    fn product(&self) -> i64;
+    /// `(horizontal, depth)`
+    fn position(&self) -> (i64, i64);
+    fn run_command(&mut self, cmd: Command);
+    fn run_commands(&mut self, cmds: impl Iterator<Item = Command>) {
+        for cmd in cmds {
+            self.run_command(cmd);
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct ShipPosition {
+    horizontal: i64,
+    depth: i64,
+}
+
+impl Ship for ShipPosition {
+    fn new() -> Self {
+        Self {
+            horizontal: 0,
+            depth: 0,
+        }
+    }
+
+    fn product(&self) -> i64 {
+        self.horizontal * self.depth
+    }
+
+    fn position(&self) -> (i64, i64) {
+        (self.horizontal, self.depth)
+    }
+
+    fn run_command(&mut self, cmd: Command) {
+        match cmd {
+            Command::Foward(amount) => self.horizontal += amount,
+            Command::Down(amount) => self.depth += amount,
+            Command::Up(amount) => self.depth -= amount,
+        };
+    }
+}
+
+#[derive(Debug)]
+pub struct ShipPositionWithAim {
+    horizontal: i64,
+    depth: i64,
+    aim: i64,
+}
+
+impl Ship for ShipPositionWithAim {
+    fn new() -> Self {
+        Self {
+            horizontal: 0,
+            depth: 0,
+            aim: 0,
+        }
+    }
+
+    fn product(&self) -> i64 {
+        self.horizontal * self.depth
+    }
+
+    fn position(&self) -> (i64, i64) {
+        (self.horizontal, self.depth)
+    }
+
+    fn run_command(&mut self, cmd: Command) {
+        match cmd {
+            Command::Foward(amount) => {
+                self.horizontal += amount;
+                self.depth += self.aim * amount;
+            }
+            Command::Down(amount) => self.aim += amount,
+            Command::Up(amount) => self.aim -= amount,
+        };
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ScriptErrorKind {
+    UnknownCommand(String),
+    /// A command without an amount that isn't a known macro either.
+    MissingAmount(String),
+    InvalidAmount(String),
+    InvalidRepeatCount(String),
+    /// A `repeat` or `macro` block without its `end`, at the line the block starts.
+    UnterminatedBlock,
+    UnexpectedEnd,
+    NestedMacro(String),
+    DuplicateMacro(String),
+}
+
+impl Display for ScriptErrorKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ScriptErrorKind::UnknownCommand(name) => write!(f, "unknown command `{name}`"),
+            ScriptErrorKind::MissingAmount(name) => {
+                write!(f, "`{name}` is neither a macro nor has an amount")
+            }
+            ScriptErrorKind::InvalidAmount(amount) => write!(f, "invalid amount `{amount}`"),
+            ScriptErrorKind::InvalidRepeatCount(count) => {
+                write!(f, "invalid repeat count `{count}`")
+            }
+            ScriptErrorKind::UnterminatedBlock => write!(f, "block is missing its `end`"),
+            ScriptErrorKind::UnexpectedEnd => write!(f, "`end` without a block"),
+            ScriptErrorKind::NestedMacro(name) => {
+                write!(f, "macro `{name}` has to be defined at the top level")
+            }
+            ScriptErrorKind::DuplicateMacro(name) => write!(f, "macro `{name}` is already defined"),
+        }
+    }
+}
+
+/// `line` is 1-based.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ScriptError {
+    pub line: usize,
+    pub kind: ScriptErrorKind,
+}
+
+impl Display for ScriptError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line {}: {}", self.line, self.kind)
+    }
+}
+
+impl std::error::Error for ScriptError {}
+
+#[derive(Clone, Debug)]
+enum Statement {
+    Command {
+        line: usize,
+        command: Command,
+    },
+    Repeat {
+        count: usize,
+        body: Vec<Statement>,
+    },
+    /// Index into `Script::macros`.
+    Call(usize),
+}
+
+#[derive(Clone, Debug)]
+pub struct Script {
+    statements: Vec<Statement>,
+    macros: Vec<(String, Vec<Statement>)>,
+}
+
+impl Script {
+    pub fn parse(input: &str) -> Result<Self, ScriptError> {
+        let mut script = Self {
+            statements: Vec::new(),
+            macros: Vec::new(),
+        };
+
+        let mut lines = input.lines().enumerate().map(|(i, line)| (i + 1, line));
+        script.statements = script.parse_block(&mut lines, None)?;
+
+        Ok(script)
+    }
+
+    /// Parses statements up to the `end` of the block opened at line `opened`, or up to the end
+    /// of the input at the top level.
+    fn parse_block<'a>(
+        &mut self,
+        lines: &mut impl Iterator<Item = (usize, &'a str)>,
+        opened: Option<usize>,
+    ) -> Result<Vec<Statement>, ScriptError> {
+        let mut block = Vec::new();
+
+        while let Some((line, text)) = lines.next() {
+            let error = |kind| ScriptError { line, kind };
+            let text = text.split('#').next().unwrap().trim();
+
+            match text.split_whitespace().collect::<Vec<_>>()[..] {
+                [] => {}
+                ["end"] => match opened {
+                    Some(_) => return Ok(block),
+                    None => return Err(error(ScriptErrorKind::UnexpectedEnd)),
+                },
+                ["repeat", count] => {
+                    let count = count.parse().map_err(|_| {
+                        error(ScriptErrorKind::InvalidRepeatCount(count.to_string()))
+                    })?;
+                    let body = self.parse_block(lines, Some(line))?;
+                    block.push(Statement::Repeat { count, body });
+                }
+                ["macro", name] => {
+                    if opened.is_some() {
+                        return Err(error(ScriptErrorKind::NestedMacro(name.to_string())));
+                    }
+                    if self.macro_index(name).is_some() {
+                        return Err(error(ScriptErrorKind::DuplicateMacro(name.to_string())));
+                    }
+                    let body = self.parse_block(lines, Some(line))?;
+                    self.macros.push((name.to_string(), body));
+                }
+                [name] => match self.macro_index(name) {
+                    Some(index) => block.push(Statement::Call(index)),
+                    None => return Err(error(ScriptErrorKind::MissingAmount(name.to_string()))),
+                },
+                _ => block.push(Statement::Command {
+                    line,
+                    command: text.parse().map_err(error)?,
+                }),
+            }
+        }
+
+        match opened {
+            Some(line) => Err(ScriptError {
+                line,
+                kind: ScriptErrorKind::UnterminatedBlock,
+            }),
+            None => Ok(block),
+        }
+    }
+
+    fn macro_index(&self, name: &str) -> Option<usize> {
+        self.macros.iter().position(|(defined, _)| defined == name)
+    }
+
+    pub fn run<S: Ship>(&self, ship: &mut S) {
+        self.execute(&self.statements, ship, &mut |_, _, _| ());
+    }
+
+    /// Runs the script and records where `ship` is after every command.
+    pub fn trace<S: Ship>(&self, ship: &mut S) -> Trace {
+        let mut trace = Trace { rows: Vec::new() };
+        self.execute(&self.statements, ship, &mut |line, command, ship| {
+            let (horizontal, depth) = ship.position();
+            trace.rows.push(TraceRow {
+                line,
+                command,
+                horizontal,
+                depth,
+            });
+        });
+
+        trace
+    }
+
+    fn execute<S: Ship>(
+        &self,
+        block: &[Statement],
+        ship: &mut S,
+        observe: &mut impl FnMut(usize, Command, &S),
+    ) {
+        for statement in block {
+            match statement {
+                &Statement::Command { line, command } => {
+                    ship.run_command(command);
+                    observe(line, command, ship);
+                }
+                Statement::Repeat { count, body } => {
+                    for _ in 0..*count {
+                        self.execute(body, ship, observe);
+                    }
+                }
+                &Statement::Call(index) => self.execute(&self.macros[index].1, ship, observe),
+            }
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TraceRow {
+    /// The script line the command comes from.
+    pub line: usize,
+    pub command: Command,
+    pub horizontal: i64,
+    pub depth: i64,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Trace {
+    pub rows: Vec<TraceRow>,
+}
+
+impl Trace {
+    /// One row per command, `step` counts from 1.
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("step,line,command,amount,horizontal,depth\n");
+        for (step, row) in self.rows.iter().enumerate() {
+            csv += &format!(
+                "{},{},{},{},{},{}\n",
+                step + 1,
+                row.line,
+                row.command.name(),
+                row.command.amount(),
+                row.horizontal,
+                row.depth
+            );
+        }
+
+        csv
+    }
+}
+
+pub fn dive<T: Ship>(input: &str) -> Result<T, ScriptError> {
+    let script = Script::parse(input)?;
+
+    let mut ship = T::new();
+    script.run(&mut ship);
+
+    Ok(ship)
+}
+
+impl Solution for Script {
+    fn parse(input: &str) -> Result<Self, Error> {
+        Ok(Script::parse(input)?)
+    }
+
+    fn part1(&self) -> Result<impl Display, Error> {
+        let mut ship = ShipPosition::new();
+        self.run(&mut ship);
+        Ok(ship.product())
+    }
+
+    fn part2(&self) -> Result<impl Display, Error> {
+        let mut ship = ShipPositionWithAim::new();
+        self.run(&mut ship);
+        Ok(ship.product())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_commands() {
+        assert_eq!("forward 5".parse(), Ok(Command::Foward(5)));
+        assert_eq!("down 2".parse(), Ok(Command::Down(2)));
+        assert_eq!("up 3".parse(), Ok(Command::Up(3)));
+        assert_eq!(
+            "sideways 3".parse::<Command>(),
+            Err(ScriptErrorKind::UnknownCommand("sideways".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_part1_example() {
+        let input = "forward 5
+down 5
+forward 8
+up 3
+down 8
+forward 2";
+        let ship = dive::<ShipPosition>(input).unwrap();
+        assert_eq!(ship.horizontal, 15);
+        assert_eq!(ship.depth, 10);
+        assert_eq!(ship.product(), 150);
+
+        let ship = dive::<ShipPositionWithAim>(input).unwrap();
+        assert_eq!(ship.horizontal, 15);
+        assert_eq!(ship.depth, 60);
+        assert_eq!(ship.product(), 900);
+    }
+
+    #[test]
+    fn test_script() {
+        let input = "# dive in steps
+macro step
+    down 2 # deeper
+    forward 1
+end
+
+repeat 3
+    step
+    repeat 2
+        forward 1
+    end
+end
+up 1";
+        let script = Script::parse(input).unwrap();
+
+        let mut ship = ShipPosition::new();
+        let trace = script.trace(&mut ship);
+        assert_eq!(ship.position(), (9, 5));
+        assert_eq!(trace.rows.len(), 13);
+        assert_eq!(
+            trace.rows[0],
+            TraceRow {
+                line: 3,
+                command: Command::Down(2),
+                horizontal: 0,
+                depth: 2
+            }
+        );
+
+        let csv = script.trace(&mut ShipPositionWithAim::new()).to_csv();
+        let mut lines = csv.lines();
+        assert_eq!(
+            lines.next(),
+            Some("step,line,command,amount,horizontal,depth")
+        );
+        assert_eq!(lines.next(), Some("1,3,down,2,0,0"));
+        assert_eq!(lines.next(), Some("2,4,forward,1,1,2"));
+        assert_eq!(lines.last(), Some("13,13,up,1,9,36"));
+    }
+
+    #[test]
+    fn test_script_errors() {
+        let error = |input| Script::parse(input).unwrap_err();
+
+        assert_eq!(
+            error("forward 1\ndown x"),
+            ScriptError {
+                line: 2,
+                kind: ScriptErrorKind::InvalidAmount("x".to_string())
+            }
+        );
+        assert_eq!(
+            error("forward 1\nrepeat 2\nforward 1"),
+            ScriptError {
+                line: 2,
+                kind: ScriptErrorKind::UnterminatedBlock
+            }
+        );
+        assert_eq!(error("end").kind, ScriptErrorKind::UnexpectedEnd);
+        assert_eq!(
+            error("zigzag\nmacro zigzag\nend"),
+            ScriptError {
+                line: 1,
+                kind: ScriptErrorKind::MissingAmount("zigzag".to_string())
+            }
+        );
+        assert_eq!(
+            error("repeat 2\nmacro inner\nend\nend").kind,
+            ScriptErrorKind::NestedMacro("inner".to_string())
+        );
+        assert_eq!(error("macro a\nend\nmacro a\nend").line, 3);
+        assert_eq!(
+            error("repeat many\nend").kind,
+            ScriptErrorKind::InvalidRepeatCount("many".to_string())
+        );
+    }
+}
@@ -0,0 +1,150 @@
+use aoc2021_day03::{Criteria, DiagnosticsReport};
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+/// A million pseudo-random 12 bit rows, like the puzzle input but much longer.
+fn large_input() -> String {
+    let mut state: u64 = 0x2545_f491_4f6c_dd1d;
+    (0..1_000_000)
+        .map(|_| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            format!("{:012b}", state >> 52)
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// The original report, one `Vec<bool>` per row, kept as a baseline for the packed rows and
+/// the trie.
+mod baseline {
+    pub struct DiagnosticsReport {
+        width: usize,
+        parsed: Vec<Vec<bool>>,
+    }
+
+    impl From<&str> for DiagnosticsReport {
+        fn from(input: &str) -> Self {
+            let width = input.lines().next().expect("No first line").len();
+            let parsed = input
+                .lines()
+                .map(|line| line.chars().map(|c| c == '1').collect())
+                .collect();
+
+            Self { width, parsed }
+        }
+    }
+
+    impl DiagnosticsReport {
+        fn with_new_data(&self, parsed: Vec<Vec<bool>>) -> Self {
+            Self {
+                width: self.width,
+                parsed,
+            }
+        }
+
+        fn count_bits(&self) -> Vec<usize> {
+            self.parsed
+                .iter()
+                .fold(vec![0; self.width], |counters: Vec<usize>, line| {
+                    counters
+                        .iter()
+                        .zip(line)
+                        .map(|(counter, &bit)| counter + bit as usize)
+                        .collect()
+                })
+        }
+
+        fn find_most_common(&self, tie: bool) -> Vec<bool> {
+            let counters = self.count_bits();
+            let total = self.parsed.len();
+
+            counters
+                .iter()
+                .map(|&trues| {
+                    if total - trues == trues {
+                        tie
+                    } else {
+                        total - trues < trues
+                    }
+                })
+                .collect()
+        }
+
+        pub fn filter_report(&self, criteria: bool) -> Vec<bool> {
+            let mut report = self.with_new_data(self.parsed.to_vec());
+
+            for i in 0..report.width {
+                let mut most_common = report.find_most_common(true);
+                if !criteria {
+                    most_common = most_common.iter().map(|a| !a).collect();
+                }
+
+                report = report.with_new_data(
+                    report
+                        .parsed
+                        .iter()
+                        .filter(|line| line[i] == most_common[i])
+                        .cloned()
+                        .collect(),
+                );
+
+                if report.parsed.len() == 1 {
+                    break;
+                }
+            }
+
+            report.parsed[0].to_vec()
+        }
+    }
+}
+
+fn criterion_benchmark(c: &mut Criterion) {
+    let input = large_input();
+    let report: DiagnosticsReport = input.parse().unwrap();
+    let baseline = baseline::DiagnosticsReport::from(input.as_str());
+
+    // All three must agree before comparing their speed.
+    for (criteria, tie) in [(Criteria::MostCommon, true), (Criteria::LeastCommon, false)] {
+        let rating = report.filter_rating(criteria, tie).unwrap();
+        let expected = baseline.filter_report(tie);
+        assert_eq!(
+            (0..rating.width())
+                .map(|i| rating.get(i))
+                .collect::<Vec<_>>(),
+            expected
+        );
+        assert_eq!(report.trie().rating(criteria, tie), Some(rating));
+    }
+
+    c.bench_function("baseline", |b| {
+        b.iter(|| {
+            (
+                baseline.filter_report(black_box(true)),
+                baseline.filter_report(black_box(false)),
+            )
+        })
+    });
+
+    c.bench_function("filter", |b| {
+        b.iter(|| {
+            (
+                report.filter_rating(black_box(Criteria::MostCommon), true),
+                report.filter_rating(black_box(Criteria::LeastCommon), false),
+            )
+        })
+    });
+
+    c.bench_function("trie", |b| {
+        b.iter(|| {
+            let trie = black_box(&report).trie();
+            (
+                trie.rating(black_box(Criteria::MostCommon), true),
+                trie.rating(black_box(Criteria::LeastCommon), false),
+            )
+        })
+    });
+}
+
+criterion_group!(benches, criterion_benchmark);
+criterion_main!(benches);
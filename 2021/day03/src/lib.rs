@@ -0,0 +1,425 @@
+use std::{cmp::Ordering, fmt::Display, str::FromStr};
+
+use aoc::{Error, ParseError, Solution};
+
+const WORD_BITS: usize = u64::BITS as usize;
+
+/// A row of bits of any width. Column 0 is the most significant bit of the first word, so
+/// comparing words compares rows as binary numbers.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Bits {
+    width: usize,
+    words: Vec<u64>,
+}
+
+impl Bits {
+    fn from_columns(width: usize, mut column: impl FnMut(usize) -> bool) -> Self {
+        let mut words = vec![0; words_for(width)];
+        for i in (0..width).filter(|&i| column(i)) {
+            words[i / WORD_BITS] |= 1 << (WORD_BITS - 1 - i % WORD_BITS);
+        }
+
+        Self { width, words }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn get(&self, column: usize) -> bool {
+        bit(&self.words, column)
+    }
+
+    /// The bits as a number, `None` if there are more than 128 of them.
+    pub fn to_u128(&self) -> Option<u128> {
+        if self.width > u128::BITS as usize {
+            return None;
+        }
+
+        Some((0..self.width).fold(0, |acc, i| acc << 1 | self.get(i) as u128))
+    }
+}
+
+impl Display for Bits {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for i in 0..self.width {
+            write!(f, "{}", self.get(i) as u8)?;
+        }
+
+        Ok(())
+    }
+}
+
+fn words_for(width: usize) -> usize {
+    width.div_ceil(WORD_BITS)
+}
+
+fn bit(words: &[u64], column: usize) -> bool {
+    words[column / WORD_BITS] >> (WORD_BITS - 1 - column % WORD_BITS) & 1 == 1
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Criteria {
+    /// Keep the rows with the more common bit, like the oxygen generator.
+    MostCommon,
+    /// Keep the rows with the less common bit, like the CO2 scrubber.
+    LeastCommon,
+}
+
+impl Criteria {
+    /// The bit to keep given how many rows have a 0 and a 1, `tie` if they are equal. A bit no
+    /// row has is never picked.
+    fn pick(&self, zeros: usize, ones: usize, tie: bool) -> bool {
+        if zeros == 0 || ones == 0 {
+            return ones > 0;
+        }
+
+        match (zeros.cmp(&ones), self) {
+            (Ordering::Equal, _) => tie,
+            (order, Criteria::MostCommon) => order == Ordering::Less,
+            (order, Criteria::LeastCommon) => order == Ordering::Greater,
+        }
+    }
+}
+
+/// Every row packed into `words_per_row` consecutive `u64` words.
+#[derive(Clone, Debug)]
+pub struct DiagnosticsReport {
+    width: usize,
+    words_per_row: usize,
+    rows: Vec<u64>,
+}
+
+impl FromStr for DiagnosticsReport {
+    type Err = ParseError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let mut lines = input
+            .lines()
+            .map(str::trim)
+            .enumerate()
+            .filter(|(_, line)| !line.is_empty())
+            .peekable();
+        let width = lines
+            .peek()
+            .map(|(_, line)| line.len())
+            .ok_or_else(|| ParseError::new(input.lines().count(), "the report is empty"))?;
+        let words_per_row = words_for(width);
+
+        let mut rows = Vec::new();
+        for (i, line) in lines {
+            if line.len() != width {
+                return Err(ParseError::new(
+                    i + 1,
+                    format!("expected {} bits, found {}", width, line.len()),
+                ));
+            }
+            if let Some(c) = line.chars().find(|c| !matches!(c, '0' | '1')) {
+                return Err(ParseError::new(i + 1, format!("{c:?} isn't a bit")));
+            }
+
+            let row = Bits::from_columns(width, |i| line.as_bytes()[i] == b'1');
+            rows.extend(row.words);
+        }
+
+        Ok(Self {
+            width,
+            words_per_row,
+            rows,
+        })
+    }
+}
+
+impl DiagnosticsReport {
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn len(&self) -> usize {
+        self.rows.len() / self.words_per_row.max(1)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+
+    fn row(&self, index: usize) -> &[u64] {
+        &self.rows[index * self.words_per_row..(index + 1) * self.words_per_row]
+    }
+
+    fn bits(&self, index: usize) -> Bits {
+        Bits {
+            width: self.width,
+            words: self.row(index).to_vec(),
+        }
+    }
+
+    fn count_bits(&self) -> Vec<usize> {
+        let mut counters = vec![0; self.width];
+        for index in 0..self.len() {
+            let row = self.row(index);
+            for (column, counter) in counters.iter_mut().enumerate() {
+                *counter += bit(row, column) as usize;
+            }
+        }
+
+        counters
+    }
+
+    fn find_most_common(&self, tie: bool) -> Bits {
+        let counters = self.count_bits();
+        let total = self.len();
+
+        Bits::from_columns(self.width, |i| {
+            let trues = counters[i];
+            if total - trues == trues {
+                tie
+            } else {
+                total - trues < trues
+            }
+        })
+    }
+
+    pub fn gamma(&self) -> Bits {
+        self.find_most_common(false)
+    }
+
+    pub fn epsilon(&self) -> Bits {
+        let gamma = self.gamma();
+        Bits::from_columns(self.width, |i| !gamma.get(i))
+    }
+
+    pub fn gamma_rate(&self) -> Option<u128> {
+        self.gamma().to_u128()
+    }
+
+    pub fn epsilon_rate(&self) -> Option<u128> {
+        self.epsilon().to_u128()
+    }
+
+    /// `None` if the rates or their product don't fit into a `u128`.
+    pub fn power_consumption(&self) -> Option<u128> {
+        self.gamma_rate()?.checked_mul(self.epsilon_rate()?)
+    }
+
+    /// Narrows the rows down one column at a time, the way the puzzle describes it.
+    pub fn filter_rating(&self, criteria: Criteria, tie: bool) -> Option<Bits> {
+        let mut remaining = (0..self.len()).collect::<Vec<_>>();
+
+        for column in 0..self.width {
+            if remaining.len() <= 1 {
+                break;
+            }
+
+            let ones = remaining
+                .iter()
+                .filter(|&&index| bit(self.row(index), column))
+                .count();
+            let keep = criteria.pick(remaining.len() - ones, ones, tie);
+            remaining.retain(|&index| bit(self.row(index), column) == keep);
+        }
+
+        remaining.first().map(|&index| self.bits(index))
+    }
+
+    pub fn trie(&self) -> DiagnosticsTrie {
+        DiagnosticsTrie::new(self)
+    }
+
+    pub fn oxygen_generator_rating(&self) -> Option<u128> {
+        self.trie().rating(Criteria::MostCommon, true)?.to_u128()
+    }
+
+    pub fn co2_scrubber_rating(&self) -> Option<u128> {
+        self.trie().rating(Criteria::LeastCommon, false)?.to_u128()
+    }
+
+    /// `None` if the report is empty or the ratings or their product don't fit into a `u128`.
+    pub fn life_support_rating(&self) -> Option<u128> {
+        let trie = self.trie();
+        let o2_generator = trie.rating(Criteria::MostCommon, true)?.to_u128()?;
+        let co2_scrubber = trie.rating(Criteria::LeastCommon, false)?.to_u128()?;
+
+        o2_generator.checked_mul(co2_scrubber)
+    }
+}
+
+/// The rows in sorted order form an implicit binary trie: all rows below a node share its
+/// prefix and are a contiguous range, so a subtree's count is just the length of its range.
+#[derive(Clone, Debug)]
+pub struct DiagnosticsTrie {
+    width: usize,
+    words_per_row: usize,
+    rows: Vec<u64>,
+}
+
+impl DiagnosticsTrie {
+    fn new(report: &DiagnosticsReport) -> Self {
+        let rows = if report.words_per_row == 1 {
+            let mut rows = report.rows.clone();
+            rows.sort_unstable();
+            rows
+        } else {
+            let mut sorted = (0..report.len()).collect::<Vec<_>>();
+            sorted.sort_unstable_by(|&a, &b| report.row(a).cmp(report.row(b)));
+            sorted
+                .into_iter()
+                .flat_map(|index| report.row(index).iter().copied())
+                .collect()
+        };
+
+        Self {
+            width: report.width,
+            words_per_row: report.words_per_row,
+            rows,
+        }
+    }
+
+    fn row(&self, index: usize) -> &[u64] {
+        &self.rows[index * self.words_per_row..(index + 1) * self.words_per_row]
+    }
+
+    /// The first row in `lo..hi` with a 1 in `column`. All rows in the range have to share
+    /// the bits before `column`.
+    fn split(&self, mut lo: usize, mut hi: usize, column: usize) -> usize {
+        while lo < hi {
+            let mid = lo + (hi - lo) / 2;
+            if bit(self.row(mid), column) {
+                hi = mid;
+            } else {
+                lo = mid + 1;
+            }
+        }
+
+        lo
+    }
+
+    /// Walks down from the root, at every node into the child `criteria` picks from the two
+    /// subtree counts. `tie` is the bit to keep when both are equal.
+    pub fn rating(&self, criteria: Criteria, tie: bool) -> Option<Bits> {
+        let len = self.rows.len() / self.words_per_row.max(1);
+        let (mut lo, mut hi) = (0, len);
+
+        for column in 0..self.width {
+            if hi - lo <= 1 {
+                break;
+            }
+
+            let split = self.split(lo, hi, column);
+            if criteria.pick(split - lo, hi - split, tie) {
+                lo = split;
+            } else {
+                hi = split;
+            }
+        }
+
+        (lo < len).then(|| Bits {
+            width: self.width,
+            words: self.row(lo).to_vec(),
+        })
+    }
+}
+
+impl Solution for DiagnosticsReport {
+    fn parse(input: &str) -> Result<Self, Error> {
+        Ok(input.parse::<DiagnosticsReport>()?)
+    }
+
+    fn part1(&self) -> Result<impl Display, Error> {
+        self.power_consumption()
+            .ok_or_else(|| "power consumption doesn't fit into 128 bits".into())
+    }
+
+    fn part2(&self) -> Result<impl Display, Error> {
+        self.life_support_rating()
+            .ok_or_else(|| "no life support rating".into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_example_input() {
+        let input = "00100
+11110
+10110
+10111
+10101
+01111
+00111
+11100
+10000
+11001
+00010
+01010";
+
+        let report: DiagnosticsReport = input.parse().unwrap();
+
+        assert_eq!(report.len(), 12);
+        assert_eq!(report.width, 5);
+
+        // part1
+        assert_eq!(report.gamma_rate(), Some(22));
+        assert_eq!(report.epsilon_rate(), Some(9));
+        assert_eq!(report.power_consumption(), Some(198));
+
+        // part2
+        assert_eq!(report.oxygen_generator_rating(), Some(23));
+        assert_eq!(report.co2_scrubber_rating(), Some(10));
+        assert_eq!(report.life_support_rating(), Some(230));
+
+        for criteria in [Criteria::MostCommon, Criteria::LeastCommon] {
+            for tie in [false, true] {
+                assert_eq!(
+                    report.trie().rating(criteria, tie),
+                    report.filter_rating(criteria, tie)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_wide_rows() {
+        let rows = [
+            format!("1{}", "0".repeat(199)),
+            format!("1{}1", "0".repeat(198)),
+            format!("0{}", "1".repeat(199)),
+        ];
+        let report: DiagnosticsReport = rows.join("\n").parse().unwrap();
+
+        assert_eq!(report.width(), 200);
+        assert_eq!(report.gamma_rate(), None);
+
+        let oxygen = report.trie().rating(Criteria::MostCommon, true).unwrap();
+        assert_eq!(oxygen.to_string(), rows[1]);
+        let co2 = report.trie().rating(Criteria::LeastCommon, false).unwrap();
+        assert_eq!(co2.to_string(), rows[2]);
+
+        assert_eq!(
+            report.filter_rating(Criteria::MostCommon, true),
+            Some(oxygen)
+        );
+        assert_eq!(
+            report.filter_rating(Criteria::LeastCommon, false),
+            Some(co2)
+        );
+    }
+
+    #[test]
+    fn test_invalid_report() {
+        assert_eq!(
+            "".parse::<DiagnosticsReport>().err(),
+            Some(ParseError::new(0, "the report is empty"))
+        );
+        assert_eq!(
+            "\n0101\n011".parse::<DiagnosticsReport>().err(),
+            Some(ParseError::new(3, "expected 4 bits, found 3"))
+        );
+        assert_eq!(
+            "0101\n0121".parse::<DiagnosticsReport>().err(),
+            Some(ParseError::new(2, "'2' isn't a bit"))
+        );
+    }
+}
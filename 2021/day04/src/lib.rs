@@ -0,0 +1,442 @@
+use std::fmt::{Display, Formatter};
+
+use aoc::{Error, ParseError, Solution};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BingoWin {
+    Row(usize),
+    Column(usize),
+    /// Top left to bottom right.
+    Diagonal,
+    /// Top right to bottom left.
+    AntiDiagonal,
+    FourCorners,
+    FullHouse,
+}
+
+/// Which lines count as a win. Diagonals only count on square cards.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct WinPatterns {
+    pub rows: bool,
+    pub columns: bool,
+    pub diagonals: bool,
+    pub four_corners: bool,
+    pub full_house: bool,
+}
+
+impl Default for WinPatterns {
+    /// Rows and columns, like the submarine's bingo.
+    fn default() -> Self {
+        Self {
+            rows: true,
+            columns: true,
+            diagonals: false,
+            four_corners: false,
+            full_house: false,
+        }
+    }
+}
+
+impl WinPatterns {
+    pub fn full_house() -> Self {
+        Self {
+            rows: false,
+            columns: false,
+            diagonals: false,
+            four_corners: false,
+            full_house: true,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct BingoCard {
+    width: usize,
+    height: usize,
+    numbers: Vec<u32>,
+    marked: Vec<bool>,
+}
+
+impl Display for BingoCard {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), std::fmt::Error> {
+        for (row, row_marks) in self
+            .numbers
+            .chunks(self.width)
+            .zip(self.marked.chunks(self.width))
+        {
+            for (&number, &marked) in row.iter().zip(row_marks.iter()) {
+                if marked {
+                    write!(f, "[{:>2}]", number)?;
+                } else {
+                    write!(f, " {:>2} ", number)?;
+                }
+            }
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl BingoCard {
+    /// `lines` are the card's rows with their line numbers.
+    fn from_lines(lines: &[(usize, &str)]) -> Result<Self, ParseError> {
+        let mut rows: Vec<Vec<u32>> = Vec::new();
+        for &(line, row) in lines {
+            let row = row
+                .split_whitespace()
+                .map(|number| {
+                    number
+                        .parse()
+                        .map_err(|_| ParseError::new(line, format!("invalid number {number:?}")))
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            if let Some(first) = rows.first().filter(|first| first.len() != row.len()) {
+                return Err(ParseError::new(
+                    line,
+                    format!("expected {} numbers, found {}", first.len(), row.len()),
+                ));
+            }
+            rows.push(row);
+        }
+
+        let width = rows.first().map_or(0, Vec::len);
+        Ok(Self {
+            width,
+            height: rows.len(),
+            numbers: rows.concat(),
+            marked: vec![false; width * rows.len()],
+        })
+    }
+
+    pub fn size(&self) -> (usize, usize) {
+        (self.width, self.height)
+    }
+
+    fn is_marked(&self, x: usize, y: usize) -> bool {
+        self.marked[y * self.width + x]
+    }
+
+    pub fn check_win(&self, patterns: &WinPatterns) -> Option<BingoWin> {
+        let (w, h) = (self.width, self.height);
+
+        if patterns.rows {
+            if let Some(y) = (0..h).find(|&y| (0..w).all(|x| self.is_marked(x, y))) {
+                return Some(BingoWin::Row(y));
+            }
+        }
+        if patterns.columns {
+            if let Some(x) = (0..w).find(|&x| (0..h).all(|y| self.is_marked(x, y))) {
+                return Some(BingoWin::Column(x));
+            }
+        }
+        if patterns.diagonals && w == h {
+            if (0..w).all(|i| self.is_marked(i, i)) {
+                return Some(BingoWin::Diagonal);
+            }
+            if (0..w).all(|i| self.is_marked(w - 1 - i, i)) {
+                return Some(BingoWin::AntiDiagonal);
+            }
+        }
+        if patterns.four_corners
+            && w > 0
+            && h > 0
+            && [(0, 0), (w - 1, 0), (0, h - 1), (w - 1, h - 1)]
+                .iter()
+                .all(|&(x, y)| self.is_marked(x, y))
+        {
+            return Some(BingoWin::FourCorners);
+        }
+        if patterns.full_house && self.marked.iter().all(|&marked| marked) {
+            return Some(BingoWin::FullHouse);
+        }
+
+        None
+    }
+
+    pub fn mark(&mut self, drawn_number: u32) {
+        for (&number, marked) in self.numbers.iter().zip(self.marked.iter_mut()) {
+            if number == drawn_number {
+                *marked = true;
+            }
+        }
+    }
+
+    pub fn numbers_with_mark(&self, mark: bool) -> Vec<u32> {
+        self.numbers
+            .iter()
+            .zip(self.marked.iter())
+            .filter(|(_, &marked)| marked == mark)
+            .map(|(&number, _)| number)
+            .collect()
+    }
+}
+
+/// When and how a card won.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CardResult {
+    /// Index into the cards of the input.
+    pub card: usize,
+    /// Index into the drawn numbers.
+    pub draw: usize,
+    pub number: u32,
+    pub win: BingoWin,
+    pub score: u32,
+}
+
+#[derive(Clone, Debug)]
+pub struct Bingo {
+    drawn_numbers: Vec<u32>,
+    cards: Vec<BingoCard>,
+    patterns: WinPatterns,
+}
+
+impl Bingo {
+    /// Every card's size is taken from the input, cards are separated by blank lines.
+    pub fn parse(input: &str) -> Result<Self, ParseError> {
+        let mut lines = input
+            .lines()
+            .enumerate()
+            .map(|(i, line)| (i + 1, line.trim()));
+
+        let (line, draws) = lines
+            .find(|(_, line)| !line.is_empty())
+            .ok_or_else(|| ParseError::new(input.lines().count(), "no drawn numbers"))?;
+        let drawn_numbers = draws
+            .split(',')
+            .map(|n| {
+                n.trim()
+                    .parse()
+                    .map_err(|_| ParseError::new(line, format!("invalid drawn number {n:?}")))
+            })
+            .collect::<Result<_, _>>()?;
+
+        let mut cards = Vec::new();
+        let mut rows = Vec::new();
+        for (line, row) in lines {
+            if !row.is_empty() {
+                rows.push((line, row));
+            } else if !rows.is_empty() {
+                cards.push(BingoCard::from_lines(&rows)?);
+                rows.clear();
+            }
+        }
+        if !rows.is_empty() {
+            cards.push(BingoCard::from_lines(&rows)?);
+        }
+
+        Ok(Self {
+            drawn_numbers,
+            cards,
+            patterns: WinPatterns::default(),
+        })
+    }
+
+    pub fn with_patterns(mut self, patterns: WinPatterns) -> Self {
+        self.patterns = patterns;
+        self
+    }
+
+    pub fn cards(&self) -> &[BingoCard] {
+        &self.cards
+    }
+
+    /// Every card that wins, in the order they win. Cards winning on the same draw are in
+    /// input order.
+    pub fn results(&self) -> Vec<CardResult> {
+        let mut cards = self.cards.clone();
+        let mut results = Vec::new();
+        let mut has_won = vec![false; cards.len()];
+
+        for (draw, &number) in self.drawn_numbers.iter().enumerate() {
+            for (card, (has_won, state)) in has_won.iter_mut().zip(cards.iter_mut()).enumerate() {
+                if *has_won {
+                    continue;
+                }
+
+                state.mark(number);
+                if let Some(win) = state.check_win(&self.patterns) {
+                    *has_won = true;
+                    results.push(CardResult {
+                        card,
+                        draw,
+                        number,
+                        win,
+                        score: calculate_score(number, state),
+                    });
+                }
+            }
+
+            if results.len() == cards.len() {
+                break;
+            }
+        }
+
+        results
+    }
+
+    /// `card` as it looks after `draw` has been marked.
+    pub fn card_after(&self, card: usize, draw: usize) -> BingoCard {
+        let mut state = self.cards[card].clone();
+        for &number in self.drawn_numbers.iter().take(draw + 1) {
+            state.mark(number);
+        }
+
+        state
+    }
+
+    /// The first card to win.
+    pub fn play(&self) -> Option<CardResult> {
+        self.results().first().copied()
+    }
+
+    /// The last card to win.
+    pub fn play_but_let_the_giant_squid_win(&self) -> Option<CardResult> {
+        self.results().last().copied()
+    }
+}
+
+pub fn calculate_score(winning_number: u32, winning_card: &BingoCard) -> u32 {
+    let unmarked_sum = winning_card.numbers_with_mark(false).iter().sum::<u32>();
+
+    winning_number * unmarked_sum
+}
+
+impl Solution for Bingo {
+    fn parse(input: &str) -> Result<Self, Error> {
+        Ok(Bingo::parse(input)?)
+    }
+
+    fn part1(&self) -> Result<impl Display, Error> {
+        let result = self.play().ok_or("no winning card")?;
+        Ok(result.score)
+    }
+
+    fn part2(&self) -> Result<impl Display, Error> {
+        let result = self
+            .play_but_let_the_giant_squid_win()
+            .ok_or("no winning card")?;
+        Ok(result.score)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE_INPUT: &str =
+        "7,4,9,5,11,17,23,2,0,14,21,24,10,16,13,6,15,25,12,22,18,20,8,19,3,26,1
+
+22 13 17 11  0
+ 8  2 23  4 24
+21  9 14 16  7
+ 6 10  3 18  5
+ 1 12 20 15 19
+
+ 3 15  0  2 22
+ 9 18 13 17  5
+19  8  7 25 23
+20 11 10 24  4
+14 21 16 12  6
+
+14 21 17 24  4
+10 16 15  9 19
+18  8 23 26 20
+22 11 13  6  5
+ 2  0 12  3  7";
+
+    #[test]
+    fn test_example_input() {
+        let bingo = Bingo::parse(EXAMPLE_INPUT).unwrap();
+
+        // part1
+        let result = bingo.play().expect("No winning card");
+        let winning_card = bingo.card_after(result.card, result.draw);
+
+        println!("winning card:");
+        println!("{}", winning_card);
+
+        let unmarked_sum = winning_card.numbers_with_mark(false).iter().sum::<u32>();
+
+        assert_eq!(result.number, 24);
+        assert_eq!(result.win, BingoWin::Row(0));
+        assert_eq!(unmarked_sum, 188);
+        assert_eq!(result.score, 4512);
+
+        // part2
+        let result = bingo
+            .play_but_let_the_giant_squid_win()
+            .expect("No winning card");
+        let winning_card = bingo.card_after(result.card, result.draw);
+
+        println!("winning card:");
+        println!("{}", winning_card);
+
+        let unmarked_sum = winning_card.numbers_with_mark(false).iter().sum::<u32>();
+
+        assert_eq!(result.number, 13);
+        assert_eq!(unmarked_sum, 148);
+        assert_eq!(result.score, 1924);
+
+        let order = bingo
+            .results()
+            .iter()
+            .map(|result| (result.card, result.draw))
+            .collect::<Vec<_>>();
+        assert_eq!(order, vec![(2, 11), (0, 13), (1, 14)]);
+    }
+
+    #[test]
+    fn test_variants() {
+        let input = "1,5,9,3,7,10,13,18,21
+
+1 2 3
+4 5 6
+7 8 9
+
+10 11 12 13
+14 15 16 17
+18 19 20 21
+
+20 30  3
+40  5 50
+ 7 60 70";
+        let bingo = Bingo::parse(input).unwrap();
+        assert_eq!(bingo.cards()[0].size(), (3, 3));
+        assert_eq!(bingo.cards()[1].size(), (4, 3));
+        assert!(bingo.results().is_empty());
+
+        let bingo = bingo.with_patterns(WinPatterns {
+            diagonals: true,
+            four_corners: true,
+            ..Default::default()
+        });
+        let wins = bingo
+            .results()
+            .iter()
+            .map(|result| (result.card, result.draw, result.win))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            wins,
+            vec![
+                (0, 2, BingoWin::Diagonal),
+                (2, 4, BingoWin::AntiDiagonal),
+                (1, 8, BingoWin::FourCorners)
+            ]
+        );
+
+        let bingo = bingo.with_patterns(WinPatterns::full_house());
+        assert!(bingo.results().is_empty());
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        assert_eq!(Bingo::parse("").unwrap_err().line, 0);
+        assert_eq!(Bingo::parse("1,x,3\n\n1 2\n3 4").unwrap_err().line, 1);
+        assert_eq!(Bingo::parse("1,2,3\n\n1 2\n3 4 5").unwrap_err().line, 4);
+        assert_eq!(
+            Bingo::parse("1,2,3\n\n1 2\n3 4\n\n1 -2").unwrap_err().line,
+            6
+        );
+    }
+}
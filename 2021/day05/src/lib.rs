@@ -0,0 +1,804 @@
+use lazy_static::lazy_static;
+use regex::Regex;
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::path::Path;
+use std::str::FromStr;
+
+use aoc::{Error, ParseError, Solution};
+use image::{DynamicImage, ImageOutputFormat, Rgb, RgbImage};
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Point {
+    pub x: i32,
+    pub y: i32,
+}
+
+impl Point {
+    pub fn new(x: i32, y: i32) -> Self {
+        Self { x, y }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum VentKind {
+    Horzontal,
+    Vertical,
+    Diagonal,
+    Other,
+}
+
+#[derive(Clone, Debug)]
+pub struct Vent(pub Point, pub Point);
+
+impl FromStr for Vent {
+    type Err = String;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        lazy_static! {
+            // don't compile this regex for every single line
+            static ref RE: Regex =
+                Regex::new(r"^(-?\d+),(-?\d+) -> (-?\d+),(-?\d+)$").unwrap();
+        }
+        let captures = RE
+            .captures(input.trim())
+            .ok_or_else(|| format!("invalid vent {:?}", input.trim()))?;
+        let coordinate = |i: usize| {
+            captures[i]
+                .parse()
+                .map_err(|_| format!("coordinate {} is out of range", &captures[i]))
+        };
+
+        Ok(Self(
+            Point::new(coordinate(1)?, coordinate(2)?),
+            Point::new(coordinate(3)?, coordinate(4)?),
+        ))
+    }
+}
+
+impl Vent {
+    fn is_horizontal(&self) -> bool {
+        self.0.x == self.1.x
+    }
+
+    fn is_vertical(&self) -> bool {
+        self.0.y == self.1.y
+    }
+
+    fn is_diagonal(&self) -> bool {
+        // a 45° degree diagonal vent has the same number of steps in both directions
+        let steps_x = (self.0.x as i64 - self.1.x as i64).abs();
+        let steps_y = (self.0.y as i64 - self.1.y as i64).abs();
+
+        steps_x == steps_y
+    }
+
+    pub fn kind(&self) -> VentKind {
+        if self.is_horizontal() {
+            VentKind::Horzontal
+        } else if self.is_vertical() {
+            VentKind::Vertical
+        } else if self.is_diagonal() {
+            VentKind::Diagonal
+        } else {
+            VentKind::Other
+        }
+    }
+
+    /// Every lattice point on the line, for any slope.
+    ///
+    /// Dividing the distance in both directions by their gcd gives the smallest step that
+    /// still lands on whole coordinates.
+    pub fn points_covered(&self) -> impl Iterator<Item = Point> {
+        let (x0, y0) = (self.0.x as i64, self.0.y as i64);
+        let (steps, step_x, step_y) = self.steps();
+
+        // every point lies between the two end points, so it fits back into an i32
+        (0..=steps)
+            .map(move |step| Point::new((x0 + step * step_x) as i32, (y0 + step * step_y) as i32))
+    }
+
+    /// How many points [`Vent::points_covered`] yields.
+    pub fn point_count(&self) -> u64 {
+        self.steps().0 as u64 + 1
+    }
+
+    /// The number of steps and the step in both directions, in i64 so far apart end points
+    /// don't overflow.
+    fn steps(&self) -> (i64, i64, i64) {
+        let dx = self.1.x as i64 - self.0.x as i64;
+        let dy = self.1.y as i64 - self.0.y as i64;
+
+        let steps = gcd(dx.abs(), dy.abs());
+        if steps == 0 {
+            (0, 0, 0)
+        } else {
+            (steps, dx / steps, dy / steps)
+        }
+    }
+}
+
+fn gcd(a: i64, b: i64) -> i64 {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+pub struct VentField {
+    pub vents: Vec<Vent>,
+}
+
+impl FromStr for VentField {
+    type Err = ParseError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let vents = input
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(i, line)| line.parse().map_err(|e| ParseError::new(i + 1, e)))
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self { vents })
+    }
+}
+
+impl VentField {
+    pub fn with_only_ventkinds_vents(&self, ventkinds: &[VentKind]) -> Self {
+        let filtered_vents = self
+            .vents
+            .iter()
+            .filter(|vent| ventkinds.contains(&vent.kind()))
+            .cloned()
+            .collect();
+
+        Self {
+            vents: filtered_vents,
+        }
+    }
+}
+
+pub struct VentFieldMap(HashMap<Point, usize>);
+
+impl From<&VentField> for VentFieldMap {
+    fn from(field: &VentField) -> Self {
+        let mut map = HashMap::<Point, usize>::new();
+
+        for vent in field.vents.iter() {
+            for point in vent.points_covered() {
+                map.entry(point)
+                    .and_modify(|count| *count += 1)
+                    .or_insert(1);
+            }
+        }
+
+        Self(map)
+    }
+}
+
+impl VentFieldMap {
+    pub fn count_at(&self, point: &Point) -> usize {
+        self.0.get(point).copied().unwrap_or(0)
+    }
+
+    pub fn max_count(&self) -> usize {
+        self.0.values().copied().max().unwrap_or(0)
+    }
+
+    pub fn points_with_overlap(&self, overlap: usize) -> impl Iterator<Item = Point> + '_ {
+        self.0.iter().filter_map(move |(point, &n)| {
+            if n >= overlap {
+                Some(point.clone())
+            } else {
+                None
+            }
+        })
+    }
+}
+
+/// Dense grids are only used while the bounding box has at most this many cells per point
+/// covered by a vent, beyond that most of the grid would stay empty.
+const MAX_CELLS_PER_POINT: u64 = 16;
+
+/// The smallest box containing all vents, or `None` for an empty field.
+fn bounding_box(field: &VentField) -> Option<Bounds> {
+    let mut points = field.vents.iter().flat_map(|vent| [&vent.0, &vent.1]);
+    let first = points.next()?;
+
+    Some(points.fold(
+        Bounds {
+            min: first.clone(),
+            max: first.clone(),
+        },
+        |bounds, p| Bounds {
+            min: Point::new(bounds.min.x.min(p.x), bounds.min.y.min(p.y)),
+            max: Point::new(bounds.max.x.max(p.x), bounds.max.y.max(p.y)),
+        },
+    ))
+}
+
+/// Overlap counter on a dense grid spanning the bounding box of all vents.
+///
+/// Much faster than hashing every point as long as the vents are packed reasonably close.
+pub struct DenseVentGrid {
+    min: Point,
+    width: usize,
+    height: usize,
+    counts: Vec<u32>,
+}
+
+impl DenseVentGrid {
+    /// `None` if the vents are spread out so far that the grid would be mostly empty, use a
+    /// [`VentFieldMap`] for those.
+    pub fn new(field: &VentField) -> Option<Self> {
+        let Some(bounds) = bounding_box(field) else {
+            return Some(Self {
+                min: Point::new(0, 0),
+                width: 0,
+                height: 0,
+                counts: Vec::new(),
+            });
+        };
+
+        let width = (bounds.max.x as i64 - bounds.min.x as i64 + 1) as u64;
+        let height = (bounds.max.y as i64 - bounds.min.y as i64 + 1) as u64;
+        let covered = field.vents.iter().map(Vent::point_count).sum::<u64>();
+        let cells = width
+            .checked_mul(height)
+            .filter(|&cells| cells <= covered.saturating_mul(MAX_CELLS_PER_POINT))?;
+
+        let (width, height) = (width as usize, height as usize);
+        let mut counts = vec![0u32; usize::try_from(cells).ok()?];
+
+        for vent in field.vents.iter() {
+            for point in vent.points_covered() {
+                let x = (point.x as i64 - bounds.min.x as i64) as usize;
+                let y = (point.y as i64 - bounds.min.y as i64) as usize;
+                counts[y * width + x] += 1;
+            }
+        }
+
+        Some(Self {
+            min: bounds.min,
+            width,
+            height,
+            counts,
+        })
+    }
+}
+
+impl DenseVentGrid {
+    /// The smallest box containing all vents, or `None` for an empty field.
+    pub fn bounds(&self) -> Option<Bounds> {
+        (!self.counts.is_empty()).then(|| Bounds {
+            min: self.min.clone(),
+            max: Point::new(
+                (self.min.x as i64 + self.width as i64 - 1) as i32,
+                (self.min.y as i64 + self.height as i64 - 1) as i32,
+            ),
+        })
+    }
+
+    pub fn count_at(&self, point: &Point) -> u32 {
+        let x = point.x as i64 - self.min.x as i64;
+        let y = point.y as i64 - self.min.y as i64;
+
+        if x < 0 || y < 0 || x as usize >= self.width || y as usize >= self.height {
+            0
+        } else {
+            self.counts[y as usize * self.width + x as usize]
+        }
+    }
+
+    pub fn max_count(&self) -> u32 {
+        self.counts.iter().copied().max().unwrap_or(0)
+    }
+
+    pub fn points_with_overlap(&self, overlap: u32) -> impl Iterator<Item = Point> + '_ {
+        self.counts
+            .iter()
+            .enumerate()
+            .filter(move |(_, &n)| n >= overlap)
+            .map(|(i, _)| {
+                Point::new(
+                    (self.min.x as i64 + (i % self.width) as i64) as i32,
+                    (self.min.y as i64 + (i / self.width) as i64) as i32,
+                )
+            })
+    }
+}
+
+/// Overlap counts on a [`DenseVentGrid`] if the vents are packed closely enough, on a
+/// [`VentFieldMap`] otherwise.
+pub enum VentCounts {
+    Dense(DenseVentGrid),
+    Sparse(VentFieldMap),
+}
+
+impl From<&VentField> for VentCounts {
+    fn from(field: &VentField) -> Self {
+        match DenseVentGrid::new(field) {
+            Some(grid) => VentCounts::Dense(grid),
+            None => VentCounts::Sparse(VentFieldMap::from(field)),
+        }
+    }
+}
+
+impl VentCounts {
+    pub fn count_at(&self, point: &Point) -> u32 {
+        match self {
+            VentCounts::Dense(grid) => grid.count_at(point),
+            VentCounts::Sparse(map) => u32::try_from(map.count_at(point)).unwrap_or(u32::MAX),
+        }
+    }
+
+    pub fn max_count(&self) -> u32 {
+        match self {
+            VentCounts::Dense(grid) => grid.max_count(),
+            VentCounts::Sparse(map) => u32::try_from(map.max_count()).unwrap_or(u32::MAX),
+        }
+    }
+
+    /// How many points are covered by at least `overlap` vents.
+    pub fn overlaps(&self, overlap: u32) -> usize {
+        match self {
+            VentCounts::Dense(grid) => grid.points_with_overlap(overlap).count(),
+            VentCounts::Sparse(map) => map.points_with_overlap(overlap as usize).count(),
+        }
+    }
+}
+
+/// An inclusive box of field coordinates.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Bounds {
+    pub min: Point,
+    pub max: Point,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OutputFormat {
+    Png,
+    Ppm,
+}
+
+/// Maps the overlap count of a point to a colour.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Palette {
+    /// Black to white, scaled to the highest count.
+    Grayscale,
+    /// Black over red and yellow to white, scaled to the highest count.
+    Heat,
+    /// One colour per count starting at 0, higher counts use the last one.
+    Steps(Vec<[u8; 3]>),
+}
+
+impl Palette {
+    fn color(&self, count: u32, max: u32) -> Rgb<u8> {
+        let t = if max == 0 {
+            0.0
+        } else {
+            count as f32 / max as f32
+        };
+        let channel = |v: f32| (v.clamp(0.0, 1.0) * 255.0) as u8;
+
+        match self {
+            Palette::Grayscale => Rgb([channel(t); 3]),
+            Palette::Heat => Rgb([
+                channel(3.0 * t),
+                channel(3.0 * t - 1.0),
+                channel(3.0 * t - 2.0),
+            ]),
+            Palette::Steps(colors) => {
+                Rgb(colors[(count as usize).min(colors.len().saturating_sub(1))])
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum RenderError {
+    EmptyPalette,
+    ZeroScale,
+    TooLarge { width: u64, height: u64 },
+    Image(image::ImageError),
+}
+
+impl Display for RenderError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RenderError::EmptyPalette => write!(f, "a step palette needs at least one colour"),
+            RenderError::ZeroScale => write!(f, "scale has to be at least 1"),
+            RenderError::TooLarge { width, height } => {
+                write!(f, "a {width}×{height} image is too large to render")
+            }
+            RenderError::Image(err) => write!(f, "failed to write image: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for RenderError {}
+
+impl From<image::ImageError> for RenderError {
+    fn from(err: image::ImageError) -> Self {
+        Self::Image(err)
+    }
+}
+
+/// Renders the overlap counts of a field as an image, one `scale`×`scale` block per point.
+#[derive(Clone, Debug)]
+pub struct HeatMapRenderer {
+    palette: Palette,
+    scale: u32,
+    bounds: Option<Bounds>,
+    overlay: Option<[u8; 3]>,
+}
+
+impl Default for HeatMapRenderer {
+    fn default() -> Self {
+        Self {
+            palette: Palette::Grayscale,
+            scale: 1,
+            bounds: None,
+            overlay: None,
+        }
+    }
+}
+
+impl HeatMapRenderer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_palette(mut self, palette: Palette) -> Result<Self, RenderError> {
+        if matches!(&palette, Palette::Steps(colors) if colors.is_empty()) {
+            return Err(RenderError::EmptyPalette);
+        }
+        self.palette = palette;
+        Ok(self)
+    }
+
+    pub fn with_scale(mut self, scale: u32) -> Result<Self, RenderError> {
+        if scale == 0 {
+            return Err(RenderError::ZeroScale);
+        }
+        self.scale = scale;
+        Ok(self)
+    }
+
+    /// Only renders the given box instead of the bounding box of all vents.
+    pub fn with_bounds(mut self, bounds: Bounds) -> Self {
+        self.bounds = Some(bounds);
+        self
+    }
+
+    /// Draws every vent as a line between the centers of its end points.
+    pub fn with_overlay(mut self, color: [u8; 3]) -> Self {
+        self.overlay = Some(color);
+        self
+    }
+
+    /// Fails if the image would have more than `u32::MAX` pixels in either direction or more
+    /// bytes than fit in memory.
+    pub fn render(&self, field: &VentField) -> Result<RgbImage, RenderError> {
+        let counts = VentCounts::from(field);
+        let bounds = self
+            .bounds
+            .clone()
+            .or_else(|| bounding_box(field))
+            .unwrap_or(Bounds {
+                min: Point::new(0, 0),
+                max: Point::new(0, 0),
+            });
+        let max = counts.max_count();
+
+        let side = |min: i32, max: i32| (max as i64 - min as i64 + 1).max(0) as u64;
+        let (width, height) = (
+            side(bounds.min.x, bounds.max.x),
+            side(bounds.min.y, bounds.max.y),
+        );
+        let too_large = || RenderError::TooLarge { width, height };
+
+        let width = width
+            .checked_mul(self.scale as u64)
+            .and_then(|w| u32::try_from(w).ok())
+            .ok_or_else(too_large)?;
+        let height = height
+            .checked_mul(self.scale as u64)
+            .and_then(|h| u32::try_from(h).ok())
+            .ok_or_else(too_large)?;
+        (width as usize)
+            .checked_mul(height as usize)
+            .and_then(|pixels| pixels.checked_mul(3))
+            .ok_or_else(too_large)?;
+
+        let mut img = RgbImage::from_fn(width, height, |x, y| {
+            let point = Point::new(
+                bounds.min.x + (x / self.scale) as i32,
+                bounds.min.y + (y / self.scale) as i32,
+            );
+            self.palette.color(counts.count_at(&point), max)
+        });
+
+        if let Some(color) = self.overlay {
+            let center = |p: &Point| {
+                (
+                    (p.x as i64 - bounds.min.x as i64) * self.scale as i64 + self.scale as i64 / 2,
+                    (p.y as i64 - bounds.min.y as i64) * self.scale as i64 + self.scale as i64 / 2,
+                )
+            };
+
+            for vent in field.vents.iter() {
+                draw_line(&mut img, center(&vent.0), center(&vent.1), Rgb(color));
+            }
+        }
+
+        Ok(img)
+    }
+
+    pub fn encode(&self, field: &VentField, format: OutputFormat) -> Result<Vec<u8>, RenderError> {
+        let format = match format {
+            OutputFormat::Png => ImageOutputFormat::Png,
+            OutputFormat::Ppm => ImageOutputFormat::Pnm(image::pnm::PNMSubtype::Pixmap(
+                image::pnm::SampleEncoding::Binary,
+            )),
+        };
+
+        let mut bytes = Vec::new();
+        DynamicImage::ImageRgb8(self.render(field)?).write_to(&mut bytes, format)?;
+
+        Ok(bytes)
+    }
+
+    pub fn save(
+        &self,
+        field: &VentField,
+        path: impl AsRef<Path>,
+        format: OutputFormat,
+    ) -> Result<(), RenderError> {
+        std::fs::write(path, self.encode(field, format)?).map_err(image::ImageError::from)?;
+
+        Ok(())
+    }
+}
+
+/// Bresenham line, clipped to the image.
+fn draw_line(img: &mut RgbImage, from: (i64, i64), to: (i64, i64), color: Rgb<u8>) {
+    let (mut x, mut y) = from;
+    let dx = (to.0 - x).abs();
+    let dy = -(to.1 - y).abs();
+    let (sx, sy) = ((to.0 - x).signum(), (to.1 - y).signum());
+    let mut err = dx + dy;
+
+    loop {
+        if x >= 0 && y >= 0 && x < img.width() as i64 && y < img.height() as i64 {
+            img.put_pixel(x as u32, y as u32, color);
+        }
+        if (x, y) == to {
+            break;
+        }
+
+        let e2 = 2 * err;
+        if e2 >= dy {
+            err += dy;
+            x += sx;
+        }
+        if e2 <= dx {
+            err += dx;
+            y += sy;
+        }
+    }
+}
+
+impl Solution for VentField {
+    fn parse(input: &str) -> Result<Self, Error> {
+        Ok(input.parse::<VentField>()?)
+    }
+
+    fn part1(&self) -> Result<impl Display, Error> {
+        let field = self.with_only_ventkinds_vents(&[VentKind::Horzontal, VentKind::Vertical]);
+        Ok(VentCounts::from(&field).overlaps(2))
+    }
+
+    fn part2(&self) -> Result<impl Display, Error> {
+        let field = self.with_only_ventkinds_vents(&[
+            VentKind::Horzontal,
+            VentKind::Vertical,
+            VentKind::Diagonal,
+        ]);
+        Ok(VentCounts::from(&field).overlaps(2))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE_INPUT: &str = "0,9 -> 5,9
+8,0 -> 0,8
+9,4 -> 3,4
+2,2 -> 2,1
+7,0 -> 7,4
+6,4 -> 2,0
+0,9 -> 2,9
+3,4 -> 1,4
+0,0 -> 8,8
+5,5 -> 8,2";
+
+    #[test]
+    fn test_example_input() {
+        let field: VentField = EXAMPLE_INPUT.parse().unwrap();
+        let hv_field = field.with_only_ventkinds_vents(&[VentKind::Horzontal, VentKind::Vertical]);
+        let hv_map = VentFieldMap::from(&hv_field);
+        assert_eq!(hv_map.points_with_overlap(2).count(), 5);
+
+        let hvd_field = field.with_only_ventkinds_vents(&[
+            VentKind::Horzontal,
+            VentKind::Vertical,
+            VentKind::Diagonal,
+        ]);
+        let hvd_map = VentFieldMap::from(&hvd_field);
+        assert_eq!(hvd_map.points_with_overlap(2).count(), 12);
+
+        let hvd_grid = DenseVentGrid::new(&hvd_field).unwrap();
+        let mut grid_points = hvd_grid.points_with_overlap(2).collect::<Vec<_>>();
+        let mut map_points = hvd_map.points_with_overlap(2).collect::<Vec<_>>();
+        grid_points.sort_by_key(|p| (p.x, p.y));
+        map_points.sort_by_key(|p| (p.x, p.y));
+        assert_eq!(grid_points, map_points);
+    }
+
+    #[test]
+    fn test_far_apart_vents() {
+        let field: VentField = "-2000000000,0 -> -2000000000,2
+2000000000,1 -> 2000000000,-1
+2000000000,0 -> 2000000000,5"
+            .parse()
+            .unwrap();
+        assert!(DenseVentGrid::new(&field).is_none());
+
+        let counts = VentCounts::from(&field);
+        assert!(matches!(counts, VentCounts::Sparse(_)));
+        assert_eq!(counts.overlaps(1), 10);
+        assert_eq!(counts.overlaps(2), 2);
+        assert_eq!(counts.count_at(&Point::new(2000000000, 1)), 2);
+
+        let vent: Vent = "-2147483648,-2147483648 -> 2147483647,2147483647"
+            .parse()
+            .unwrap();
+        assert_eq!(vent.kind(), VentKind::Diagonal);
+        assert_eq!(vent.point_count(), 1 << 32);
+    }
+
+    #[test]
+    fn test_heat_map_golden() {
+        let field: VentField = EXAMPLE_INPUT.parse().unwrap();
+        let renderer = HeatMapRenderer::new()
+            .with_palette(Palette::Steps(vec![
+                [0, 0, 0],
+                [0, 0, 160],
+                [255, 64, 0],
+                [255, 255, 0],
+            ]))
+            .unwrap()
+            .with_scale(3)
+            .unwrap()
+            .with_bounds(Bounds {
+                min: Point::new(-1, -1),
+                max: Point::new(10, 10),
+            })
+            .with_overlay([255, 255, 255]);
+
+        let ppm = renderer.encode(&field, OutputFormat::Ppm).unwrap();
+        assert_eq!(ppm, include_bytes!("../example_heatmap.ppm"));
+
+        let img = renderer.render(&field).unwrap();
+        assert_eq!(img.dimensions(), (36, 36));
+        // (4, 4) is covered by three vents, the top left corner by none
+        assert_eq!(img.get_pixel(5 * 3 + 1, 5 * 3), &Rgb([255, 255, 0]));
+        assert_eq!(img.get_pixel(0, 0), &Rgb([0, 0, 0]));
+    }
+
+    #[test]
+    fn test_invalid_renderer() {
+        assert!(matches!(
+            HeatMapRenderer::new().with_palette(Palette::Steps(vec![])),
+            Err(RenderError::EmptyPalette)
+        ));
+        assert!(matches!(
+            HeatMapRenderer::new().with_scale(0),
+            Err(RenderError::ZeroScale)
+        ));
+
+        let field: VentField = "0,0 -> 1,1".parse().unwrap();
+        let renderer = HeatMapRenderer::new()
+            .with_scale(u32::MAX)
+            .unwrap()
+            .with_bounds(Bounds {
+                min: Point::new(i32::MIN, 0),
+                max: Point::new(i32::MAX, 0),
+            });
+        assert!(matches!(
+            renderer.render(&field),
+            Err(RenderError::TooLarge {
+                width: 4294967296,
+                height: 1
+            })
+        ));
+    }
+
+    #[test]
+    fn test_parse_vent() {
+        let vent_str = "1234,32 -> 1,15";
+        let vent: Vent = vent_str.parse().unwrap();
+
+        assert_eq!(vent.0.x, 1234);
+        assert_eq!(vent.0.y, 32);
+        assert_eq!(vent.1.x, 1);
+        assert_eq!(vent.1.y, 15);
+
+        assert!("1,2 -> 3".parse::<Vent>().is_err());
+        assert!("1,2 -> 3,4000000000".parse::<Vent>().is_err());
+        let error = "1,2 -> 3,4\n\n5,6 => 7,8".parse::<VentField>().err();
+        assert_eq!(error.map(|e| e.line), Some(3));
+    }
+
+    #[test]
+    fn test_kind_and_covered_points() {
+        let testcases = vec![
+            (
+                "1,1 -> 1,3",
+                vec![Point::new(1, 1), Point::new(1, 2), Point::new(1, 3)],
+                VentKind::Horzontal,
+            ),
+            (
+                "1,3 -> 1,1",
+                vec![Point::new(1, 3), Point::new(1, 2), Point::new(1, 1)],
+                VentKind::Horzontal,
+            ),
+            (
+                "9,7 -> 7,7",
+                vec![Point::new(9, 7), Point::new(8, 7), Point::new(7, 7)],
+                VentKind::Vertical,
+            ),
+            (
+                "7,7 -> 9,7",
+                vec![Point::new(7, 7), Point::new(8, 7), Point::new(9, 7)],
+                VentKind::Vertical,
+            ),
+            (
+                "1,1 -> 3,3",
+                vec![Point::new(1, 1), Point::new(2, 2), Point::new(3, 3)],
+                VentKind::Diagonal,
+            ),
+            (
+                "9,7 -> 7,9",
+                vec![Point::new(9, 7), Point::new(8, 8), Point::new(7, 9)],
+                VentKind::Diagonal,
+            ),
+            (
+                "0,0 -> 6,4",
+                vec![Point::new(0, 0), Point::new(3, 2), Point::new(6, 4)],
+                VentKind::Other,
+            ),
+            (
+                "5,-1 -> 2,8",
+                vec![
+                    Point::new(5, -1),
+                    Point::new(4, 2),
+                    Point::new(3, 5),
+                    Point::new(2, 8),
+                ],
+                VentKind::Other,
+            ),
+            ("3,3 -> 3,3", vec![Point::new(3, 3)], VentKind::Horzontal),
+        ];
+        for (vent, points, kind) in testcases {
+            let vent: Vent = vent.parse().unwrap();
+
+            assert_eq!(vent.kind(), kind);
+            assert_eq!(vent.points_covered().collect::<Vec<Point>>(), points);
+        }
+    }
+}
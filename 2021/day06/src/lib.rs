@@ -0,0 +1,361 @@
+use std::{fmt::Display, str::FromStr};
+
+use aoc::{Error, Solution};
+use num_bigint::BigUint;
+
+/// Timer settings of a fish species: a fish spawns when its timer is at 0, then restarts at
+/// `reset_timer` while the new fish starts at `spawn_timer`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Species {
+    pub reset_timer: usize,
+    pub spawn_timer: usize,
+}
+
+impl Species {
+    pub const LANTERNFISH: Species = Species {
+        reset_timer: 6,
+        spawn_timer: 8,
+    };
+
+    fn timers(&self) -> usize {
+        self.reset_timer.max(self.spawn_timer) + 1
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct LanternFishSim {
+    species: Species,
+    lifetimes: Vec<u64>,
+}
+
+impl FromStr for LanternFishSim {
+    type Err = String;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        Self::with_species(input, Species::LANTERNFISH)
+    }
+}
+
+impl LanternFishSim {
+    pub fn with_species(input: &str, species: Species) -> Result<Self, String> {
+        let mut lifetimes = vec![0; species.timers()];
+
+        for fish in input.split(',') {
+            let lifetime = fish
+                .trim()
+                .parse::<usize>()
+                .ok()
+                .and_then(|lifetime| lifetimes.get_mut(lifetime))
+                .ok_or_else(|| format!("invalid timer {:?}", fish.trim()))?;
+
+            *lifetime += 1;
+        }
+
+        Ok(LanternFishSim { species, lifetimes })
+    }
+
+    /// Advances the simulation by a single day, the counts overflow shortly after day 400.
+    pub fn simulate_step(&mut self) {
+        let spawning = self.lifetimes[0];
+
+        self.lifetimes.rotate_left(1);
+        *self.lifetimes.last_mut().unwrap() = 0;
+        self.lifetimes[self.species.reset_timer] += spawning;
+        self.lifetimes[self.species.spawn_timer] += spawning;
+    }
+
+    pub fn count(&self) -> u64 {
+        self.lifetimes.iter().sum()
+    }
+
+    /// The fish count after `days` more days, or `None` if it doesn't fit into a `u128`.
+    pub fn forecast(&self, days: u64) -> Option<u128> {
+        self.forecast_in(&Checked, days)
+    }
+
+    /// The fish count after `days` more days modulo `modulus`.
+    pub fn forecast_mod(&self, days: u64, modulus: u64) -> u64 {
+        assert!(modulus > 0, "modulus has to be positive");
+        self.forecast_in(&Modulo(modulus), days)
+    }
+
+    /// The exact fish count after `days` more days.
+    pub fn forecast_big(&self, days: u64) -> BigUint {
+        self.forecast_in(&Big, days)
+    }
+
+    /// Applies the `days`th power of the transition matrix to the current timers.
+    fn forecast_in<R: Ring>(&self, ring: &R, days: u64) -> R::Value {
+        let transitions = Matrix::transitions(ring, self.species).pow(ring, days);
+        let lifetimes = self
+            .lifetimes
+            .iter()
+            .map(|&n| ring.lift(n))
+            .collect::<Vec<_>>();
+
+        let mut total = ring.zero();
+        for row in 0..transitions.n {
+            for (col, count) in lifetimes.iter().enumerate() {
+                let fish = ring.mul(transitions.get(row, col), count);
+                total = ring.add(&total, &fish);
+            }
+        }
+
+        total
+    }
+}
+
+/// The arithmetic fish are counted in.
+trait Ring {
+    type Value: Clone;
+
+    fn zero(&self) -> Self::Value;
+    fn one(&self) -> Self::Value;
+    fn lift(&self, n: u64) -> Self::Value;
+    fn add(&self, a: &Self::Value, b: &Self::Value) -> Self::Value;
+    fn mul(&self, a: &Self::Value, b: &Self::Value) -> Self::Value;
+}
+
+/// `u128` that stays `None` once anything overflows.
+struct Checked;
+
+impl Ring for Checked {
+    type Value = Option<u128>;
+
+    fn zero(&self) -> Self::Value {
+        Some(0)
+    }
+
+    fn one(&self) -> Self::Value {
+        Some(1)
+    }
+
+    fn lift(&self, n: u64) -> Self::Value {
+        Some(n as u128)
+    }
+
+    fn add(&self, a: &Self::Value, b: &Self::Value) -> Self::Value {
+        a.as_ref()?.checked_add(b.as_ref().copied()?)
+    }
+
+    fn mul(&self, a: &Self::Value, b: &Self::Value) -> Self::Value {
+        a.as_ref()?.checked_mul(b.as_ref().copied()?)
+    }
+}
+
+struct Modulo(u64);
+
+impl Ring for Modulo {
+    type Value = u64;
+
+    fn zero(&self) -> Self::Value {
+        0
+    }
+
+    fn one(&self) -> Self::Value {
+        1 % self.0
+    }
+
+    fn lift(&self, n: u64) -> Self::Value {
+        n % self.0
+    }
+
+    fn add(&self, a: &Self::Value, b: &Self::Value) -> Self::Value {
+        ((*a as u128 + *b as u128) % self.0 as u128) as u64
+    }
+
+    fn mul(&self, a: &Self::Value, b: &Self::Value) -> Self::Value {
+        ((*a as u128 * *b as u128) % self.0 as u128) as u64
+    }
+}
+
+struct Big;
+
+impl Ring for Big {
+    type Value = BigUint;
+
+    fn zero(&self) -> Self::Value {
+        BigUint::from(0u8)
+    }
+
+    fn one(&self) -> Self::Value {
+        BigUint::from(1u8)
+    }
+
+    fn lift(&self, n: u64) -> Self::Value {
+        BigUint::from(n)
+    }
+
+    fn add(&self, a: &Self::Value, b: &Self::Value) -> Self::Value {
+        a + b
+    }
+
+    fn mul(&self, a: &Self::Value, b: &Self::Value) -> Self::Value {
+        a * b
+    }
+}
+
+/// Square matrix, `get(row, col)` is how many fish with timer `row` a fish with timer `col`
+/// turns into.
+struct Matrix<T> {
+    n: usize,
+    data: Vec<T>,
+}
+
+impl<T: Clone> Matrix<T> {
+    fn identity<R: Ring<Value = T>>(ring: &R, n: usize) -> Self {
+        let mut data = vec![ring.zero(); n * n];
+        for i in 0..n {
+            data[i * n + i] = ring.one();
+        }
+
+        Self { n, data }
+    }
+
+    fn transitions<R: Ring<Value = T>>(ring: &R, species: Species) -> Self {
+        let n = species.timers();
+        let mut data = vec![ring.zero(); n * n];
+
+        for timer in 1..n {
+            data[(timer - 1) * n + timer] = ring.one();
+        }
+        for row in [species.reset_timer, species.spawn_timer] {
+            data[row * n] = ring.add(&data[row * n], &ring.one());
+        }
+
+        Self { n, data }
+    }
+
+    fn get(&self, row: usize, col: usize) -> &T {
+        &self.data[row * self.n + col]
+    }
+
+    fn mul<R: Ring<Value = T>>(&self, ring: &R, other: &Self) -> Self {
+        let n = self.n;
+        let mut data = Vec::with_capacity(n * n);
+
+        for row in 0..n {
+            for col in 0..n {
+                let mut sum = ring.zero();
+                for k in 0..n {
+                    sum = ring.add(&sum, &ring.mul(self.get(row, k), other.get(k, col)));
+                }
+                data.push(sum);
+            }
+        }
+
+        Self { n, data }
+    }
+
+    /// Square-and-multiply, `O(log exp)` matrix products.
+    fn pow<R: Ring<Value = T>>(&self, ring: &R, mut exp: u64) -> Self {
+        let mut result = Self::identity(ring, self.n);
+        let mut base = Self {
+            n: self.n,
+            data: self.data.clone(),
+        };
+
+        while exp > 0 {
+            if exp & 1 == 1 {
+                result = result.mul(ring, &base);
+            }
+            exp >>= 1;
+            if exp > 0 {
+                base = base.mul(ring, &base);
+            }
+        }
+
+        result
+    }
+}
+
+impl Solution for LanternFishSim {
+    fn parse(input: &str) -> Result<Self, Error> {
+        Ok(input.parse::<LanternFishSim>()?)
+    }
+
+    fn part1(&self) -> Result<impl Display, Error> {
+        self.forecast(80).ok_or_else(|| "too many fish".into())
+    }
+
+    fn part2(&self) -> Result<impl Display, Error> {
+        self.forecast(256).ok_or_else(|| "too many fish".into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_example_input() {
+        let input = "3,4,3,1,2";
+        let mut sim: LanternFishSim = input.parse().unwrap();
+
+        for _ in 0..80 {
+            sim.simulate_step();
+        }
+
+        assert_eq!(sim.count(), 5934);
+
+        for _ in 80..256 {
+            sim.simulate_step();
+        }
+
+        assert_eq!(sim.count(), 26984457539);
+    }
+
+    #[test]
+    fn test_forecast() {
+        let input = "3,4,3,1,2";
+        let sim: LanternFishSim = input.parse().unwrap();
+
+        assert_eq!(sim.forecast(80), Some(5934));
+        assert_eq!(sim.forecast(256), Some(26984457539));
+        assert_eq!(sim.forecast_big(256), BigUint::from(26984457539u64));
+        assert_eq!(sim.forecast(100_000), None);
+
+        let big = sim.forecast_big(10_000);
+        let modulus = 1_000_000_007;
+        assert_eq!(
+            BigUint::from(sim.forecast_mod(10_000, modulus)),
+            big % modulus
+        );
+    }
+
+    #[test]
+    fn test_invalid_timers() {
+        assert!("3,4,x".parse::<LanternFishSim>().is_err());
+        assert!("3,9".parse::<LanternFishSim>().is_err());
+        assert!("".parse::<LanternFishSim>().is_err());
+    }
+
+    #[test]
+    fn test_forecast_against_stepper() {
+        let species = [
+            Species::LANTERNFISH,
+            Species {
+                reset_timer: 2,
+                spawn_timer: 4,
+            },
+            Species {
+                reset_timer: 5,
+                spawn_timer: 3,
+            },
+        ];
+
+        for species in species {
+            let mut sim = LanternFishSim::with_species("0,1,2,3,3", species).unwrap();
+            let forecaster = LanternFishSim::with_species("0,1,2,3,3", species).unwrap();
+
+            for day in 0..200 {
+                assert_eq!(
+                    forecaster.forecast(day),
+                    Some(sim.count() as u128),
+                    "{species:?} on day {day}"
+                );
+                sim.simulate_step();
+            }
+        }
+    }
+}
@@ -0,0 +1,202 @@
+use std::fmt::Display;
+
+use aoc::{Error, Solution};
+
+/// What is known about the shape of a cost function, used to pick the fastest solver.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Convexity {
+    /// Cost grows linearly with distance, the median is optimal.
+    Linear,
+    /// Cost is `n (n + 1) / 2`, the optimum lies within half a step of the mean.
+    Triangular,
+    /// Any other convex cost, solved by ternary search.
+    Convex,
+}
+
+pub trait FuelCost {
+    /// Fuel needed for a single crab to move `distance` steps.
+    fn cost(&self, distance: usize) -> usize;
+
+    fn convexity(&self) -> Convexity {
+        Convexity::Convex
+    }
+}
+
+pub struct LinearCost;
+
+impl FuelCost for LinearCost {
+    fn cost(&self, distance: usize) -> usize {
+        distance
+    }
+
+    fn convexity(&self) -> Convexity {
+        Convexity::Linear
+    }
+}
+
+pub struct TriangularCost;
+
+impl FuelCost for TriangularCost {
+    fn cost(&self, distance: usize) -> usize {
+        // gauss, could alternatively use (1..(n+1)).sum() (inefficent)
+        (distance * distance + distance) / 2
+    }
+
+    fn convexity(&self) -> Convexity {
+        Convexity::Triangular
+    }
+}
+
+impl<F> FuelCost for F
+where
+    F: Fn(usize) -> usize,
+{
+    fn cost(&self, distance: usize) -> usize {
+        self(distance)
+    }
+}
+
+/// There's always at least one position, an empty input is an invalid one.
+pub fn parse_positions(input: &str) -> Result<Vec<usize>, String> {
+    input
+        .split(',')
+        .map(|n| {
+            n.trim()
+                .parse()
+                .map_err(|_| format!("invalid position {:?}", n.trim()))
+        })
+        .collect()
+}
+
+pub fn total_fuel<C: FuelCost + ?Sized>(positions: &[usize], target: usize, cost: &C) -> usize {
+    positions
+        .iter()
+        .map(|&position| cost.cost(position.abs_diff(target)))
+        .sum()
+}
+
+/// Returns (cheapest_position, total_fuel), using the solver matching the cost's convexity.
+pub fn find_cheapest_position<C: FuelCost + ?Sized>(
+    positions: &[usize],
+    cost: &C,
+) -> (usize, usize) {
+    match cost.convexity() {
+        Convexity::Linear => solve_median(positions, cost),
+        Convexity::Triangular => solve_around_mean(positions, cost),
+        Convexity::Convex => solve_ternary(positions, cost),
+    }
+}
+
+pub fn solve_median<C: FuelCost + ?Sized>(positions: &[usize], cost: &C) -> (usize, usize) {
+    let mut sorted = positions.to_vec();
+    let mid = sorted.len() / 2;
+    let (_, &mut median, _) = sorted.select_nth_unstable(mid);
+
+    (median, total_fuel(positions, median, cost))
+}
+
+pub fn solve_around_mean<C: FuelCost + ?Sized>(positions: &[usize], cost: &C) -> (usize, usize) {
+    let sum = positions.iter().sum::<usize>();
+    let len = positions.len();
+    let (floor, ceil) = (sum / len, sum.div_ceil(len));
+
+    (floor.saturating_sub(1)..=ceil + 1)
+        .map(|target| (target, total_fuel(positions, target, cost)))
+        .min_by_key(|&(_, fuel)| fuel)
+        .unwrap()
+}
+
+/// Integer ternary search between the outermost crabs, the total of convex costs is convex.
+pub fn solve_ternary<C: FuelCost + ?Sized>(positions: &[usize], cost: &C) -> (usize, usize) {
+    let mut lo = *positions.iter().min().unwrap();
+    let mut hi = *positions.iter().max().unwrap();
+
+    while hi - lo > 2 {
+        let m1 = lo + (hi - lo) / 3;
+        let m2 = hi - (hi - lo) / 3;
+
+        if total_fuel(positions, m1, cost) <= total_fuel(positions, m2, cost) {
+            hi = m2;
+        } else {
+            lo = m1;
+        }
+    }
+
+    (lo..=hi)
+        .map(|target| (target, total_fuel(positions, target, cost)))
+        .min_by_key(|&(_, fuel)| fuel)
+        .unwrap()
+}
+
+/// Tries every position between the outermost crabs.
+pub fn brute_force<C: FuelCost + ?Sized>(positions: &[usize], cost: &C) -> (usize, usize) {
+    let lo = *positions.iter().min().unwrap();
+    let hi = *positions.iter().max().unwrap();
+
+    (lo..=hi)
+        .map(|target| (target, total_fuel(positions, target, cost)))
+        .min_by_key(|&(_, fuel)| fuel)
+        .unwrap()
+}
+
+/// The puzzle input, crab positions in any order.
+pub struct CrabPositions(pub Vec<usize>);
+
+impl Solution for CrabPositions {
+    fn parse(input: &str) -> Result<Self, Error> {
+        Ok(Self(parse_positions(input)?))
+    }
+
+    fn part1(&self) -> Result<impl Display, Error> {
+        Ok(find_cheapest_position(&self.0, &LinearCost).1)
+    }
+
+    fn part2(&self) -> Result<impl Display, Error> {
+        Ok(find_cheapest_position(&self.0, &TriangularCost).1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn test_example_input() {
+        let positions = parse_positions("16,1,2,0,4,2,7,1,2,14").unwrap();
+        let (position, fuel) = find_cheapest_position(&positions, &LinearCost);
+
+        assert_eq!(position, 2);
+        assert_eq!(fuel, 37);
+
+        let (position, fuel) = find_cheapest_position(&positions, &TriangularCost);
+
+        assert_eq!(position, 5);
+        assert_eq!(fuel, 168);
+
+        assert!(parse_positions("16,1,x").is_err());
+        assert!(parse_positions(" ").is_err());
+    }
+
+    fn assert_optimal(positions: &[usize], (position, fuel): (usize, usize), cost: &dyn FuelCost) {
+        assert_eq!(total_fuel(positions, position, cost), fuel);
+        assert_eq!(fuel, brute_force(positions, cost).1);
+    }
+
+    proptest! {
+        #[test]
+        fn solvers_match_brute_force(positions in prop::collection::vec(0usize..2000, 1..200)) {
+            assert_optimal(&positions, solve_median(&positions, &LinearCost), &LinearCost);
+            assert_optimal(
+                &positions,
+                solve_around_mean(&positions, &TriangularCost),
+                &TriangularCost,
+            );
+
+            let cubic = |n: usize| n * n * n;
+            for cost in [&LinearCost as &dyn FuelCost, &TriangularCost, &cubic] {
+                assert_optimal(&positions, solve_ternary(&positions, cost), cost);
+            }
+        }
+    }
+}
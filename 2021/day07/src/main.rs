@@ -1,72 +1,24 @@
+use aoc2021_day07::{find_cheapest_position, parse_positions, LinearCost, TriangularCost};
+
 fn main() {
-    let input = include_str!("../input.txt");
-    let (position, fuel) = find_cheapest_position(input, simple_cost_fn);
+    let input = aoc::input(env!("CARGO_MANIFEST_DIR"));
+    let positions = match parse_positions(input) {
+        Ok(positions) => positions,
+        Err(e) => {
+            eprintln!("input.txt {}", e);
+            return;
+        }
+    };
+    let (position, fuel) = find_cheapest_position(&positions, &LinearCost);
 
     println!("Using a too simple cost function:");
     println!("Cheapest position: {}", position);
     println!("Total fuel: {}", fuel);
 
-    let (position, fuel) = find_cheapest_position(input, advanced_cost_fn);
+    let (position, fuel) = find_cheapest_position(&positions, &TriangularCost);
 
     println!();
     println!("Using a more advanced cost function:");
     println!("Cheapest position: {}", position);
     println!("Total fuel: {}", fuel);
 }
-
-fn simple_cost_fn(final_pos: usize, current_pos: usize) -> usize {
-    (final_pos as i64 - current_pos as i64).abs() as usize
-}
-
-fn advanced_cost_fn(final_pos: usize, current_pos: usize) -> usize {
-    let n = (final_pos as i64 - current_pos as i64).abs() as usize;
-
-    // gauss, could alternatively use (1..(n+1)).sum() (inefficent)
-    (n * n + n) / 2
-}
-
-/// Returns (cheapest_position, total_fuel)
-fn find_cheapest_position<F>(input: &str, cost_fn: F) -> (usize, usize)
-where
-    F: Fn(usize, usize) -> usize,
-{
-    let positions = input
-        .split(",")
-        .map(|n| n.trim().parse().unwrap())
-        .collect::<Vec<usize>>();
-
-    let fuels = (0..positions.len())
-        .map(|final_position| {
-            positions
-                .iter()
-                .map(|&position| cost_fn(final_position, position))
-                .sum()
-        })
-        .collect::<Vec<usize>>();
-
-    fuels
-        .iter()
-        .enumerate()
-        .min_by_key(|(_, &val)| val)
-        .map(|(idx, &val)| (idx, val)) // this map is quite ugly, not sure how to avoid it
-        .unwrap()
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_example_input() {
-        let input = "16,1,2,0,4,2,7,1,2,14";
-        let (position, fuel) = find_cheapest_position(input, simple_cost_fn);
-
-        assert_eq!(position, 2);
-        assert_eq!(fuel, 37);
-
-        let (position, fuel) = find_cheapest_position(input, advanced_cost_fn);
-
-        assert_eq!(position, 5);
-        assert_eq!(fuel, 168);
-    }
-}
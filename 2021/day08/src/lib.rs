@@ -0,0 +1,516 @@
+use std::fmt::Display;
+
+use aoc::{Error, Solution};
+
+/// A segment display given as data: the number of segments and which of them every symbol
+/// lights up. Segments and wires are named `a`, `b`, `c`, ... in order.
+#[derive(Clone, Debug)]
+pub struct SegmentDisplay {
+    segments: usize,
+    symbols: Vec<(char, u32)>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum DecodeError {
+    MissingSeparator,
+    UnknownWire(char),
+    /// No wiring maps every pattern onto a symbol.
+    Unsatisfiable,
+    /// Several wirings fit the patterns but read the output differently.
+    Ambiguous(Vec<String>),
+}
+
+impl Display for DecodeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DecodeError::MissingSeparator => write!(f, "missing '|' between patterns and output"),
+            DecodeError::UnknownWire(c) => write!(f, "unknown wire {c:?}"),
+            DecodeError::Unsatisfiable => write!(f, "no wiring fits all patterns"),
+            DecodeError::Ambiguous(readings) => {
+                write!(f, "output could read as any of {}", readings.join(", "))
+            }
+        }
+    }
+}
+
+impl std::error::Error for DecodeError {}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct EntryError {
+    pub line: usize,
+    pub error: DecodeError,
+}
+
+impl Display for EntryError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line {}: {}", self.line, self.error)
+    }
+}
+
+impl std::error::Error for EntryError {}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum DisplayError {
+    /// Segments are stored in a `u32`.
+    TooManySegments(usize),
+    UnknownSegment {
+        symbol: char,
+        segment: char,
+    },
+}
+
+impl Display for DisplayError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DisplayError::TooManySegments(segments) => {
+                write!(f, "a display has at most 32 segments, found {segments}")
+            }
+            DisplayError::UnknownSegment { symbol, segment } => {
+                write!(f, "{symbol:?} uses unknown segment {segment:?}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for DisplayError {}
+
+impl SegmentDisplay {
+    /// `symbols` lists every symbol with the names of its lit segments, like `('1', "cf")`.
+    pub fn new(segments: usize, symbols: &[(char, &str)]) -> Result<Self, DisplayError> {
+        if segments > 32 {
+            return Err(DisplayError::TooManySegments(segments));
+        }
+
+        let symbols = symbols
+            .iter()
+            .map(|&(symbol, lit)| {
+                let mask = wires_to_mask(lit, segments)
+                    .map_err(|segment| DisplayError::UnknownSegment { symbol, segment })?;
+                Ok((symbol, mask))
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self { segments, symbols })
+    }
+
+    pub fn seven_segment() -> Self {
+        Self::new(
+            7,
+            &[
+                ('0', "abcefg"),
+                ('1', "cf"),
+                ('2', "acdeg"),
+                ('3', "acdfg"),
+                ('4', "bcdf"),
+                ('5', "abdfg"),
+                ('6', "abdefg"),
+                ('7', "acf"),
+                ('8', "abcdefg"),
+                ('9', "abcdfg"),
+            ],
+        )
+        .expect("the seven segment display only uses segments a to g")
+    }
+
+    fn full(&self) -> u32 {
+        ((1u64 << self.segments) - 1) as u32
+    }
+
+    fn symbol(&self, mask: u32) -> Option<char> {
+        self.symbols
+            .iter()
+            .find(|(_, symbol)| *symbol == mask)
+            .map(|(c, _)| *c)
+    }
+
+    /// Finds the wiring and reads the output.
+    ///
+    /// Every pattern and every output has to show one of the display's symbols. The wiring is
+    /// searched by narrowing down which segments each wire can drive and branching on the wire
+    /// with the fewest candidates left.
+    pub fn decode(&self, patterns: &[&str], outputs: &[&str]) -> Result<Vec<char>, DecodeError> {
+        let parse = |patterns: &[&str]| {
+            patterns
+                .iter()
+                .map(|pattern| {
+                    wires_to_mask(pattern, self.segments).map_err(DecodeError::UnknownWire)
+                })
+                .collect::<Result<Vec<_>, _>>()
+        };
+        let outputs = parse(outputs)?;
+        let mut observed = parse(patterns)?;
+        observed.extend_from_slice(&outputs);
+
+        let mut readings = Vec::new();
+        self.search(
+            vec![self.full(); self.segments],
+            &observed,
+            &outputs,
+            &mut readings,
+        );
+
+        match readings.len() {
+            0 => Err(DecodeError::Unsatisfiable),
+            1 => Ok(readings.pop().unwrap()),
+            _ => Err(DecodeError::Ambiguous(
+                readings.into_iter().map(String::from_iter).collect(),
+            )),
+        }
+    }
+
+    /// Symbols a pattern can still show with the current candidates.
+    fn feasible_symbols<'a>(
+        &'a self,
+        candidates: &'a [u32],
+        pattern: u32,
+    ) -> impl Iterator<Item = u32> + 'a {
+        let full = self.full();
+
+        self.symbols
+            .iter()
+            .map(|(_, symbol)| *symbol)
+            .filter(move |symbol| symbol.count_ones() == pattern.count_ones())
+            .filter(move |symbol| {
+                candidates.iter().enumerate().all(|(wire, candidate)| {
+                    if pattern & (1 << wire) != 0 {
+                        candidate & symbol != 0
+                    } else {
+                        candidate & !symbol & full != 0
+                    }
+                })
+            })
+    }
+
+    /// Narrows the candidates until nothing changes, `false` on a contradiction.
+    fn propagate(&self, candidates: &mut [u32], observed: &[u32]) -> bool {
+        let full = self.full();
+
+        loop {
+            let before = candidates.to_vec();
+
+            for &pattern in observed {
+                let (lit, unlit) = self
+                    .feasible_symbols(candidates, pattern)
+                    .fold((0, 0), |(lit, unlit), symbol| {
+                        (lit | symbol, unlit | (!symbol & full))
+                    });
+
+                for (wire, candidate) in candidates.iter_mut().enumerate() {
+                    *candidate &= if pattern & (1 << wire) != 0 {
+                        lit
+                    } else {
+                        unlit
+                    };
+                }
+            }
+
+            // a segment that is certain for one wire can't be driven by any other
+            for wire in 0..candidates.len() {
+                if candidates[wire].count_ones() == 1 {
+                    let segment = candidates[wire];
+                    for (other, candidate) in candidates.iter_mut().enumerate() {
+                        if other != wire {
+                            *candidate &= !segment;
+                        }
+                    }
+                }
+            }
+
+            if candidates.contains(&0) {
+                return false;
+            }
+            if candidates == before.as_slice() {
+                return true;
+            }
+        }
+    }
+
+    /// Collects the distinct readings of `outputs`, stops as soon as there are two.
+    fn search(
+        &self,
+        mut candidates: Vec<u32>,
+        observed: &[u32],
+        outputs: &[u32],
+        readings: &mut Vec<Vec<char>>,
+    ) {
+        if readings.len() > 1 || !self.propagate(&mut candidates, observed) {
+            return;
+        }
+
+        let undecided = candidates
+            .iter()
+            .enumerate()
+            .filter(|(_, candidate)| candidate.count_ones() > 1)
+            .min_by_key(|(_, candidate)| candidate.count_ones());
+
+        match undecided {
+            Some((wire, &candidate)) => {
+                for segment in (0..self.segments).filter(|s| candidate & (1 << s) != 0) {
+                    let mut branch = candidates.clone();
+                    branch[wire] = 1 << segment;
+                    self.search(branch, observed, outputs, readings);
+                }
+            }
+            None => {
+                let rewire = |pattern: u32| {
+                    (0..self.segments)
+                        .filter(|wire| pattern & (1 << wire) != 0)
+                        .fold(0, |mask, wire| mask | candidates[wire])
+                };
+
+                let valid = observed
+                    .iter()
+                    .all(|&pattern| self.symbol(rewire(pattern)).is_some());
+                if valid {
+                    let reading = outputs
+                        .iter()
+                        .map(|&pattern| self.symbol(rewire(pattern)).unwrap())
+                        .collect::<Vec<_>>();
+                    if !readings.contains(&reading) {
+                        readings.push(reading);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Fails with the first wire past the last segment.
+fn wires_to_mask(wires: &str, segments: usize) -> Result<u32, char> {
+    wires.chars().try_fold(0, |mask, c| {
+        let index = (c as u32).wrapping_sub('a' as u32);
+        if (index as usize) < segments {
+            Ok(mask | 1 << index)
+        } else {
+            Err(c)
+        }
+    })
+}
+
+fn parse_line(line: &str) -> Result<(Vec<&str>, Vec<&str>), DecodeError> {
+    let (patterns, outputs) = line.split_once('|').ok_or(DecodeError::MissingSeparator)?;
+    let patterns = patterns.split_whitespace();
+    let outputs = outputs.split_whitespace();
+
+    Ok((patterns.collect(), outputs.collect()))
+}
+
+/// Decodes every line on the given display.
+pub fn decode_entries(input: &str, display: &SegmentDisplay) -> Result<Vec<Vec<char>>, EntryError> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| {
+            parse_line(line)
+                .and_then(|(patterns, outputs)| display.decode(&patterns, &outputs))
+                .map_err(|error| EntryError { line: i + 1, error })
+        })
+        .collect()
+}
+
+pub fn parse_input(input: &str) -> Result<Vec<Vec<u8>>, EntryError> {
+    Ok(decode_entries(input, &SegmentDisplay::seven_segment())?
+        .into_iter()
+        .map(|output| {
+            output
+                .into_iter()
+                .map(|c| c.to_digit(10).unwrap() as u8)
+                .collect()
+        })
+        .collect())
+}
+
+pub fn count_simple_digits(outputs: &[Vec<u8>]) -> usize {
+    outputs
+        .iter()
+        .flat_map(|output| {
+            output
+                .iter()
+                .filter(|&&digit| digit == 1 || digit == 4 || digit == 7 || digit == 8)
+        })
+        .count()
+}
+
+pub fn make_digits(outputs: &[Vec<u8>]) -> Vec<u64> {
+    outputs.iter().map(|digits| digits_to_int(digits)).collect()
+}
+
+fn digits_to_int(digits: &[u8]) -> u64 {
+    digits
+        .iter()
+        .rev()
+        .enumerate()
+        .map(|(i, &d)| d as u64 * 10_u64.pow(i as u32))
+        .sum()
+}
+
+/// The decoded output digits of every entry.
+pub struct Outputs(pub Vec<Vec<u8>>);
+
+impl Solution for Outputs {
+    fn parse(input: &str) -> Result<Self, Error> {
+        Ok(Self(parse_input(input)?))
+    }
+
+    fn part1(&self) -> Result<impl Display, Error> {
+        Ok(count_simple_digits(&self.0))
+    }
+
+    fn part2(&self) -> Result<impl Display, Error> {
+        Ok(make_digits(&self.0).iter().sum::<u64>())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_example_input() {
+        let input =
+            "be cfbegad cbdgef fgaecd cgeb fdcge agebfd fecdb fabcd edb | fdgacbe cefdb cefbgd gcbe
+edbfga begcd cbg gc gcadebf fbgde acbgfd abcde gfcbed gfec | fcgedb cgb dgebacf gc
+fgaebd cg bdaec gdafb agbcfd gdcbef bgcad gfac gcb cdgabef | cg cg fdcagb cbg
+fbegcd cbd adcefb dageb afcb bc aefdc ecdab fgdeca fcdbega | efabcd cedba gadfec cb
+aecbfdg fbg gf bafeg dbefa fcge gcbea fcaegb dgceab fcbdga | gecf egdcabf bgf bfgea
+fgeab ca afcebg bdacfeg cfaedg gcfdb baec bfadeg bafgc acf | gebdcfa ecba ca fadegcb
+dbcfg fgd bdegcaf fgec aegbdf ecdfab fbedc dacgb gdcebf gf | cefg dcbef fcge gbcadfe
+bdfegc cbegaf gecbf dfcage bdacg ed bedf ced adcbefg gebcd | ed bcgafe cdgba cbgef
+egadfb cdbfeg cegd fecab cgb gbdefca cg fgcdab egfdb bfceg | gbdfcae bgc cg cgb
+gcafb gcf dcaebfg ecagb gf abcdeg gaef cafbge fdbac fegbdc | fgae cfgab fg bagce";
+
+        let input2 =
+            "acedgfb cdfbe gcdfa fbcad dab cefabd cdfgeb eafb cagedb ab | cdfeb fcadb cdfeb cdbaf";
+
+        let outputs = parse_input(input2).unwrap();
+        assert_eq!(outputs[0], vec![5, 3, 5, 3]);
+
+        let outputs = parse_input(input).unwrap();
+        assert_eq!(outputs[0], vec![8, 3, 9, 4]);
+
+        let count = count_simple_digits(&outputs);
+        assert_eq!(count, 26);
+
+        let digits = make_digits(&outputs);
+        assert_eq!(digits[0], 8394);
+        assert_eq!(digits.iter().sum::<u64>(), 61229);
+    }
+
+    #[test]
+    fn test_digits_to_int() {
+        let input = vec![1, 2, 3, 4];
+        assert_eq!(digits_to_int(&input), 1234);
+
+        let input = vec![7, 1, 9, 3, 5];
+        assert_eq!(digits_to_int(&input), 71935);
+    }
+
+    /// Rewires a pattern with `wiring[segment] = wire`.
+    fn scramble(pattern: &str, wiring: &[char]) -> String {
+        pattern
+            .chars()
+            .map(|c| wiring[(c as u8 - b'a') as usize])
+            .collect()
+    }
+
+    #[test]
+    fn test_fourteen_segments() {
+        let display = SegmentDisplay::new(
+            14,
+            &[
+                ('0', "abcdefkl"),
+                ('1', "bc"),
+                ('2', "abdegh"),
+                ('3', "abcdh"),
+                ('4', "bcfgh"),
+                ('5', "adfgn"),
+                ('6', "acdefgh"),
+                ('7', "abc"),
+                ('8', "abcdefgh"),
+                ('9', "abcdfgh"),
+                ('A', "abcefgh"),
+                ('B', "abcdhjm"),
+                ('C', "adef"),
+                ('D', "abcdjm"),
+                ('E', "adefg"),
+                ('H', "bcefgh"),
+                ('I', "adjm"),
+                ('K', "efgkn"),
+                ('M', "bcefik"),
+                ('N', "bcefin"),
+                ('X', "ikln"),
+                ('Z', "adkl"),
+            ],
+        )
+        .unwrap();
+        let wiring = "kdhbnlagcmjefi".chars().collect::<Vec<_>>();
+
+        let patterns = display
+            .symbols
+            .iter()
+            .map(|(_, mask)| {
+                let segments = (0..14)
+                    .filter(|s| mask & (1 << s) != 0)
+                    .map(|s| (b'a' + s as u8) as char)
+                    .collect::<String>();
+                scramble(&segments, &wiring)
+            })
+            .collect::<Vec<_>>();
+        let patterns = patterns.iter().map(String::as_str).collect::<Vec<_>>();
+        let outputs = ["adefg", "bcefgh", "adkl", "abc"].map(|o| scramble(o, &wiring));
+        let outputs = outputs.iter().map(String::as_str).collect::<Vec<_>>();
+
+        assert_eq!(
+            display.decode(&patterns, &outputs),
+            Ok(vec!['E', 'H', 'Z', '7'])
+        );
+    }
+
+    #[test]
+    fn test_invalid_display() {
+        assert_eq!(
+            SegmentDisplay::new(33, &[]).err(),
+            Some(DisplayError::TooManySegments(33))
+        );
+        assert_eq!(
+            SegmentDisplay::new(3, &[('1', "ab"), ('7', "abd")]).err(),
+            Some(DisplayError::UnknownSegment {
+                symbol: '7',
+                segment: 'd'
+            })
+        );
+    }
+
+    #[test]
+    fn test_custom_font_and_errors() {
+        let display = SegmentDisplay::new(
+            5,
+            &[('L', "ab"), ('T', "ac"), ('U', "abcd"), ('O', "abcde")],
+        )
+        .unwrap();
+        assert_eq!(display.decode(&["ed", "eb"], &["ebcd"]), Ok(vec!['U']));
+
+        // `b` and `c` are only told apart by which symbol a two-wire pattern is
+        assert_eq!(
+            display.decode(&["ab"], &["ab"]),
+            Err(DecodeError::Ambiguous(vec!["L".to_owned(), "T".to_owned()]))
+        );
+
+        let seven = SegmentDisplay::seven_segment();
+        assert_eq!(
+            seven.decode(&["a"], &["ab"]),
+            Err(DecodeError::Unsatisfiable)
+        );
+        assert_eq!(
+            seven.decode(&["ab"], &["ax"]),
+            Err(DecodeError::UnknownWire('x'))
+        );
+        assert_eq!(
+            parse_input("ab cd\nab"),
+            Err(EntryError {
+                line: 1,
+                error: DecodeError::MissingSeparator
+            })
+        );
+    }
+}
@@ -0,0 +1,431 @@
+use std::{fmt::Display, io::Write};
+
+use aoc::{Error, ParseError, Solution};
+
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Point {
+    pub x: usize,
+    pub y: usize,
+}
+
+impl Point {
+    pub fn new(x: usize, y: usize) -> Self {
+        Self { x, y }
+    }
+}
+
+pub struct HeightMap {
+    map: Vec<Vec<u32>>,
+    width: usize,
+    height: usize,
+}
+
+/// Disjoint sets over cell indices, with path halving and union by size.
+struct UnionFind {
+    parent: Vec<usize>,
+    size: Vec<usize>,
+}
+
+impl UnionFind {
+    fn new(n: usize) -> Self {
+        Self {
+            parent: (0..n).collect(),
+            size: vec![1; n],
+        }
+    }
+
+    fn find(&mut self, mut i: usize) -> usize {
+        while self.parent[i] != i {
+            self.parent[i] = self.parent[self.parent[i]];
+            i = self.parent[i];
+        }
+
+        i
+    }
+
+    fn union(&mut self, a: usize, b: usize) {
+        let (mut a, mut b) = (self.find(a), self.find(b));
+        if a == b {
+            return;
+        }
+
+        if self.size[a] < self.size[b] {
+            std::mem::swap(&mut a, &mut b);
+        }
+        self.parent[b] = a;
+        self.size[a] += self.size[b];
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Basin {
+    pub low_point: Point,
+    pub area: usize,
+    /// Height difference between the highest and the lowest cell of the basin.
+    pub depth: u32,
+}
+
+/// Every cell labelled with the index of its basin, `None` for the height 9 walls.
+#[derive(Clone, Debug)]
+pub struct BasinLabels {
+    width: usize,
+    height: usize,
+    labels: Vec<Option<usize>>,
+    pub basins: Vec<Basin>,
+}
+
+impl BasinLabels {
+    pub fn label(&self, point: &Point) -> Option<usize> {
+        self.labels[point.y * self.width + point.x]
+    }
+
+    /// Writes a binary PPM with one colour per basin and the walls in black.
+    pub fn write_ppm<W: Write>(&self, mut writer: W, scale: usize) -> std::io::Result<()> {
+        let colors = (0..self.basins.len()).map(basin_color).collect::<Vec<_>>();
+
+        write!(
+            writer,
+            "P6\n{} {}\n255\n",
+            self.width * scale,
+            self.height * scale
+        )?;
+
+        let mut row = Vec::with_capacity(self.width * scale * 3);
+        for y in 0..self.height {
+            row.clear();
+            for x in 0..self.width {
+                let color = match self.labels[y * self.width + x] {
+                    Some(label) => colors[label],
+                    None => [0, 0, 0],
+                };
+                for _ in 0..scale {
+                    row.extend_from_slice(&color);
+                }
+            }
+
+            for _ in 0..scale {
+                writer.write_all(&row)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Spreads the hues by the golden angle so neighbouring labels get clearly different colours.
+fn basin_color(label: usize) -> [u8; 3] {
+    let hue = (label as f64 * 0.618_033_988_749_895).fract() * 6.0;
+    let x = 1.0 - (hue % 2.0 - 1.0).abs();
+    let (r, g, b) = match hue as u32 {
+        0 => (1.0, x, 0.0),
+        1 => (x, 1.0, 0.0),
+        2 => (0.0, 1.0, x),
+        3 => (0.0, x, 1.0),
+        4 => (x, 0.0, 1.0),
+        _ => (1.0, 0.0, x),
+    };
+
+    // keep the colours a bit lighter than fully saturated so they don't get close to the walls
+    [r, g, b].map(|c| (64.0 + c * 191.0) as u8)
+}
+
+impl HeightMap {
+    pub fn parse_input(input: &str) -> Result<Self, ParseError> {
+        let mut map: Vec<Vec<u32>> = Vec::new();
+        for (i, line) in input.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            let row = line
+                .chars()
+                .map(|c| {
+                    c.to_digit(10)
+                        .ok_or_else(|| ParseError::new(i + 1, format!("{c:?} isn't a height")))
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            if let Some(first) = map.first().filter(|first| first.len() != row.len()) {
+                return Err(ParseError::new(
+                    i + 1,
+                    format!("expected {} heights, found {}", first.len(), row.len()),
+                ));
+            }
+            map.push(row);
+        }
+
+        let width = map
+            .first()
+            .map(Vec::len)
+            .ok_or_else(|| ParseError::new(input.lines().count(), "the map is empty"))?;
+        let height = map.len();
+
+        Ok(Self { map, width, height })
+    }
+
+    fn is_in_bounds(&self, x: isize, y: isize) -> bool {
+        x >= 0 && x < self.width as isize && y >= 0 && y < self.height as isize
+    }
+
+    fn is_lowspot(&self, x: usize, y: usize) -> bool {
+        let val = self.map[y][x];
+
+        [(-1isize, 0isize), (1, 0), (0, 1), (0, -1)]
+            .map(|(dy, dx)| ((y as isize + dy), (x as isize + dx)))
+            .iter()
+            .filter(|(ny, nx)| self.is_in_bounds(*nx, *ny))
+            .all(|(ny, nx)| val < self.map[*ny as usize][*nx as usize])
+    }
+
+    pub fn find_lowspots(&self) -> Vec<Point> {
+        (0..self.height)
+            .flat_map(|y| {
+                (0..self.width).filter_map(move |x| {
+                    if self.is_lowspot(x, y) {
+                        Some(Point::new(x, y))
+                    } else {
+                        None
+                    }
+                })
+            })
+            .collect()
+    }
+
+    /// Labels the basins in a single pass: every cell that isn't a wall is joined with its right
+    /// and lower neighbour, so each connected region between the walls becomes one basin.
+    pub fn find_basins(&self) -> BasinLabels {
+        let index = |x: usize, y: usize| y * self.width + x;
+        let mut sets = UnionFind::new(self.width * self.height);
+
+        for y in 0..self.height {
+            for x in 0..self.width {
+                if self.map[y][x] == 9 {
+                    continue;
+                }
+                if x + 1 < self.width && self.map[y][x + 1] != 9 {
+                    sets.union(index(x, y), index(x + 1, y));
+                }
+                if y + 1 < self.height && self.map[y + 1][x] != 9 {
+                    sets.union(index(x, y), index(x, y + 1));
+                }
+            }
+        }
+
+        let mut label_of_root = vec![None; self.width * self.height];
+        let mut labels = vec![None; self.width * self.height];
+        let mut basins = Vec::<Basin>::new();
+        // highest cell per basin, turned into the depth at the end
+        let mut highest = Vec::new();
+
+        for y in 0..self.height {
+            for x in 0..self.width {
+                let value = self.map[y][x];
+                if value == 9 {
+                    continue;
+                }
+
+                let root = sets.find(index(x, y));
+                let label = *label_of_root[root].get_or_insert_with(|| {
+                    basins.push(Basin {
+                        low_point: Point::new(x, y),
+                        area: 0,
+                        depth: 0,
+                    });
+                    highest.push(value);
+                    basins.len() - 1
+                });
+                labels[index(x, y)] = Some(label);
+
+                let basin = &mut basins[label];
+                basin.area += 1;
+                if value < self.map[basin.low_point.y][basin.low_point.x] {
+                    basin.low_point = Point::new(x, y);
+                }
+                highest[label] = highest[label].max(value);
+            }
+        }
+
+        for (basin, highest) in basins.iter_mut().zip(highest) {
+            basin.depth = highest - self.map[basin.low_point.y][basin.low_point.x];
+        }
+
+        BasinLabels {
+            width: self.width,
+            height: self.height,
+            labels,
+            basins,
+        }
+    }
+
+    pub fn risk_level(&self, lowspots: &[Point]) -> u32 {
+        lowspots
+            .iter()
+            .map(|point| self.map[point.y][point.x] + 1)
+            .sum()
+    }
+
+    /// `None` if there are fewer than three basins.
+    pub fn largest_basin_product(&self, basins: &BasinLabels) -> Option<usize> {
+        let mut sizes = basins
+            .basins
+            .iter()
+            .map(|basin| basin.area)
+            .collect::<Vec<usize>>();
+
+        sizes.sort_by(|a, b| b.cmp(a));
+
+        sizes.get(0..3).map(|largest| largest.iter().product())
+    }
+
+    pub fn display<F>(&self, mark: F)
+    where
+        F: Fn(Point) -> bool,
+    {
+        for y in 0..self.height {
+            for x in 0..self.width {
+                if mark(Point::new(x, y)) {
+                    print!("*");
+                } else {
+                    print!("{}", self.map[y][x]);
+                }
+            }
+
+            println!()
+        }
+    }
+}
+
+impl Solution for HeightMap {
+    fn parse(input: &str) -> Result<Self, Error> {
+        Ok(HeightMap::parse_input(input)?)
+    }
+
+    fn part1(&self) -> Result<impl Display, Error> {
+        Ok(self.risk_level(&self.find_lowspots()))
+    }
+
+    fn part2(&self) -> Result<impl Display, Error> {
+        self.largest_basin_product(&self.find_basins())
+            .ok_or_else(|| "fewer than three basins".into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE_INPUT: &str = "2199943210
+3987894921
+9856789892
+8767896789
+9899965678";
+
+    #[test]
+    fn parse_example_input() {
+        let map = HeightMap::parse_input(EXAMPLE_INPUT).unwrap();
+        let lowspots = map.find_lowspots();
+        let risk_level = map.risk_level(&lowspots);
+
+        assert_eq!(risk_level, 15);
+
+        println!("Map:");
+        map.display(|_| false);
+        println!();
+
+        println!("Lowspots marked:");
+        map.display(|p| lowspots.contains(&p));
+        println!();
+
+        let basins = map.find_basins();
+
+        println!("{:?}", basins.basins);
+
+        assert_eq!(map.largest_basin_product(&basins), Some(1134));
+    }
+
+    #[test]
+    fn test_too_few_basins() {
+        let map = HeightMap::parse_input("109\n999").unwrap();
+        assert_eq!(map.largest_basin_product(&map.find_basins()), None);
+        assert!(map.part2().is_err());
+    }
+
+    #[test]
+    fn test_basin_labels() {
+        let map = HeightMap::parse_input(EXAMPLE_INPUT).unwrap();
+        let labels = map.find_basins();
+
+        assert_eq!(
+            labels.basins,
+            vec![
+                Basin {
+                    low_point: Point::new(1, 0),
+                    area: 3,
+                    depth: 2,
+                },
+                Basin {
+                    low_point: Point::new(9, 0),
+                    area: 9,
+                    depth: 4,
+                },
+                Basin {
+                    low_point: Point::new(2, 2),
+                    area: 14,
+                    depth: 3,
+                },
+                Basin {
+                    low_point: Point::new(6, 4),
+                    area: 9,
+                    depth: 3,
+                },
+            ]
+        );
+
+        let mut low_points = labels
+            .basins
+            .iter()
+            .map(|basin| basin.low_point.clone())
+            .collect::<Vec<_>>();
+        low_points.sort();
+        let mut lowspots = map.find_lowspots();
+        lowspots.sort();
+        assert_eq!(low_points, lowspots);
+
+        assert_eq!(labels.label(&Point::new(0, 0)), Some(0));
+        assert_eq!(labels.label(&Point::new(2, 0)), None);
+        assert_eq!(labels.label(&Point::new(7, 3)), Some(3));
+    }
+
+    #[test]
+    fn test_write_ppm() {
+        let labels = HeightMap::parse_input(EXAMPLE_INPUT).unwrap().find_basins();
+
+        let mut ppm = Vec::new();
+        labels.write_ppm(&mut ppm, 2).unwrap();
+
+        let header = b"P6\n20 10\n255\n";
+        assert_eq!(&ppm[..header.len()], header);
+        assert_eq!(ppm.len(), header.len() + 20 * 10 * 3);
+
+        let pixel = |x: usize, y: usize| {
+            let i = header.len() + (y * 20 + x) * 3;
+            [ppm[i], ppm[i + 1], ppm[i + 2]]
+        };
+        assert_eq!(pixel(4, 0), [0, 0, 0]);
+        assert_eq!(pixel(0, 0), pixel(1, 1));
+        assert_ne!(pixel(0, 0), pixel(19, 0));
+    }
+
+    #[test]
+    fn test_invalid_map() {
+        assert_eq!(HeightMap::parse_input("").err().map(|e| e.line), Some(0));
+        assert_eq!(
+            HeightMap::parse_input("123\n12a").err().map(|e| e.line),
+            Some(2)
+        );
+        assert_eq!(
+            HeightMap::parse_input("123\n\n12").err().map(|e| e.line),
+            Some(3)
+        );
+    }
+}
@@ -0,0 +1,480 @@
+use std::fmt::{Display, Write};
+
+use aoc::{Error, Solution};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BracketType {
+    Round,
+    Square,
+    Curly,
+    Angle,
+}
+
+impl BracketType {
+    fn syntax_error_value(&self) -> u64 {
+        match self {
+            BracketType::Round => 3,
+            BracketType::Square => 57,
+            BracketType::Curly => 1197,
+            BracketType::Angle => 25137,
+        }
+    }
+
+    fn autocomplete_score(&self) -> u64 {
+        match self {
+            BracketType::Round => 1,
+            BracketType::Square => 2,
+            BracketType::Curly => 3,
+            BracketType::Angle => 4,
+        }
+    }
+
+    pub fn opening(&self) -> char {
+        match self {
+            BracketType::Round => '(',
+            BracketType::Square => '[',
+            BracketType::Curly => '{',
+            BracketType::Angle => '<',
+        }
+    }
+
+    pub fn closing(&self) -> char {
+        match self {
+            BracketType::Round => ')',
+            BracketType::Square => ']',
+            BracketType::Curly => '}',
+            BracketType::Angle => '>',
+        }
+    }
+}
+
+#[derive(Debug)]
+enum Symbol {
+    Opening(BracketType),
+    Closing(BracketType),
+}
+
+impl TryFrom<char> for Symbol {
+    type Error = ();
+
+    fn try_from(c: char) -> Result<Self, Self::Error> {
+        Ok(match c {
+            '(' => Symbol::Opening(BracketType::Round),
+            '[' => Symbol::Opening(BracketType::Square),
+            '{' => Symbol::Opening(BracketType::Curly),
+            '<' => Symbol::Opening(BracketType::Angle),
+            ')' => Symbol::Closing(BracketType::Round),
+            ']' => Symbol::Closing(BracketType::Square),
+            '}' => Symbol::Closing(BracketType::Curly),
+            '>' => Symbol::Closing(BracketType::Angle),
+            _ => Err(())?,
+        })
+    }
+}
+
+/// 1-based line and column, columns count characters.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Position {
+    pub line: usize,
+    pub column: usize,
+}
+
+impl Display for Position {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}", self.line, self.column)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Diagnostic {
+    /// A closing bracket that doesn't match the innermost open one.
+    Mismatched {
+        position: Position,
+        expected: BracketType,
+        found: BracketType,
+        opened_at: Position,
+    },
+    /// A closing bracket without any open one.
+    UnmatchedClosing {
+        position: Position,
+        found: BracketType,
+    },
+    UnexpectedCharacter {
+        position: Position,
+        found: char,
+    },
+    /// The line ended with brackets still open, innermost last.
+    Incomplete {
+        position: Position,
+        unclosed: Vec<(BracketType, Position)>,
+    },
+}
+
+impl Diagnostic {
+    pub fn position(&self) -> Position {
+        match self {
+            Diagnostic::Mismatched { position, .. }
+            | Diagnostic::UnmatchedClosing { position, .. }
+            | Diagnostic::UnexpectedCharacter { position, .. }
+            | Diagnostic::Incomplete { position, .. } => *position,
+        }
+    }
+
+    /// The closing brackets that complete an incomplete line.
+    pub fn completion(&self) -> Option<String> {
+        match self {
+            Diagnostic::Incomplete { unclosed, .. } => Some(
+                unclosed
+                    .iter()
+                    .rev()
+                    .map(|(bracket, _)| bracket.closing())
+                    .collect(),
+            ),
+            _ => None,
+        }
+    }
+
+    /// Writes the diagnostic with the offending line and markers below it.
+    fn write_report(&self, out: &mut String, source: &str) -> std::fmt::Result {
+        let severity = match self {
+            Diagnostic::Incomplete { .. } => "warning",
+            _ => "error",
+        };
+        let position = self.position();
+        let gutter = " ".repeat(position.line.to_string().len());
+
+        writeln!(out, "{severity}: {self}")?;
+        writeln!(out, "{gutter}--> {position}")?;
+        writeln!(out, "{gutter} |")?;
+        writeln!(out, "{} | {}", position.line, source)?;
+
+        let pad = |column: usize| " ".repeat(column - 1);
+        match self {
+            Diagnostic::Mismatched {
+                expected,
+                opened_at,
+                ..
+            } => {
+                writeln!(
+                    out,
+                    "{gutter} | {}-{}^ expected `{}`",
+                    pad(opened_at.column),
+                    pad(position.column - opened_at.column),
+                    expected.closing()
+                )?;
+                writeln!(
+                    out,
+                    "{gutter} | {}`{}` opened here",
+                    pad(opened_at.column),
+                    expected.opening()
+                )?;
+            }
+            Diagnostic::UnmatchedClosing { .. } => {
+                writeln!(out, "{gutter} | {}^ nothing to close", pad(position.column))?;
+            }
+            Diagnostic::UnexpectedCharacter { .. } => {
+                writeln!(out, "{gutter} | {}^ not a bracket", pad(position.column))?;
+            }
+            Diagnostic::Incomplete { .. } => {
+                writeln!(
+                    out,
+                    "{gutter} | {}^ add `{}`",
+                    pad(position.column),
+                    self.completion().unwrap()
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Display for Diagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Diagnostic::Mismatched {
+                expected, found, ..
+            } => write!(
+                f,
+                "expected `{}`, found `{}`",
+                expected.closing(),
+                found.closing()
+            ),
+            Diagnostic::UnmatchedClosing { found, .. } => {
+                write!(f, "unmatched closing bracket `{}`", found.closing())
+            }
+            Diagnostic::UnexpectedCharacter { found, .. } => {
+                write!(f, "unexpected character {found:?}")
+            }
+            Diagnostic::Incomplete { unclosed, .. } => {
+                write!(f, "{} unclosed brackets", unclosed.len())
+            }
+        }
+    }
+}
+
+impl std::error::Error for Diagnostic {}
+
+/// Checks a single line, `line_number` is only used for the positions. Whitespace is skipped.
+pub fn check_line(line_number: usize, line: &str) -> Result<(), Diagnostic> {
+    let mut brackets: Vec<(BracketType, Position)> = vec![];
+    let mut end = 1;
+
+    for (i, c) in line.trim_end().chars().enumerate() {
+        let position = Position {
+            line: line_number,
+            column: i + 1,
+        };
+        end = i + 2;
+        if c.is_whitespace() {
+            continue;
+        }
+
+        match Symbol::try_from(c) {
+            Ok(Symbol::Opening(bracket)) => {
+                brackets.push((bracket, position));
+            }
+            Ok(Symbol::Closing(found)) => match brackets.pop() {
+                Some((expected, _)) if expected == found => {}
+                Some((expected, opened_at)) => {
+                    return Err(Diagnostic::Mismatched {
+                        position,
+                        expected,
+                        found,
+                        opened_at,
+                    })
+                }
+                None => return Err(Diagnostic::UnmatchedClosing { position, found }),
+            },
+            Err(()) => return Err(Diagnostic::UnexpectedCharacter { position, found: c }),
+        };
+    }
+
+    if !brackets.is_empty() {
+        return Err(Diagnostic::Incomplete {
+            position: Position {
+                line: line_number,
+                column: end,
+            },
+            unclosed: brackets,
+        });
+    }
+
+    Ok(())
+}
+
+/// Every diagnostic in the input, in line order.
+pub fn check(input: &str) -> Vec<Diagnostic> {
+    input
+        .lines()
+        .enumerate()
+        .filter_map(|(i, line)| check_line(i + 1, line).err())
+        .collect()
+}
+
+/// Compiler-style report of every diagnostic in `input`, empty if there is nothing to report.
+pub fn report(input: &str) -> String {
+    let lines = input.lines().collect::<Vec<_>>();
+    let mut out = String::new();
+
+    for diagnostic in check(input) {
+        let source = lines[diagnostic.position().line - 1].trim_end();
+        diagnostic.write_report(&mut out, source).unwrap();
+        out.push('\n');
+    }
+
+    out
+}
+
+/// The completion of every incomplete line.
+pub fn completions(diagnostics: &[Diagnostic]) -> Vec<String> {
+    diagnostics
+        .iter()
+        .filter_map(Diagnostic::completion)
+        .collect()
+}
+
+pub fn syntax_checker_score(diagnostics: &[Diagnostic]) -> u64 {
+    diagnostics
+        .iter()
+        .map(|diagnostic| match diagnostic {
+            Diagnostic::Mismatched { found, .. } | Diagnostic::UnmatchedClosing { found, .. } => {
+                found.syntax_error_value()
+            }
+            _ => 0,
+        })
+        .sum()
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum AutocompleteError {
+    NoIncompleteLine,
+    /// The score of the line doesn't fit in a `u64`, it has too many unclosed brackets.
+    Overflow {
+        line: usize,
+    },
+}
+
+impl Display for AutocompleteError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AutocompleteError::NoIncompleteLine => write!(f, "no line is incomplete"),
+            AutocompleteError::Overflow { line } => {
+                write!(f, "line {line}: autocomplete score overflows")
+            }
+        }
+    }
+}
+
+impl std::error::Error for AutocompleteError {}
+
+/// The middle score of the incomplete lines.
+pub fn autocomplete_score(diagnostics: &[Diagnostic]) -> Result<u64, AutocompleteError> {
+    let mut scores = diagnostics
+        .iter()
+        .filter_map(|diagnostic| match diagnostic {
+            Diagnostic::Incomplete { position, unclosed } => Some(
+                unclosed
+                    .iter()
+                    .rev()
+                    .try_fold(0u64, |acc, (bracket, _)| {
+                        acc.checked_mul(5)?
+                            .checked_add(bracket.autocomplete_score())
+                    })
+                    .ok_or(AutocompleteError::Overflow {
+                        line: position.line,
+                    }),
+            ),
+            _ => None,
+        })
+        .collect::<Result<Vec<u64>, _>>()?;
+
+    scores.sort();
+    scores
+        .get(scores.len() / 2)
+        .copied()
+        .ok_or(AutocompleteError::NoIncompleteLine)
+}
+
+/// The diagnostics of the navigation subsystem's lines.
+pub struct NavigationSubsystem {
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+impl Solution for NavigationSubsystem {
+    /// Lines that are corrupted or incomplete are what the puzzle is about, characters that
+    /// aren't brackets are an error.
+    fn parse(input: &str) -> Result<Self, Error> {
+        let diagnostics = check(input);
+        if let Some(unexpected) = diagnostics
+            .iter()
+            .find(|diagnostic| matches!(diagnostic, Diagnostic::UnexpectedCharacter { .. }))
+        {
+            return Err(format!("{}: {}", unexpected.position(), unexpected).into());
+        }
+
+        Ok(Self { diagnostics })
+    }
+
+    fn part1(&self) -> Result<impl Display, Error> {
+        Ok(syntax_checker_score(&self.diagnostics))
+    }
+
+    fn part2(&self) -> Result<impl Display, Error> {
+        Ok(autocomplete_score(&self.diagnostics)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE_INPUT: &str = "[({(<(())[]>[[{[]{<()<>>
+[(()[<>])]({[<{<<[]>>(
+{([(<{}[<>[]}>{[]{[(<()>
+(((({<>}<{<{<>}{[]{[]{}
+[[<[([]))<([[{}[[()]]]
+[{[{({}]{}}([{[{{{}}([]
+{<[[]]>}<{[{[{[]{()[[[]
+[<(<(<(<{}))><([]([]()
+<{([([[(<>()){}]>(<<{{
+<{([{{}}[<[[[<>{}]]]>[]]";
+
+    #[test]
+    fn parse_example_input() {
+        let diagnostics = check(EXAMPLE_INPUT);
+        let score = syntax_checker_score(&diagnostics);
+        assert_eq!(score, 26397);
+
+        let score = autocomplete_score(&diagnostics);
+        assert_eq!(score, Ok(288957));
+        assert_eq!(
+            autocomplete_score(&check("[]\n(>")),
+            Err(AutocompleteError::NoIncompleteLine)
+        );
+        assert_eq!(
+            autocomplete_score(&check(&format!("()\n{}", "<".repeat(28)))),
+            Err(AutocompleteError::Overflow { line: 2 })
+        );
+        assert!(autocomplete_score(&check(&"<".repeat(27))).is_ok());
+
+        assert_eq!(
+            completions(&diagnostics),
+            vec!["}}]])})]", ")}>]})", "}}>}>))))", "]]}}]}]}>", "])}>"]
+        );
+    }
+
+    #[test]
+    fn test_diagnostics() {
+        assert_eq!(
+            check_line(3, "{([(<{}[<>[]}>{[]{[(<()>"),
+            Err(Diagnostic::Mismatched {
+                position: Position {
+                    line: 3,
+                    column: 13
+                },
+                expected: BracketType::Square,
+                found: BracketType::Curly,
+                opened_at: Position { line: 3, column: 8 },
+            })
+        );
+        assert_eq!(
+            check_line(1, "()>"),
+            Err(Diagnostic::UnmatchedClosing {
+                position: Position { line: 1, column: 3 },
+                found: BracketType::Angle,
+            })
+        );
+        assert_eq!(
+            check_line(1, "(x)"),
+            Err(Diagnostic::UnexpectedCharacter {
+                position: Position { line: 1, column: 2 },
+                found: 'x',
+            })
+        );
+        assert_eq!(check_line(1, "( [] )"), Ok(()));
+
+        let error = NavigationSubsystem::parse("()\n(x)").err().unwrap();
+        assert_eq!(error.to_string(), "2:2: unexpected character 'x'");
+    }
+
+    #[test]
+    fn test_report() {
+        let expected = "\
+error: expected `]`, found `}`
+ --> 1:13
+  |
+1 | {([(<{}[<>[]}>{[]{[(<()>
+  |        -    ^ expected `]`
+  |        `[` opened here
+
+warning: 2 unclosed brackets
+ --> 2:3
+  |
+2 | [(
+  |   ^ add `)]`
+
+";
+        assert_eq!(report("{([(<{}[<>[]}>{[]{[(<()>\n[(\n()"), expected);
+    }
+}
@@ -0,0 +1,412 @@
+use std::{collections::HashMap, fmt::Display, str::FromStr};
+
+use aoc::{Error, ParseError, Solution};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Neighbourhood {
+    /// All eight surrounding cells.
+    Moore,
+    /// Only the four orthogonal ones.
+    VonNeumann,
+}
+
+impl Neighbourhood {
+    fn offsets(&self) -> &'static [(isize, isize)] {
+        match self {
+            Neighbourhood::Moore => &[
+                (-1, -1),
+                (0, -1),
+                (1, -1),
+                (-1, 0),
+                (1, 0),
+                (-1, 1),
+                (0, 1),
+                (1, 1),
+            ],
+            Neighbourhood::VonNeumann => &[(0, -1), (-1, 0), (1, 0), (0, 1)],
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Edges {
+    Bounded,
+    /// The grid is a torus, leaving on one side enters on the other.
+    Wrapping,
+}
+
+/// Every step each cell gains `gain` energy. A cell above `threshold` flashes once per step,
+/// gives `spread` to each neighbour that hasn't flashed yet and ends the step at `reset`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct EnergyRule {
+    pub gain: u32,
+    pub threshold: u32,
+    pub spread: u32,
+    pub reset: u32,
+}
+
+impl EnergyRule {
+    pub fn octopus() -> Self {
+        Self {
+            gain: 1,
+            threshold: 9,
+            spread: 1,
+            reset: 0,
+        }
+    }
+}
+
+/// The state repeats every `period` steps from step `start` on.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Cycle {
+    pub start: usize,
+    pub period: usize,
+}
+
+#[derive(Clone, Debug)]
+pub struct Automaton {
+    width: usize,
+    height: usize,
+    cells: Vec<u32>,
+    neighbourhood: Neighbourhood,
+    edges: Edges,
+    rule: EnergyRule,
+}
+
+impl Automaton {
+    /// A bounded Moore automaton with the octopus rule, `cells` row by row.
+    pub fn new(width: usize, height: usize, cells: Vec<u32>) -> Self {
+        assert_eq!(cells.len(), width * height);
+
+        Self {
+            width,
+            height,
+            cells,
+            neighbourhood: Neighbourhood::Moore,
+            edges: Edges::Bounded,
+            rule: EnergyRule::octopus(),
+        }
+    }
+
+    pub fn with_neighbourhood(mut self, neighbourhood: Neighbourhood) -> Self {
+        self.neighbourhood = neighbourhood;
+        self
+    }
+
+    pub fn with_edges(mut self, edges: Edges) -> Self {
+        self.edges = edges;
+        self
+    }
+
+    pub fn with_rule(mut self, rule: EnergyRule) -> Self {
+        // otherwise a cell could end a step above the threshold and the states aren't bounded
+        assert!(rule.reset <= rule.threshold);
+        self.rule = rule;
+        self
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> u32 {
+        self.cells[y * self.width + x]
+    }
+
+    fn neighbour(&self, index: usize, (dx, dy): (isize, isize)) -> Option<usize> {
+        let x = (index % self.width) as isize + dx;
+        let y = (index / self.width) as isize + dy;
+        let (width, height) = (self.width as isize, self.height as isize);
+
+        let (x, y) = match self.edges {
+            Edges::Bounded if x < 0 || x >= width || y < 0 || y >= height => return None,
+            Edges::Bounded => (x, y),
+            Edges::Wrapping => (x.rem_euclid(width), y.rem_euclid(height)),
+        };
+
+        Some(y as usize * self.width + x as usize)
+    }
+
+    /// Runs one step and returns the number of cells that flashed.
+    ///
+    /// Cells go into a work queue when they cross the threshold, so every flash is handled
+    /// exactly once no matter how long the cascade gets.
+    pub fn step(&mut self) -> usize {
+        let mut flashed = vec![false; self.cells.len()];
+        let mut queue = Vec::new();
+
+        for (i, cell) in self.cells.iter_mut().enumerate() {
+            *cell += self.rule.gain;
+            if *cell > self.rule.threshold {
+                flashed[i] = true;
+                queue.push(i);
+            }
+        }
+
+        let mut flashes = 0;
+        while let Some(i) = queue.pop() {
+            flashes += 1;
+
+            for &offset in self.neighbourhood.offsets() {
+                // on small wrapping grids several offsets can land on the same cell, each of
+                // them counts
+                if let Some(n) = self.neighbour(i, offset) {
+                    if flashed[n] {
+                        continue;
+                    }
+
+                    self.cells[n] += self.rule.spread;
+                    if self.cells[n] > self.rule.threshold {
+                        flashed[n] = true;
+                        queue.push(n);
+                    }
+                }
+            }
+        }
+
+        for (cell, _) in self.cells.iter_mut().zip(flashed).filter(|(_, f)| *f) {
+            *cell = self.rule.reset;
+        }
+
+        flashes
+    }
+
+    /// Runs `steps` steps and returns the total number of flashes.
+    pub fn run(&mut self, steps: usize) -> usize {
+        (0..steps).map(|_| self.step()).sum()
+    }
+
+    /// Steps until every cell flashes in the same step and returns that step's number, or
+    /// `None` once the states start repeating without that ever happening.
+    pub fn run_until_synchronised(&mut self) -> Option<usize> {
+        let mut seen = HashMap::new();
+
+        for step in 1.. {
+            seen.insert(self.cells.clone(), step);
+
+            if self.step() == self.cells.len() {
+                return Some(step);
+            }
+            if seen.contains_key(&self.cells) {
+                return None;
+            }
+        }
+
+        unreachable!()
+    }
+
+    /// Finds the cycle the automaton ends up in, which always exists because the energy of
+    /// every cell is at most the threshold between steps.
+    pub fn find_cycle(&self) -> Cycle {
+        let mut automaton = self.clone();
+        let mut seen = HashMap::new();
+
+        for step in 0.. {
+            if let Some(start) = seen.insert(automaton.cells.clone(), step) {
+                return Cycle {
+                    start,
+                    period: step - start,
+                };
+            }
+            automaton.step();
+        }
+
+        unreachable!()
+    }
+}
+
+impl Display for Automaton {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for row in self.cells.chunks(self.width) {
+            for cell in row {
+                write!(f, "{:>2}", cell)?;
+            }
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// The puzzle's configuration: a bounded grid, Moore neighbourhood and the octopus rule.
+#[derive(Clone, Debug)]
+pub struct OctopusGrid {
+    automaton: Automaton,
+}
+
+impl FromStr for OctopusGrid {
+    type Err = ParseError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let mut rows: Vec<Vec<u32>> = Vec::new();
+        for (i, line) in input.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            let row = line
+                .chars()
+                .map(|c| {
+                    c.to_digit(10).ok_or_else(|| {
+                        ParseError::new(i + 1, format!("{c:?} isn't an energy level"))
+                    })
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            if let Some(first) = rows.first().filter(|first| first.len() != row.len()) {
+                return Err(ParseError::new(
+                    i + 1,
+                    format!("expected {} octopuses, found {}", first.len(), row.len()),
+                ));
+            }
+            rows.push(row);
+        }
+
+        let width = rows
+            .first()
+            .map(Vec::len)
+            .ok_or_else(|| ParseError::new(input.lines().count(), "there are no octopuses"))?;
+        Ok(Self {
+            automaton: Automaton::new(width, rows.len(), rows.concat()),
+        })
+    }
+}
+
+impl Display for OctopusGrid {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.automaton.fmt(f)
+    }
+}
+
+impl OctopusGrid {
+    pub fn run_steps(&mut self, steps: usize) -> usize {
+        self.automaton.run(steps)
+    }
+
+    pub fn run_til_the_supernova_happens(&mut self) -> Option<usize> {
+        self.automaton.run_until_synchronised()
+    }
+}
+
+impl Solution for OctopusGrid {
+    fn parse(input: &str) -> Result<Self, Error> {
+        Ok(input.parse::<OctopusGrid>()?)
+    }
+
+    fn part1(&self) -> Result<impl Display, Error> {
+        Ok(self.clone().run_steps(100))
+    }
+
+    fn part2(&self) -> Result<impl Display, Error> {
+        self.clone()
+            .run_til_the_supernova_happens()
+            .ok_or_else(|| "the octopuses never flash all at once".into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE_INPUT: &str = "5483143223
+2745854711
+5264556173
+6141336146
+6357385478
+4167524645
+2176841721
+6882881134
+4846848554
+5283751526";
+
+    #[test]
+    fn test_example_input() {
+        let mut grid = EXAMPLE_INPUT.parse::<OctopusGrid>().unwrap();
+        println!("{}", grid);
+
+        let flashes = grid.run_steps(10);
+
+        assert_eq!(flashes, 204);
+
+        let flashes = flashes + grid.run_steps(90);
+        assert_eq!(flashes, 1656);
+
+        let mut grid = EXAMPLE_INPUT.parse::<OctopusGrid>().unwrap();
+        assert_eq!(grid.run_til_the_supernova_happens(), Some(195));
+    }
+
+    #[test]
+    fn test_neighbourhoods_and_edges() {
+        let single_flash = |neighbourhood, edges| {
+            let mut automaton = Automaton::new(3, 3, vec![0, 0, 0, 0, 0, 0, 0, 0, 9])
+                .with_neighbourhood(neighbourhood)
+                .with_edges(edges);
+            automaton.step();
+            automaton.cells
+        };
+
+        assert_eq!(
+            single_flash(Neighbourhood::Moore, Edges::Bounded),
+            vec![1, 1, 1, 1, 2, 2, 1, 2, 0]
+        );
+        assert_eq!(
+            single_flash(Neighbourhood::VonNeumann, Edges::Bounded),
+            vec![1, 1, 1, 1, 1, 2, 1, 2, 0]
+        );
+        assert_eq!(
+            single_flash(Neighbourhood::VonNeumann, Edges::Wrapping),
+            vec![1, 1, 2, 1, 1, 2, 2, 2, 0]
+        );
+        assert_eq!(
+            single_flash(Neighbourhood::Moore, Edges::Wrapping),
+            vec![2; 8].into_iter().chain([0]).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_cycles() {
+        // without spreading the cells never catch up with each other
+        let mut automaton = Automaton::new(2, 1, vec![0, 5]).with_rule(EnergyRule {
+            spread: 0,
+            ..EnergyRule::octopus()
+        });
+        assert_eq!(
+            automaton.find_cycle(),
+            Cycle {
+                start: 0,
+                period: 10
+            }
+        );
+        assert_eq!(automaton.run_until_synchronised(), None);
+
+        // the top three rows of the example never line up
+        let mut grid = "5483143223\n2745854711\n5264556173"
+            .parse::<OctopusGrid>()
+            .unwrap();
+        assert_eq!(grid.run_til_the_supernova_happens(), None);
+
+        // the whole example does, and from then on flashes together every ten steps
+        let mut grid = EXAMPLE_INPUT.parse::<OctopusGrid>().unwrap();
+        assert_eq!(grid.run_til_the_supernova_happens(), Some(195));
+        assert_eq!(
+            grid.automaton.find_cycle(),
+            Cycle {
+                start: 0,
+                period: 10
+            }
+        );
+        assert_eq!(grid.run_steps(10), 100);
+    }
+
+    #[test]
+    fn test_invalid_grid() {
+        let line = |input: &str| input.parse::<OctopusGrid>().err().map(|e| e.line);
+        assert_eq!(line(""), Some(0));
+        assert_eq!(line("123\n1-3"), Some(2));
+        assert_eq!(line("123\n1234"), Some(2));
+    }
+}
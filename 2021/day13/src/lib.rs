@@ -0,0 +1,453 @@
+use std::fmt::Display;
+
+use aoc::{Error, ParseError, Solution};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FoldAxis {
+    X,
+    Y,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Fold {
+    pub axis: FoldAxis,
+    pub position: usize,
+}
+
+impl Display for Fold {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let axis = match self.axis {
+            FoldAxis::X => 'x',
+            FoldAxis::Y => 'y',
+        };
+        write!(f, "fold along {}={}", axis, self.position)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum FoldError {
+    /// The fold line isn't inside the paper as it is at that point.
+    OutsidePaper { fold: Fold, extent: usize },
+    /// A dot lies on the fold line itself.
+    OnDot { fold: Fold, dot: (usize, usize) },
+    /// The folded-over part is longer than the rest and this dot ends up past the edge.
+    PastEdge { fold: Fold, dot: (usize, usize) },
+}
+
+impl Display for FoldError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FoldError::OutsidePaper { fold, extent } => {
+                write!(f, "{fold} is outside the paper of extent {extent}")
+            }
+            FoldError::OnDot { fold, dot } => {
+                write!(f, "{fold} runs through the dot at {},{}", dot.0, dot.1)
+            }
+            FoldError::PastEdge { fold, dot } => {
+                write!(
+                    f,
+                    "{fold} moves the dot at {},{} past the edge",
+                    dot.0, dot.1
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for FoldError {}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum OcrError {
+    /// The paper isn't 6 dots high.
+    Height(usize),
+    /// The glyph at `index` isn't in the font.
+    UnknownGlyph { index: usize },
+}
+
+impl Display for OcrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            OcrError::Height(height) => {
+                write!(f, "paper is {height} dots high, glyphs are {GLYPH_HEIGHT}")
+            }
+            OcrError::UnknownGlyph { index } => write!(f, "glyph {index} is not in the font"),
+        }
+    }
+}
+
+impl std::error::Error for OcrError {}
+
+const GLYPH_WIDTH: usize = 4;
+const GLYPH_HEIGHT: usize = 6;
+/// Glyphs are separated by one blank column.
+const GLYPH_PITCH: usize = GLYPH_WIDTH + 1;
+
+const FONT: &[(char, [&str; GLYPH_HEIGHT])] = &[
+    ('A', [".##.", "#..#", "#..#", "####", "#..#", "#..#"]),
+    ('B', ["###.", "#..#", "###.", "#..#", "#..#", "###."]),
+    ('C', [".##.", "#..#", "#...", "#...", "#..#", ".##."]),
+    ('E', ["####", "#...", "###.", "#...", "#...", "####"]),
+    ('F', ["####", "#...", "###.", "#...", "#...", "#..."]),
+    ('G', [".##.", "#..#", "#...", "#.##", "#..#", ".###"]),
+    ('H', ["#..#", "#..#", "####", "#..#", "#..#", "#..#"]),
+    ('I', [".###", "..#.", "..#.", "..#.", "..#.", ".###"]),
+    ('J', ["..##", "...#", "...#", "...#", "#..#", ".##."]),
+    ('K', ["#..#", "#.#.", "##..", "#.#.", "#.#.", "#..#"]),
+    ('L', ["#...", "#...", "#...", "#...", "#...", "####"]),
+    ('O', [".##.", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('P', ["###.", "#..#", "#..#", "###.", "#...", "#..."]),
+    ('R', ["###.", "#..#", "#..#", "###.", "#.#.", "#..#"]),
+    ('S', [".###", "#...", "#...", ".##.", "...#", "###."]),
+    ('U', ["#..#", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('Z', ["####", "...#", "..#.", ".#..", "#...", "####"]),
+];
+
+/// Bit `y * GLYPH_WIDTH + x` is set for every dot of the glyph.
+fn glyph_mask(rows: &[&str; GLYPH_HEIGHT]) -> u32 {
+    rows.iter()
+        .flat_map(|row| row.bytes())
+        .enumerate()
+        .filter(|&(_, c)| c == b'#')
+        .fold(0, |mask, (i, _)| mask | 1 << i)
+}
+
+#[derive(Clone, Copy, Debug)]
+enum Miss {
+    OnLine,
+    PastEdge,
+}
+
+/// Where every coordinate along one axis ends up after all folds along it, or the index of the
+/// fold that went wrong for it.
+type AxisMap = Vec<Result<usize, (usize, Fold, Miss)>>;
+
+#[derive(Clone, Debug)]
+pub struct Paper {
+    width: usize,
+    height: usize,
+    dots: Vec<(usize, usize)>,
+}
+
+impl Paper {
+    pub fn new(dots: Vec<(usize, usize)>) -> Self {
+        let (w, h) = dots
+            .iter()
+            .fold((0, 0), |max, dot| (dot.0.max(max.0), dot.1.max(max.1)));
+
+        let mut paper = Self {
+            width: w + 1,
+            height: h + 1,
+            dots,
+        };
+        paper.dots.sort_unstable();
+        paper.dots.dedup();
+        paper
+    }
+
+    /// The dots, then after a blank line the folds.
+    pub fn parse(input: &str) -> Result<(Self, Vec<Fold>), ParseError> {
+        let mut lines = input
+            .lines()
+            .enumerate()
+            .map(|(i, line)| (i + 1, line.trim()));
+
+        let mut dots = Vec::new();
+        for (line, dot) in lines.by_ref().take_while(|(_, line)| !line.is_empty()) {
+            let coordinate = |n: &str| {
+                n.parse()
+                    .map_err(|_| ParseError::new(line, format!("invalid coordinate {n:?}")))
+            };
+            let (x, y) = dot
+                .split_once(',')
+                .ok_or_else(|| ParseError::new(line, format!("invalid dot {dot:?}")))?;
+            dots.push((coordinate(x)?, coordinate(y)?));
+        }
+
+        let mut folds = Vec::new();
+        for (line, fold) in lines.filter(|(_, line)| !line.is_empty()) {
+            let invalid = || ParseError::new(line, format!("invalid fold {fold:?}"));
+            let (axis, position) = fold
+                .strip_prefix("fold along ")
+                .and_then(|fold| fold.split_once('='))
+                .ok_or_else(invalid)?;
+            folds.push(Fold {
+                axis: match axis {
+                    "x" => FoldAxis::X,
+                    "y" => FoldAxis::Y,
+                    _ => return Err(invalid()),
+                },
+                position: position.parse().map_err(|_| invalid())?,
+            });
+        }
+
+        Ok((Self::new(dots), folds))
+    }
+
+    pub fn size(&self) -> (usize, usize) {
+        (self.width, self.height)
+    }
+
+    /// Composes the folds along `axis` into a single lookup table over `0..extent`. Returns the
+    /// table and the extent of the folded paper.
+    fn axis_map(
+        extent: usize,
+        axis: FoldAxis,
+        folds: &[Fold],
+    ) -> Result<(AxisMap, usize), FoldError> {
+        let mut map: AxisMap = (0..extent).map(Ok).collect();
+        let mut extent = extent;
+
+        for (i, &fold) in folds.iter().enumerate().filter(|(_, f)| f.axis == axis) {
+            if fold.position >= extent {
+                return Err(FoldError::OutsidePaper { fold, extent });
+            }
+
+            let position = fold.position;
+            for coord in map.iter_mut() {
+                if let Ok(c) = *coord {
+                    *coord = match c {
+                        c if c < position => Ok(c),
+                        c if c == position => Err((i, fold, Miss::OnLine)),
+                        c if c - position > position => Err((i, fold, Miss::PastEdge)),
+                        c => Ok(2 * position - c),
+                    };
+                }
+            }
+            extent = position;
+        }
+
+        Ok((map, extent))
+    }
+
+    /// Applies all `folds` in order, mapping every dot once through the composed transform.
+    pub fn fold(&self, folds: &[Fold]) -> Result<Self, FoldError> {
+        // An out-of-paper fold is reported before any dot errors, whichever axis it's on.
+        let (xs, width) = Self::axis_map(self.width, FoldAxis::X, folds)?;
+        let (ys, height) = Self::axis_map(self.height, FoldAxis::Y, folds)?;
+
+        let mut dots = Vec::with_capacity(self.dots.len());
+        for &dot in self.dots.iter() {
+            match (&xs[dot.0], &ys[dot.1]) {
+                (Ok(x), Ok(y)) => dots.push((*x, *y)),
+                // Report the fold that hit the dot first.
+                (x, y) => {
+                    let &(_, fold, miss) = [x, y]
+                        .into_iter()
+                        .filter_map(|c| c.as_ref().err())
+                        .min_by_key(|(i, _, _)| *i)
+                        .unwrap();
+
+                    return Err(match miss {
+                        Miss::OnLine => FoldError::OnDot { fold, dot },
+                        Miss::PastEdge => FoldError::PastEdge { fold, dot },
+                    });
+                }
+            }
+        }
+
+        dots.sort_unstable();
+        dots.dedup();
+
+        Ok(Self {
+            width,
+            height,
+            dots,
+        })
+    }
+
+    pub fn visible_dots(&self) -> usize {
+        self.dots.len()
+    }
+
+    /// Reads the dots as a row of 4x6 capital letters.
+    pub fn read_code(&self) -> Result<String, OcrError> {
+        if self.height != GLYPH_HEIGHT {
+            return Err(OcrError::Height(self.height));
+        }
+
+        let glyphs = (self.width + 1) / GLYPH_PITCH;
+        let mut masks = vec![0u32; glyphs];
+        for &(x, y) in self.dots.iter() {
+            let (glyph, column) = (x / GLYPH_PITCH, x % GLYPH_PITCH);
+            match masks.get_mut(glyph) {
+                Some(mask) if column < GLYPH_WIDTH => *mask |= 1 << (y * GLYPH_WIDTH + column),
+                // A dot in the gap between glyphs or past the last one.
+                _ => return Err(OcrError::UnknownGlyph { index: glyph }),
+            }
+        }
+
+        masks
+            .into_iter()
+            .enumerate()
+            .map(|(index, mask)| {
+                FONT.iter()
+                    .find(|(_, rows)| glyph_mask(rows) == mask)
+                    .map(|(c, _)| *c)
+                    .ok_or(OcrError::UnknownGlyph { index })
+            })
+            .collect()
+    }
+}
+
+impl Display for Paper {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut grid = vec![false; self.width * self.height];
+        for &(x, y) in self.dots.iter() {
+            grid[y * self.width + x] = true;
+        }
+
+        for row in grid.chunks(self.width) {
+            for &dot in row {
+                write!(f, "{}", if dot { '#' } else { '.' })?;
+            }
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// The transparent paper together with the folding instructions from the manual.
+pub struct Manual {
+    pub paper: Paper,
+    pub folds: Vec<Fold>,
+}
+
+impl Solution for Manual {
+    fn parse(input: &str) -> Result<Self, Error> {
+        let (paper, folds) = Paper::parse(input)?;
+        Ok(Self { paper, folds })
+    }
+
+    fn part1(&self) -> Result<impl Display, Error> {
+        let first = self.folds.first().ok_or("no folds")?;
+        Ok(self.paper.fold(std::slice::from_ref(first))?.visible_dots())
+    }
+
+    fn part2(&self) -> Result<impl Display, Error> {
+        Ok(self.paper.fold(&self.folds)?.read_code()?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE_INPUT: &str = "6,10
+0,14
+9,10
+0,3
+10,4
+4,11
+6,0
+6,12
+4,1
+0,13
+10,12
+3,4
+3,0
+8,4
+1,10
+2,14
+8,10
+9,0
+
+fold along y=7
+fold along x=5";
+
+    #[test]
+    fn test_example_input() {
+        let (paper, folds) = Paper::parse(EXAMPLE_INPUT).unwrap();
+        println!("{}", paper);
+
+        let paper_firstfold = paper.fold(&folds[..1]).unwrap();
+        println!("{}", paper_firstfold);
+        assert_eq!(paper_firstfold.visible_dots(), 17);
+
+        let folded = paper.fold(&folds).unwrap();
+        println!("{}", folded);
+        assert_eq!(folded.visible_dots(), 16);
+        assert_eq!(folded.size(), (5, 7));
+    }
+
+    #[test]
+    fn test_fold_errors() {
+        let (paper, _) = Paper::parse(EXAMPLE_INPUT).unwrap();
+        let fold = |axis, position| Fold { axis, position };
+
+        assert_eq!(
+            paper
+                .fold(&[fold(FoldAxis::X, 5), fold(FoldAxis::X, 5)])
+                .unwrap_err(),
+            FoldError::OutsidePaper {
+                fold: fold(FoldAxis::X, 5),
+                extent: 5
+            }
+        );
+        assert_eq!(
+            paper.fold(&[fold(FoldAxis::Y, 10)]).unwrap_err(),
+            FoldError::OnDot {
+                fold: fold(FoldAxis::Y, 10),
+                dot: (1, 10)
+            }
+        );
+        assert_eq!(
+            paper.fold(&[fold(FoldAxis::Y, 3)]).unwrap_err(),
+            FoldError::OnDot {
+                fold: fold(FoldAxis::Y, 3),
+                dot: (0, 3)
+            }
+        );
+        assert_eq!(
+            paper.fold(&[fold(FoldAxis::Y, 2)]).unwrap_err(),
+            FoldError::PastEdge {
+                fold: fold(FoldAxis::Y, 2),
+                dot: (0, 13)
+            }
+        );
+    }
+
+    #[test]
+    fn test_read_code() {
+        let code = [
+            "#..#.###.",
+            "#..#.#..#",
+            "####.###.",
+            "#..#.#..#",
+            "#..#.#..#",
+            "#..#.###.",
+        ];
+        let dots = code
+            .iter()
+            .enumerate()
+            .flat_map(|(y, row)| {
+                row.bytes()
+                    .enumerate()
+                    .filter(|&(_, c)| c == b'#')
+                    .map(move |(x, _)| (x, y))
+            })
+            .collect();
+
+        let paper = Paper::new(dots);
+        assert_eq!(paper.read_code(), Ok("HB".to_string()));
+
+        let unknown = Paper::new(vec![(0, 0), (0, 5), (3, 5)]);
+        assert_eq!(
+            unknown.read_code(),
+            Err(OcrError::UnknownGlyph { index: 0 })
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let line = |input| Paper::parse(input).err().map(|e| e.line);
+        assert_eq!(line("1,2\n3;4\n\nfold along x=1"), Some(2));
+        assert_eq!(line("1,2\n3,-4\n\nfold along x=1"), Some(2));
+        assert_eq!(line("1,2\n\nfold along x=1\nfold along z=1"), Some(4));
+        assert_eq!(line("1,2\n\nfold x=1"), Some(3));
+
+        let (paper, folds) = Paper::parse("1,2\n").unwrap();
+        assert_eq!(paper.size(), (2, 3));
+        assert!(folds.is_empty());
+    }
+}
@@ -0,0 +1,370 @@
+use std::fmt::Display;
+
+use aoc::{Error, ParseError, Solution};
+
+/// Pair insertion over the elements that appear in the template and the rules. Elements are
+/// numbered by their position in `alphabet`, the pair `(a, b)` by `a * alphabet.len() + b`.
+#[derive(Clone, Debug)]
+pub struct PolymerEngine {
+    alphabet: Vec<char>,
+    template: Vec<usize>,
+    /// The element inserted between each pair, `None` if the pair has no rule.
+    rules: Vec<Option<usize>>,
+}
+
+/// How often every element occurs in the polymer after some step.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Histogram {
+    pub step: usize,
+    pub counts: Vec<(char, u128)>,
+}
+
+impl Histogram {
+    pub fn length(&self) -> u128 {
+        self.counts.iter().map(|(_, count)| count).sum()
+    }
+
+    pub fn most_common(&self) -> Option<(char, u128)> {
+        self.present().max_by_key(|(_, count)| *count)
+    }
+
+    pub fn least_common(&self) -> Option<(char, u128)> {
+        self.present().min_by_key(|(_, count)| *count)
+    }
+
+    /// Most common minus least common element count, the puzzle answer.
+    pub fn spread(&self) -> u128 {
+        match (self.most_common(), self.least_common()) {
+            (Some((_, max)), Some((_, min))) => max - min,
+            _ => 0,
+        }
+    }
+
+    /// Elements that only appear in rules that never fire are left out of the statistics.
+    fn present(&self) -> impl Iterator<Item = (char, u128)> + '_ {
+        self.counts.iter().copied().filter(|(_, count)| *count > 0)
+    }
+}
+
+impl PolymerEngine {
+    /// The template, then after a blank line the pair insertion rules.
+    pub fn parse(input: &str) -> Result<Self, ParseError> {
+        let mut lines = input
+            .lines()
+            .enumerate()
+            .map(|(i, line)| (i + 1, line.trim()));
+        let template = lines.next().map_or("", |(_, template)| template);
+
+        let rules = lines
+            .filter(|(_, line)| !line.is_empty())
+            .map(|(i, line)| {
+                let rule = line.split_once(" -> ").and_then(|(pair, insertion)| {
+                    let (mut pair, mut insertion) = (pair.chars(), insertion.chars());
+                    match (
+                        pair.next(),
+                        pair.next(),
+                        pair.next(),
+                        insertion.next(),
+                        insertion.next(),
+                    ) {
+                        (Some(a), Some(b), None, Some(c), None) => Some(((a, b), c)),
+                        _ => None,
+                    }
+                });
+                rule.ok_or_else(|| ParseError::new(i, format!("invalid rule {line:?}")))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut alphabet = template
+            .chars()
+            .chain(rules.iter().flat_map(|&((a, b), c)| [a, b, c]))
+            .collect::<Vec<_>>();
+        alphabet.sort_unstable();
+        alphabet.dedup();
+
+        let index = |c: char| alphabet.binary_search(&c).unwrap();
+        let n = alphabet.len();
+
+        let mut table = vec![None; n * n];
+        for &((a, b), c) in rules.iter() {
+            table[index(a) * n + index(b)] = Some(index(c));
+        }
+
+        Ok(Self {
+            template: template.chars().map(index).collect(),
+            rules: table,
+            alphabet,
+        })
+    }
+
+    /// Builds the polymer after `steps` steps the slow way, by inserting into a string.
+    pub fn expand(&self, steps: usize) -> String {
+        let mut polymer = self.template.clone();
+
+        for _ in 0..steps {
+            let mut next = Vec::with_capacity(polymer.len() * 2);
+            for pair in polymer.windows(2) {
+                next.push(pair[0]);
+                if let Some(c) = self.rules[self.pair(pair[0], pair[1])] {
+                    next.push(c);
+                }
+            }
+            next.extend(polymer.last());
+            polymer = next;
+        }
+
+        polymer.iter().map(|&i| self.alphabet[i]).collect()
+    }
+
+    /// The element histogram after `steps` steps, or `None` if the polymer length doesn't fit
+    /// into a `u128`.
+    pub fn histogram(&self, steps: usize) -> Option<Histogram> {
+        let counts = self.run(&Checked, steps, |_, _| ());
+        self.histogram_from(steps, &counts)
+    }
+
+    /// The histogram after every step from the template up to `steps`. Stops early at the first
+    /// step whose polymer length doesn't fit into a `u128`.
+    pub fn histograms(&self, steps: usize) -> Vec<Histogram> {
+        let mut histograms = Vec::new();
+        self.run(&Checked, steps, |step, counts| {
+            if histograms.len() == step {
+                histograms.extend(self.histogram_from(step, counts));
+            }
+        });
+
+        histograms
+    }
+
+    /// The element counts after `steps` steps modulo `modulus`.
+    pub fn histogram_mod(&self, steps: usize, modulus: u64) -> Vec<(char, u64)> {
+        assert!(modulus > 0, "modulus has to be positive");
+
+        let counts = self.run(&Modulo(modulus), steps, |_, _| ());
+        self.alphabet.iter().copied().zip(counts).collect()
+    }
+
+    fn pair(&self, a: usize, b: usize) -> usize {
+        a * self.alphabet.len() + b
+    }
+
+    fn histogram_from(&self, step: usize, counts: &[Option<u128>]) -> Option<Histogram> {
+        // Checking the total up front keeps `Histogram::length` from overflowing.
+        counts
+            .iter()
+            .try_fold(0u128, |length, count| length.checked_add((*count)?))?;
+
+        Some(Histogram {
+            step,
+            counts: self
+                .alphabet
+                .iter()
+                .copied()
+                .zip(counts.iter().flatten().copied())
+                .collect(),
+        })
+    }
+
+    /// Steps the pair counts `steps` times in `count`, calling `observe` with the element counts
+    /// of the template and after every step. Returns the element counts after the last step.
+    fn run<C: Count>(
+        &self,
+        count: &C,
+        steps: usize,
+        mut observe: impl FnMut(usize, &[C::Value]),
+    ) -> Vec<C::Value> {
+        let n = self.alphabet.len();
+
+        let mut pairs = vec![count.zero(); n * n];
+        for pair in self.template.windows(2) {
+            let i = self.pair(pair[0], pair[1]);
+            pairs[i] = count.add(&pairs[i], &count.one());
+        }
+
+        let mut elements = self.elements(count, &pairs);
+        observe(0, &elements);
+
+        for step in 1..=steps {
+            let mut next = vec![count.zero(); n * n];
+            for (pair, amount) in pairs.iter().enumerate() {
+                match self.rules[pair] {
+                    Some(c) => {
+                        for target in [self.pair(pair / n, c), self.pair(c, pair % n)] {
+                            next[target] = count.add(&next[target], amount);
+                        }
+                    }
+                    None => next[pair] = count.add(&next[pair], amount),
+                }
+            }
+            pairs = next;
+
+            elements = self.elements(count, &pairs);
+            observe(step, &elements);
+        }
+
+        elements
+    }
+
+    /// Every pair contributes its first element, the last element of the template stays last.
+    fn elements<C: Count>(&self, count: &C, pairs: &[C::Value]) -> Vec<C::Value> {
+        let n = self.alphabet.len();
+
+        let mut elements = vec![count.zero(); n];
+        for (pair, amount) in pairs.iter().enumerate() {
+            elements[pair / n] = count.add(&elements[pair / n], amount);
+        }
+        if let Some(&last) = self.template.last() {
+            elements[last] = count.add(&elements[last], &count.one());
+        }
+
+        elements
+    }
+}
+
+/// The arithmetic polymer lengths are counted in.
+trait Count {
+    type Value: Clone;
+
+    fn zero(&self) -> Self::Value;
+    fn one(&self) -> Self::Value;
+    fn add(&self, a: &Self::Value, b: &Self::Value) -> Self::Value;
+}
+
+/// `u128` that stays `None` once it overflows.
+struct Checked;
+
+impl Count for Checked {
+    type Value = Option<u128>;
+
+    fn zero(&self) -> Self::Value {
+        Some(0)
+    }
+
+    fn one(&self) -> Self::Value {
+        Some(1)
+    }
+
+    fn add(&self, a: &Self::Value, b: &Self::Value) -> Self::Value {
+        a.as_ref()?.checked_add(b.as_ref().copied()?)
+    }
+}
+
+struct Modulo(u64);
+
+impl Count for Modulo {
+    type Value = u64;
+
+    fn zero(&self) -> Self::Value {
+        0
+    }
+
+    fn one(&self) -> Self::Value {
+        1 % self.0
+    }
+
+    fn add(&self, a: &Self::Value, b: &Self::Value) -> Self::Value {
+        ((*a as u128 + *b as u128) % self.0 as u128) as u64
+    }
+}
+
+impl Solution for PolymerEngine {
+    fn parse(input: &str) -> Result<Self, Error> {
+        Ok(PolymerEngine::parse(input)?)
+    }
+
+    fn part1(&self) -> Result<impl Display, Error> {
+        let histogram = self.histogram(10).ok_or("the polymer grows too long")?;
+        Ok(histogram.spread())
+    }
+
+    fn part2(&self) -> Result<impl Display, Error> {
+        let histogram = self.histogram(40).ok_or("the polymer grows too long")?;
+        Ok(histogram.spread())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE_INPUT: &str = "NNCB
+
+CH -> B
+HH -> N
+CB -> H
+NH -> C
+HB -> C
+HC -> B
+HN -> C
+NN -> C
+BH -> H
+NC -> B
+NB -> B
+BN -> B
+BB -> N
+BC -> B
+CC -> N
+CN -> C";
+
+    #[test]
+    fn test_example_input() {
+        let engine = PolymerEngine::parse(EXAMPLE_INPUT).unwrap();
+
+        assert_eq!(engine.expand(1), "NCNBCHB");
+        assert_eq!(engine.histogram(10).unwrap().spread(), 1588);
+        assert_eq!(engine.histogram(40).unwrap().spread(), 2188189693529);
+
+        let histogram = engine.histogram(10).unwrap();
+        assert_eq!(histogram.length(), 3073);
+        assert_eq!(histogram.most_common(), Some(('B', 1749)));
+        assert_eq!(histogram.least_common(), Some(('H', 161)));
+    }
+
+    #[test]
+    fn test_against_expansion() {
+        let engines = [
+            PolymerEngine::parse(EXAMPLE_INPUT).unwrap(),
+            // Only some pairs have rules, the rest pass through.
+            PolymerEngine::parse("ABCA\n\nAB -> C\nCA -> A").unwrap(),
+            PolymerEngine::parse("X").unwrap(),
+        ];
+
+        for engine in engines.iter() {
+            let histograms = engine.histograms(10);
+            assert_eq!(histograms.len(), 11);
+
+            for histogram in histograms {
+                let polymer = engine.expand(histogram.step);
+                for &(c, count) in histogram.counts.iter() {
+                    assert_eq!(polymer.matches(c).count() as u128, count);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_large_counts() {
+        let engine = PolymerEngine::parse(EXAMPLE_INPUT).unwrap();
+
+        // The polymer is 3 * 2^steps + 1 long, which fits into a u128 up to step 126.
+        let histograms = engine.histograms(200);
+        assert_eq!(histograms.len(), 127);
+        assert_eq!(engine.histogram(200), None);
+
+        let modulus = 1_000_000_007;
+        let exact = engine.histogram(60).unwrap();
+        let modular = engine.histogram_mod(60, modulus);
+        for (&(c, count), &(d, residue)) in exact.counts.iter().zip(modular.iter()) {
+            assert_eq!(c, d);
+            assert_eq!((count % modulus as u128) as u64, residue);
+        }
+    }
+
+    #[test]
+    fn test_invalid_rules() {
+        let line = |input| PolymerEngine::parse(input).err().map(|e| e.line);
+        assert_eq!(line("NN\n\nNN -> C\nNC => N"), Some(4));
+        assert_eq!(line("NN\n\nNNN -> C"), Some(3));
+        assert_eq!(line("NN\n\nNN -> "), Some(3));
+    }
+}
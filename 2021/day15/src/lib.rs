@@ -0,0 +1,219 @@
+use std::{cmp::Reverse, collections::BinaryHeap, fmt::Display};
+
+use aoc::{Error, ParseError, Solution};
+
+#[derive(Clone, Debug)]
+pub struct RiskMap {
+    width: usize,
+    height: usize,
+    risks: Vec<u32>,
+}
+
+impl RiskMap {
+    pub fn parse(input: &str) -> Result<Self, ParseError> {
+        let lines = input
+            .lines()
+            .map(str::trim)
+            .enumerate()
+            .filter(|(_, line)| !line.is_empty());
+        let width = lines.clone().next().map_or(0, |(_, line)| line.len());
+
+        let mut risks = Vec::new();
+        for (i, line) in lines {
+            if line.len() != width {
+                return Err(ParseError::new(
+                    i + 1,
+                    format!("expected {} risk levels, found {}", width, line.len()),
+                ));
+            }
+            for c in line.chars() {
+                let risk = c
+                    .to_digit(10)
+                    .filter(|risk| (1..=9).contains(risk))
+                    .ok_or_else(|| ParseError::new(i + 1, format!("{c:?} isn't a risk level")))?;
+                risks.push(risk);
+            }
+        }
+
+        Ok(Self {
+            width,
+            height: risks.len() / width.max(1),
+            risks,
+        })
+    }
+
+    /// The map repeated `factor` times in both directions, every tile one step riskier than the
+    /// one to its left or above, wrapping from 9 back to 1. Risks are worked out on demand.
+    pub fn tiled(&self, factor: usize) -> TiledCave<'_> {
+        TiledCave { map: self, factor }
+    }
+}
+
+/// The lowest-risk way from the top left to the bottom right.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Route {
+    /// Sum of the risks of every position entered, the start doesn't count.
+    pub risk: u32,
+    /// Every position along the way as `(x, y)`, start and end included.
+    pub path: Vec<(usize, usize)>,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct TiledCave<'a> {
+    map: &'a RiskMap,
+    factor: usize,
+}
+
+impl<'a> TiledCave<'a> {
+    pub fn size(&self) -> (usize, usize) {
+        (self.map.width * self.factor, self.map.height * self.factor)
+    }
+
+    pub fn risk(&self, x: usize, y: usize) -> u32 {
+        let (tile_x, x) = (x / self.map.width, x % self.map.width);
+        let (tile_y, y) = (y / self.map.height, y % self.map.height);
+        let risk = self.map.risks[y * self.map.width + x];
+
+        (risk + (tile_x + tile_y) as u32 - 1) % 9 + 1
+    }
+
+    fn neighbours(&self, index: usize) -> impl Iterator<Item = usize> {
+        let (width, height) = self.size();
+        let (x, y) = (index % width, index / width);
+
+        [(1, 0), (-1, 0), (0, 1), (0, -1)]
+            .into_iter()
+            .filter_map(move |(dx, dy)| {
+                let x = x.checked_add_signed(dx).filter(|&x| x < width)?;
+                let y = y.checked_add_signed(dy).filter(|&y| y < height)?;
+                Some(y * width + x)
+            })
+    }
+
+    /// Dijkstra from the top left corner, stopping once the bottom right one is settled.
+    pub fn lowest_risk_route(&self) -> Option<Route> {
+        let (width, height) = self.size();
+        let target = (width * height).checked_sub(1)?;
+
+        let mut distances = vec![u32::MAX; width * height];
+        let mut previous = vec![None; width * height];
+        let mut queue = BinaryHeap::new();
+
+        distances[0] = 0;
+        queue.push(Reverse((0, 0)));
+
+        while let Some(Reverse((distance, u))) = queue.pop() {
+            if u == target {
+                break;
+            }
+            // Stale entry, `u` was reached more cheaply since it was queued.
+            if distance > distances[u] {
+                continue;
+            }
+
+            for v in self.neighbours(u) {
+                let alt = distance + self.risk(v % width, v / width);
+                if alt < distances[v] {
+                    distances[v] = alt;
+                    previous[v] = Some(u);
+                    queue.push(Reverse((alt, v)));
+                }
+            }
+        }
+
+        let mut path = vec![target];
+        while let Some(u) = previous[*path.last().unwrap()] {
+            path.push(u);
+        }
+        path.reverse();
+
+        Some(Route {
+            risk: distances[target],
+            path: path.into_iter().map(|i| (i % width, i / width)).collect(),
+        })
+    }
+}
+
+impl Solution for RiskMap {
+    fn parse(input: &str) -> Result<Self, Error> {
+        Ok(RiskMap::parse(input)?)
+    }
+
+    fn part1(&self) -> Result<impl Display, Error> {
+        let route = self
+            .tiled(1)
+            .lowest_risk_route()
+            .ok_or("the cave is empty")?;
+        Ok(route.risk)
+    }
+
+    fn part2(&self) -> Result<impl Display, Error> {
+        let route = self
+            .tiled(5)
+            .lowest_risk_route()
+            .ok_or("the cave is empty")?;
+        Ok(route.risk)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE_INPUT: &str = "1163751742
+1381373672
+2136511328
+3694931569
+7463417111
+1319128137
+1359912421
+3125421639
+1293138521
+2311944581";
+
+    #[test]
+    fn test_example_input() {
+        let map = RiskMap::parse(EXAMPLE_INPUT).unwrap();
+
+        let route = map.tiled(1).lowest_risk_route().unwrap();
+        assert_eq!(route.risk, 40);
+        assert_eq!(route.path.first(), Some(&(0, 0)));
+        assert_eq!(route.path.last(), Some(&(9, 9)));
+
+        let cave = map.tiled(5);
+        let route = cave.lowest_risk_route().unwrap();
+        assert_eq!(route.risk, 315);
+
+        let risk: u32 = route.path[1..].iter().map(|&(x, y)| cave.risk(x, y)).sum();
+        assert_eq!(risk, route.risk);
+        for step in route.path.windows(2) {
+            let ((x0, y0), (x1, y1)) = (step[0], step[1]);
+            assert_eq!(x0.abs_diff(x1) + y0.abs_diff(y1), 1);
+        }
+    }
+
+    #[test]
+    fn test_tiling() {
+        let map = RiskMap::parse("8").unwrap();
+        let cave = map.tiled(3);
+
+        assert_eq!(cave.size(), (3, 3));
+        let risks = (0..3)
+            .flat_map(|y| (0..3).map(move |x| cave.risk(x, y)))
+            .collect::<Vec<_>>();
+        assert_eq!(risks, vec![8, 9, 1, 9, 1, 2, 1, 2, 3]);
+
+        let route = cave.lowest_risk_route().unwrap();
+        assert_eq!(route.risk, 9 + 1 + 2 + 3);
+        assert_eq!(route.path.len(), 5);
+    }
+
+    #[test]
+    fn test_invalid_map() {
+        let line = |input| RiskMap::parse(input).err().map(|e| e.line);
+        assert_eq!(line("123\n\n1234"), Some(3));
+        assert_eq!(line("123\n1x3"), Some(2));
+        assert_eq!(line("19\n10"), Some(2));
+        assert_eq!(line(""), None);
+    }
+}
@@ -0,0 +1,1107 @@
+use std::fmt::{Display, Write};
+
+use aoc::{Error, Solution};
+use bitvec::prelude::*;
+
+/// Deeper nesting than this is rejected instead of risking a stack overflow.
+const MAX_DEPTH: usize = 1000;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Operator {
+    Sum,
+    Product,
+    Minimum,
+    Maximum,
+    GreaterThan,
+    LessThan,
+    EqualTo,
+}
+
+impl Operator {
+    fn from_typeid(type_id: u8) -> Option<Self> {
+        Some(match type_id {
+            0 => Self::Sum,
+            1 => Self::Product,
+            2 => Self::Minimum,
+            3 => Self::Maximum,
+            5 => Self::GreaterThan,
+            6 => Self::LessThan,
+            7 => Self::EqualTo,
+            _ => None?,
+        })
+    }
+
+    pub fn type_id(&self) -> u8 {
+        match self {
+            Self::Sum => 0,
+            Self::Product => 1,
+            Self::Minimum => 2,
+            Self::Maximum => 3,
+            Self::GreaterThan => 5,
+            Self::LessThan => 6,
+            Self::EqualTo => 7,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Sum => "sum",
+            Self::Product => "product",
+            Self::Minimum => "min",
+            Self::Maximum => "max",
+            Self::GreaterThan => "gt",
+            Self::LessThan => "lt",
+            Self::EqualTo => "eq",
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        Some(match name {
+            "sum" => Self::Sum,
+            "product" => Self::Product,
+            "min" => Self::Minimum,
+            "max" => Self::Maximum,
+            "gt" => Self::GreaterThan,
+            "lt" => Self::LessThan,
+            "eq" => Self::EqualTo,
+            _ => None?,
+        })
+    }
+
+    fn is_comparison(&self) -> bool {
+        matches!(self, Self::GreaterThan | Self::LessThan | Self::EqualTo)
+    }
+
+    fn accepts_operands(&self, count: usize) -> bool {
+        if self.is_comparison() {
+            count == 2
+        } else {
+            count > 0
+        }
+    }
+}
+
+/// How an operator packet announces the size of its subpackets.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LengthType {
+    /// Length type 0: 15 bits holding the total length of all subpackets in bits.
+    TotalLength,
+    /// Length type 1: 11 bits holding the number of subpackets.
+    SubpacketCount,
+}
+
+impl LengthType {
+    fn header_len(&self) -> usize {
+        match self {
+            Self::TotalLength => 1 + 15,
+            Self::SubpacketCount => 1 + 11,
+        }
+    }
+
+    fn fits(&self, subpackets: usize, total_length: usize) -> bool {
+        match self {
+            Self::TotalLength => total_length < 1 << 15,
+            Self::SubpacketCount => subpackets < 1 << 11,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OperatorPacket {
+    pub subpackets: Vec<Packet>,
+    pub operator: Operator,
+    pub length_type: LengthType,
+}
+
+impl OperatorPacket {
+    fn version_sum(&self) -> u32 {
+        self.subpackets
+            .iter()
+            .map(|packet| packet.version_sum())
+            .sum()
+    }
+
+    /// Returns `None` if a value doesn't fit into a `u64` or the operands don't match the
+    /// operator.
+    pub fn eval(&self) -> Option<u64> {
+        let values = self
+            .subpackets
+            .iter()
+            .map(Packet::value)
+            .collect::<Option<Vec<_>>>()?;
+
+        Some(match (self.operator, values.as_slice()) {
+            (Operator::Sum, values) => {
+                values.iter().try_fold(0u64, |acc, &v| acc.checked_add(v))?
+            }
+            (Operator::Product, values) => {
+                values.iter().try_fold(1u64, |acc, &v| acc.checked_mul(v))?
+            }
+            (Operator::Minimum, values) => *values.iter().min()?,
+            (Operator::Maximum, values) => *values.iter().max()?,
+            (Operator::GreaterThan, [a, b]) => (a > b) as u64,
+            (Operator::LessThan, [a, b]) => (a < b) as u64,
+            (Operator::EqualTo, [a, b]) => (a == b) as u64,
+            _ => None?,
+        })
+    }
+
+    /// The stored length type is used if it can represent the subpackets, otherwise the encoder
+    /// falls back to the other one. `total_length` is the encoded length of the subpackets.
+    fn encoded_length_type(&self, total_length: usize) -> Result<LengthType, EncodeError> {
+        [
+            self.length_type,
+            LengthType::TotalLength,
+            LengthType::SubpacketCount,
+        ]
+        .into_iter()
+        .find(|length_type| length_type.fits(self.subpackets.len(), total_length))
+        .ok_or(EncodeError::TooLarge {
+            subpackets: self.subpackets.len(),
+            total_length,
+        })
+    }
+}
+
+/// The value bits of a literal packet, kept at whatever width the transmission used.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Literal(BitVec<usize, Msb0>);
+
+impl Literal {
+    pub fn from_u64(value: u64) -> Self {
+        let groups = (64 - value.leading_zeros() as usize).div_ceil(4).max(1);
+
+        let mut bits = BitVec::new();
+        bits.extend_from_bitslice(&value.view_bits::<Msb0>()[64 - groups * 4..]);
+
+        Self(bits)
+    }
+
+    /// Parses hex digits into a literal of any width.
+    pub fn from_hex(digits: &str) -> Option<Self> {
+        let mut bits = BitVec::new();
+        for c in digits.chars() {
+            bits.extend_from_bitslice(&c.to_digit(16)?.view_bits::<Msb0>()[28..]);
+        }
+
+        (!bits.is_empty()).then_some(Self(bits))
+    }
+
+    pub fn bits(&self) -> &BitSlice<usize, Msb0> {
+        &self.0
+    }
+
+    /// Returns `None` if the literal has more than 64 significant bits.
+    pub fn to_u64(&self) -> Option<u64> {
+        let significant = &self.0[self.0.first_one().unwrap_or(self.0.len())..];
+
+        match significant.len() {
+            0 => Some(0),
+            1..=64 => Some(significant.load_be()),
+            _ => None,
+        }
+    }
+}
+
+impl Display for Literal {
+    /// Literals too wide for a `u64` are written in hex.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.to_u64() {
+            Some(value) => write!(f, "{value}"),
+            None => {
+                write!(f, "0x")?;
+                for nibble in self.0.rchunks(4).rev() {
+                    write!(f, "{:X}", nibble.load_be::<u8>())?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PacketType {
+    Literal(Literal),
+    Operator(OperatorPacket),
+}
+
+impl PacketType {
+    fn type_id(&self) -> u8 {
+        match self {
+            PacketType::Literal(_) => 4,
+            PacketType::Operator(operator) => operator.operator.type_id(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DecodeErrorCause {
+    InvalidHexDigit(char),
+    UnexpectedEnd { needed: usize },
+    UnknownTypeId(u8),
+    WrongOperandCount { operator: Operator, count: usize },
+    LengthMismatch { expected: usize, actual: usize },
+    TooDeep,
+}
+
+/// A decoding failure at `offset` bits into the transmission.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DecodeError {
+    pub offset: usize,
+    pub cause: DecodeErrorCause,
+}
+
+impl Display for DecodeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "bit {}: ", self.offset)?;
+
+        match &self.cause {
+            DecodeErrorCause::InvalidHexDigit(c) => write!(f, "{c:?} is not a hex digit"),
+            DecodeErrorCause::UnexpectedEnd { needed } => {
+                write!(f, "transmission ends {needed} bits too early")
+            }
+            DecodeErrorCause::UnknownTypeId(type_id) => write!(f, "unknown type id {type_id}"),
+            DecodeErrorCause::WrongOperandCount { operator, count } => {
+                write!(f, "{} can't take {count} subpackets", operator.name())
+            }
+            DecodeErrorCause::LengthMismatch { expected, actual } => write!(
+                f,
+                "subpackets take {actual} bits, but the header announces {expected}"
+            ),
+            DecodeErrorCause::TooDeep => write!(f, "packets nested deeper than {MAX_DEPTH}"),
+        }
+    }
+}
+
+impl std::error::Error for DecodeError {}
+
+struct Decoder<'a, T: BitStore> {
+    bits: &'a BitSlice<T, Msb0>,
+    offset: usize,
+    depth: usize,
+}
+
+impl<'a, T: BitStore> Decoder<'a, T> {
+    fn error(offset: usize, cause: DecodeErrorCause) -> DecodeError {
+        DecodeError { offset, cause }
+    }
+
+    fn read(&mut self, len: usize) -> Result<&'a BitSlice<T, Msb0>, DecodeError> {
+        let bits = self
+            .bits
+            .get(self.offset..self.offset + len)
+            .ok_or_else(|| {
+                let needed = self.offset + len - self.bits.len();
+                Self::error(self.offset, DecodeErrorCause::UnexpectedEnd { needed })
+            })?;
+        self.offset += len;
+
+        Ok(bits)
+    }
+
+    fn packet(&mut self) -> Result<Packet, DecodeError> {
+        let start = self.offset;
+        if self.depth >= MAX_DEPTH {
+            return Err(Self::error(start, DecodeErrorCause::TooDeep));
+        }
+
+        let version = self.read(3)?.load_be::<u8>();
+        let type_id = self.read(3)?.load_be::<u8>();
+
+        let inner = match type_id {
+            4 => PacketType::Literal(self.literal()?),
+            _ => {
+                let operator = Operator::from_typeid(type_id).ok_or(Self::error(
+                    start + 3,
+                    DecodeErrorCause::UnknownTypeId(type_id),
+                ))?;
+
+                self.depth += 1;
+                let packet = self.operator(start, operator)?;
+                self.depth -= 1;
+
+                PacketType::Operator(packet)
+            }
+        };
+
+        Ok(Packet { version, inner })
+    }
+
+    fn literal(&mut self) -> Result<Literal, DecodeError> {
+        let mut bv = BitVec::<usize, Msb0>::new();
+
+        loop {
+            let group = self.read(5)?;
+            bv.extend_from_bitslice(&group[1..]);
+
+            if !group[0] {
+                break;
+            }
+        }
+
+        Ok(Literal(bv))
+    }
+
+    fn operator(
+        &mut self,
+        start: usize,
+        operator: Operator,
+    ) -> Result<OperatorPacket, DecodeError> {
+        let length_type_id = self.read(1)?[0];
+
+        let (subpackets, length_type) = match length_type_id {
+            false => {
+                let total_length = self.read(15)?.load_be::<usize>();
+                let mut subpackets = Vec::new();
+                let subpackets_start = self.offset;
+
+                while self.offset - subpackets_start < total_length {
+                    subpackets.push(self.packet()?);
+                }
+
+                let actual = self.offset - subpackets_start;
+                if actual != total_length {
+                    return Err(Self::error(
+                        start,
+                        DecodeErrorCause::LengthMismatch {
+                            expected: total_length,
+                            actual,
+                        },
+                    ));
+                }
+
+                (subpackets, LengthType::TotalLength)
+            }
+            true => {
+                let num_subpackets = self.read(11)?.load_be::<usize>();
+                let mut subpackets = Vec::with_capacity(num_subpackets);
+
+                for _ in 0..num_subpackets {
+                    subpackets.push(self.packet()?);
+                }
+
+                (subpackets, LengthType::SubpacketCount)
+            }
+        };
+
+        if !operator.accepts_operands(subpackets.len()) {
+            return Err(Self::error(
+                start,
+                DecodeErrorCause::WrongOperandCount {
+                    operator,
+                    count: subpackets.len(),
+                },
+            ));
+        }
+
+        Ok(OperatorPacket {
+            subpackets,
+            operator,
+            length_type,
+        })
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Packet {
+    pub version: u8,
+    pub inner: PacketType,
+}
+
+impl Packet {
+    /// Decodes one packet and returns it together with the bits following it.
+    pub fn from_bits<T: BitStore>(
+        bv: &BitSlice<T, Msb0>,
+    ) -> Result<(Self, &BitSlice<T, Msb0>), DecodeError> {
+        let mut decoder = Decoder {
+            bits: bv,
+            offset: 0,
+            depth: 0,
+        };
+        let packet = decoder.packet()?;
+
+        Ok((packet, &bv[decoder.offset..]))
+    }
+
+    /// Decodes a hex transmission, ignoring the padding after the outermost packet.
+    pub fn from_hex(input: &str) -> Result<Self, DecodeError> {
+        let bits = parse_packet_to_bitvec(input)?;
+
+        Ok(Self::from_bits(&bits)?.0)
+    }
+
+    pub fn version_sum(&self) -> u32 {
+        let subversions = match &self.inner {
+            PacketType::Literal(_) => 0,
+            PacketType::Operator(packet) => packet.version_sum(),
+        };
+
+        self.version as u32 + subversions
+    }
+
+    /// Returns `None` if the value doesn't fit into a `u64`.
+    pub fn value(&self) -> Option<u64> {
+        match &self.inner {
+            PacketType::Literal(literal) => literal.to_u64(),
+            PacketType::Operator(operator) => operator.eval(),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum EncodeError {
+    TooLarge {
+        subpackets: usize,
+        total_length: usize,
+    },
+}
+
+impl Display for EncodeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EncodeError::TooLarge {
+                subpackets,
+                total_length,
+            } => write!(
+                f,
+                "{subpackets} subpackets with {total_length} bits fit in neither length type"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for EncodeError {}
+
+impl Packet {
+    pub fn literal(value: u64) -> Self {
+        Self {
+            version: 0,
+            inner: PacketType::Literal(Literal::from_u64(value)),
+        }
+    }
+
+    pub fn operator(operator: Operator, subpackets: Vec<Packet>) -> Self {
+        Self {
+            version: 0,
+            inner: PacketType::Operator(OperatorPacket {
+                subpackets,
+                operator,
+                length_type: LengthType::TotalLength,
+            }),
+        }
+    }
+
+    pub fn with_version(mut self, version: u8) -> Self {
+        assert!(version < 8, "versions only have 3 bits");
+        self.version = version;
+        self
+    }
+
+    pub fn with_length_type(mut self, length_type: LengthType) -> Self {
+        if let PacketType::Operator(operator) = &mut self.inner {
+            operator.length_type = length_type;
+        }
+        self
+    }
+
+    /// Builds a packet from an S-expression like `(sum 1 (product 2 3))`.
+    pub fn from_sexpr(input: &str) -> Result<Self, SexprError> {
+        let tokens = input
+            .replace('(', " ( ")
+            .replace(')', " ) ")
+            .split_whitespace()
+            .map(str::to_owned)
+            .collect::<Vec<_>>();
+        let mut tokens = tokens.iter().map(String::as_str).peekable();
+
+        let packet = parse_sexpr(&mut tokens)?;
+        match tokens.next() {
+            Some(token) => Err(SexprError::UnexpectedToken(token.to_owned())),
+            None => Ok(packet),
+        }
+    }
+
+    /// Returns the encoded length of the packet and pushes the length of every packet's
+    /// contents, the literal groups or the subpackets, in the order `pretty_into` visits them.
+    fn layout(&self, contents: &mut Vec<usize>) -> usize {
+        let index = contents.len();
+        contents.push(0);
+
+        let (content_len, header) = match &self.inner {
+            PacketType::Literal(literal) => (literal.0.len().div_ceil(4).max(1) * 5, 0),
+            PacketType::Operator(operator) => {
+                let total_length = operator
+                    .subpackets
+                    .iter()
+                    .map(|packet| packet.layout(contents))
+                    .sum::<usize>();
+                // the header length only depends on the length type, so a fallback is fine here
+                let header = operator
+                    .encoded_length_type(total_length)
+                    .unwrap_or(operator.length_type)
+                    .header_len();
+                (total_length, header)
+            }
+        };
+        contents[index] = content_len;
+
+        6 + header + content_len
+    }
+
+    pub fn encode_bits(&self, out: &mut BitVec<usize, Msb0>) -> Result<(), EncodeError> {
+        out.extend_from_bitslice(&self.version.view_bits::<Msb0>()[5..]);
+        out.extend_from_bitslice(&self.inner.type_id().view_bits::<Msb0>()[5..]);
+
+        match &self.inner {
+            PacketType::Literal(literal) => {
+                let padding = (4 - literal.0.len() % 4) % 4;
+                let mut padded = bitvec![usize, Msb0; 0; padding];
+                padded.extend_from_bitslice(&literal.0);
+                if padded.is_empty() {
+                    padded.resize(4, false);
+                }
+
+                let groups = padded.len() / 4;
+                for (i, group) in padded.chunks(4).enumerate() {
+                    out.push(i + 1 < groups);
+                    out.extend_from_bitslice(group);
+                }
+            }
+            PacketType::Operator(operator) => {
+                let mut subpackets = BitVec::new();
+                for packet in &operator.subpackets {
+                    packet.encode_bits(&mut subpackets)?;
+                }
+
+                match operator.encoded_length_type(subpackets.len())? {
+                    LengthType::TotalLength => {
+                        out.push(false);
+                        out.extend_from_bitslice(&subpackets.len().view_bits::<Msb0>()[64 - 15..]);
+                    }
+                    LengthType::SubpacketCount => {
+                        let count = operator.subpackets.len();
+                        out.push(true);
+                        out.extend_from_bitslice(&count.view_bits::<Msb0>()[64 - 11..]);
+                    }
+                }
+                out.extend_from_bitslice(&subpackets);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Encodes the packet as a hex transmission, padded with zeros to a whole number of digits.
+    pub fn encode(&self) -> Result<String, EncodeError> {
+        let mut bits = BitVec::<usize, Msb0>::new();
+        self.encode_bits(&mut bits)?;
+
+        let padding = (4 - bits.len() % 4) % 4;
+        bits.resize(bits.len() + padding, false);
+
+        Ok(bits
+            .chunks(4)
+            .map(|nibble| {
+                char::from_digit(nibble.load_be::<u32>(), 16)
+                    .unwrap()
+                    .to_ascii_uppercase()
+            })
+            .collect())
+    }
+
+    /// Lists every packet with its bit range, version and type id, indented by depth.
+    pub fn pretty(&self) -> String {
+        let mut contents = Vec::new();
+        self.layout(&mut contents);
+
+        let mut out = String::new();
+        self.pretty_into(&mut out, &mut contents.into_iter(), 0, 0);
+        out
+    }
+
+    /// Returns the encoded length of the packet, so the caller can place the next sibling.
+    fn pretty_into(
+        &self,
+        out: &mut String,
+        contents: &mut impl Iterator<Item = usize>,
+        offset: usize,
+        depth: usize,
+    ) -> usize {
+        let content_len = contents.next().unwrap();
+        let header = match &self.inner {
+            PacketType::Literal(_) => None,
+            PacketType::Operator(operator) => Some(
+                operator
+                    .encoded_length_type(content_len)
+                    .unwrap_or(operator.length_type),
+            ),
+        };
+        let len = 6 + header.map_or(0, |length_type| length_type.header_len()) + content_len;
+        write!(
+            out,
+            "{:>6}..{:<6} {:indent$}v{} type {} ",
+            offset,
+            offset + len,
+            "",
+            self.version,
+            self.inner.type_id(),
+            indent = depth * 2,
+        )
+        .unwrap();
+
+        match &self.inner {
+            PacketType::Literal(literal) => writeln!(out, "literal {literal}").unwrap(),
+            PacketType::Operator(operator) => {
+                let length_type = header.unwrap();
+                let length = match length_type {
+                    LengthType::TotalLength => format!("{content_len} bits"),
+                    LengthType::SubpacketCount => {
+                        format!("{} subpackets", operator.subpackets.len())
+                    }
+                };
+                let value = match self.value() {
+                    Some(value) => value.to_string(),
+                    None => "overflow".to_owned(),
+                };
+                writeln!(out, "{} ({length}) = {value}", operator.operator.name()).unwrap();
+
+                let mut offset = offset + 6 + length_type.header_len();
+                for packet in &operator.subpackets {
+                    offset += packet.pretty_into(out, contents, offset, depth + 1);
+                }
+            }
+        }
+
+        len
+    }
+}
+
+impl Display for Packet {
+    /// Formats the packet as the S-expression accepted by [`Packet::from_sexpr`].
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.inner {
+            PacketType::Literal(literal) => write!(f, "{literal}"),
+            PacketType::Operator(operator) => {
+                write!(f, "({}", operator.operator.name())?;
+                for packet in &operator.subpackets {
+                    write!(f, " {packet}")?;
+                }
+                write!(f, ")")
+            }
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum SexprError {
+    UnexpectedEnd,
+    UnexpectedToken(String),
+    UnknownOperator(String),
+    WrongOperandCount(Operator, usize),
+}
+
+impl Display for SexprError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SexprError::UnexpectedEnd => write!(f, "unexpected end of expression"),
+            SexprError::UnexpectedToken(token) => write!(f, "unexpected token {token:?}"),
+            SexprError::UnknownOperator(name) => write!(f, "unknown operator {name:?}"),
+            SexprError::WrongOperandCount(operator, count) => {
+                write!(f, "{} can't take {count} operands", operator.name())
+            }
+        }
+    }
+}
+
+impl std::error::Error for SexprError {}
+
+fn parse_sexpr<'a>(
+    tokens: &mut std::iter::Peekable<impl Iterator<Item = &'a str>>,
+) -> Result<Packet, SexprError> {
+    match tokens.next().ok_or(SexprError::UnexpectedEnd)? {
+        "(" => {
+            let name = tokens.next().ok_or(SexprError::UnexpectedEnd)?;
+            let operator =
+                Operator::from_name(name).ok_or(SexprError::UnknownOperator(name.to_owned()))?;
+
+            let mut subpackets = Vec::new();
+            loop {
+                match tokens.peek() {
+                    Some(&")") => {
+                        tokens.next();
+                        break;
+                    }
+                    Some(_) => subpackets.push(parse_sexpr(tokens)?),
+                    None => Err(SexprError::UnexpectedEnd)?,
+                }
+            }
+
+            let count = subpackets.len();
+            if !operator.accepts_operands(count) {
+                return Err(SexprError::WrongOperandCount(operator, count));
+            }
+
+            Ok(Packet::operator(operator, subpackets))
+        }
+        token => {
+            let literal = match token.strip_prefix("0x") {
+                Some(digits) => Literal::from_hex(digits),
+                None => token.parse::<u64>().ok().map(Literal::from_u64),
+            }
+            .ok_or(SexprError::UnexpectedToken(token.to_owned()))?;
+
+            Ok(Packet {
+                version: 0,
+                inner: PacketType::Literal(literal),
+            })
+        }
+    }
+}
+
+pub fn parse_packet_to_bitvec(input: &str) -> Result<BitVec<usize, Msb0>, DecodeError> {
+    let mut bits = BitVec::new();
+    for (i, c) in input.trim().chars().enumerate() {
+        let nibble = c.to_digit(16).ok_or(DecodeError {
+            offset: i * 4,
+            cause: DecodeErrorCause::InvalidHexDigit(c),
+        })?;
+        bits.extend_from_bitslice(&nibble.view_bits::<Msb0>()[28..]);
+    }
+
+    Ok(bits)
+}
+
+impl Solution for Packet {
+    fn parse(input: &str) -> Result<Self, Error> {
+        Ok(Packet::from_hex(input)?)
+    }
+
+    fn part1(&self) -> Result<impl Display, Error> {
+        Ok(self.version_sum())
+    }
+
+    fn part2(&self) -> Result<impl Display, Error> {
+        self.value()
+            .ok_or_else(|| "the packet value doesn't fit into 64 bits".into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_literal_packet_input() {
+        let bv = parse_packet_to_bitvec("D2FE28").unwrap();
+        dbg!(&bv);
+        let (packet, _) = Packet::from_bits(&bv).unwrap();
+        dbg!(&packet);
+
+        assert_eq!(packet.version, 6);
+
+        let PacketType::Literal(literal) = packet.inner else {panic!("wrong packet type")};
+        assert_eq!(literal.to_u64(), Some(2021));
+    }
+
+    #[test]
+    fn test_operator_packet_lentype0_input() {
+        let bv = parse_packet_to_bitvec("38006F45291200").unwrap();
+        dbg!(&bv);
+        let (packet, _) = Packet::from_bits(&bv).unwrap();
+        dbg!(&packet);
+
+        assert_eq!(packet.version, 1);
+
+        let PacketType::Operator(operator) = packet.inner else { panic!("wrong packet type") };
+        assert_eq!(operator.subpackets.len(), 2);
+
+        let PacketType::Literal(ref literal) = &operator.subpackets[0].inner else { panic!() };
+        assert_eq!(literal.to_u64(), Some(10));
+
+        let PacketType::Literal(ref literal) = &operator.subpackets[1].inner else { panic!() };
+        assert_eq!(literal.to_u64(), Some(20));
+    }
+
+    #[test]
+    fn test_operator_packet_lentype1_input() {
+        let bv = parse_packet_to_bitvec("EE00D40C823060").unwrap();
+        dbg!(&bv);
+        let (packet, _) = Packet::from_bits(&bv).unwrap();
+        dbg!(&packet);
+
+        assert_eq!(packet.version, 7);
+
+        let PacketType::Operator(operator) = packet.inner else { panic!("wrong packet type") };
+        assert_eq!(operator.subpackets.len(), 3);
+
+        let PacketType::Literal(ref literal) = &operator.subpackets[0].inner else { panic!() };
+        assert_eq!(literal.to_u64(), Some(1));
+
+        let PacketType::Literal(ref literal) = &operator.subpackets[1].inner else { panic!() };
+        assert_eq!(literal.to_u64(), Some(2));
+
+        let PacketType::Literal(ref literal) = &operator.subpackets[2].inner else { panic!() };
+        assert_eq!(literal.to_u64(), Some(3));
+    }
+
+    #[test]
+    fn test_version_sums_1() {
+        let bv = parse_packet_to_bitvec("8A004A801A8002F478").unwrap();
+        let (packet, _) = Packet::from_bits(&bv).unwrap();
+        assert_eq!(packet.version_sum(), 16);
+    }
+
+    #[test]
+    fn test_version_sums_2() {
+        let bv = parse_packet_to_bitvec("620080001611562C8802118E34").unwrap();
+        let (packet, _) = Packet::from_bits(&bv).unwrap();
+        assert_eq!(packet.version_sum(), 12);
+    }
+
+    #[test]
+    fn test_version_sums_3() {
+        let bv = parse_packet_to_bitvec("C0015000016115A2E0802F182340").unwrap();
+        let (packet, _) = Packet::from_bits(&bv).unwrap();
+        assert_eq!(packet.version_sum(), 23);
+    }
+
+    #[test]
+    fn test_version_sums_4() {
+        let bv = parse_packet_to_bitvec("A0016C880162017C3686B18A3D4780").unwrap();
+        let (packet, _) = Packet::from_bits(&bv).unwrap();
+        assert_eq!(packet.version_sum(), 31);
+    }
+
+    #[test]
+    fn test_operators() {
+        let testcases = [
+            ("C200B40A82", 3),
+            ("04005AC33890", 54),
+            ("880086C3E88112", 7),
+            ("CE00C43D881120", 9),
+            ("D8005AC2A8F0", 1),
+            ("F600BC2D8F", 0),
+            ("9C005AC2F8F0", 0),
+            ("9C0141080250320F1802104A08", 1),
+        ];
+
+        for (input, result) in testcases {
+            let bv = parse_packet_to_bitvec(input).unwrap();
+            let (packet, _) = Packet::from_bits(&bv).unwrap();
+
+            let PacketType::Operator(operator) = packet.inner else { panic!("wrong packet type") };
+            assert_eq!(operator.eval(), Some(result));
+        }
+    }
+
+    #[test]
+    fn test_encode_examples() {
+        for input in ["D2FE28", "38006F45291200", "EE00D40C823060"] {
+            let packet = Packet::from_hex(input).unwrap();
+            let encoded = packet.encode().unwrap();
+
+            assert!(input.starts_with(encoded.trim_end_matches('0')));
+        }
+    }
+
+    #[test]
+    fn test_sexpr() {
+        let packet = Packet::from_sexpr("(sum 1 (product 2 3))").unwrap();
+        assert_eq!(packet.value(), Some(7));
+        assert_eq!(packet.to_string(), "(sum 1 (product 2 3))");
+
+        assert_eq!(
+            Packet::from_sexpr("(lt 1)"),
+            Err(SexprError::WrongOperandCount(Operator::LessThan, 1))
+        );
+        assert_eq!(
+            Packet::from_sexpr("(avg 1 2)"),
+            Err(SexprError::UnknownOperator("avg".to_owned()))
+        );
+        assert_eq!(Packet::from_sexpr("(sum 1"), Err(SexprError::UnexpectedEnd));
+    }
+
+    #[test]
+    fn test_pretty() {
+        let packet = Packet::from_hex("38006F45291200").unwrap();
+        let pretty = packet.pretty();
+        let lines = pretty.lines().collect::<Vec<_>>();
+
+        assert_eq!(lines.len(), 3);
+        assert!(lines[0].starts_with("     0..49     v1 type 6 lt (27 bits)"));
+        assert!(lines[1].starts_with("    22..33       v6 type 4 literal 10"));
+        assert!(lines[2].starts_with("    33..49       v2 type 4 literal 20"));
+    }
+
+    #[test]
+    fn test_deeply_nested() {
+        // Past a few hundred levels the outer subpackets no longer fit a 15 bit total length.
+        let mut packet = Packet::literal(1);
+        for _ in 0..400 {
+            packet = Packet::operator(Operator::Maximum, vec![packet, Packet::literal(u64::MAX)]);
+        }
+
+        let encoded = packet.encode().unwrap();
+        let decoded = Packet::from_hex(&encoded).unwrap();
+        // the outer packets fell back to counting subpackets, which the decoded ones remember
+        assert_eq!(decoded.to_string(), packet.to_string());
+        assert_eq!(decoded.encode().unwrap(), encoded);
+        assert_eq!(decoded.value(), Some(u64::MAX));
+
+        let mut bits = BitVec::new();
+        packet.encode_bits(&mut bits).unwrap();
+        let pretty = packet.pretty();
+        assert_eq!(pretty.lines().count(), 801);
+        assert!(pretty.starts_with(&format!(
+            "     0..{:<6} v0 type 3 max (2 subpackets)",
+            bits.len()
+        )));
+    }
+
+    #[test]
+    fn test_decode_errors() {
+        let error = |input| Packet::from_hex(input).unwrap_err();
+
+        assert_eq!(
+            error("D2FG28"),
+            DecodeError {
+                offset: 12,
+                cause: DecodeErrorCause::InvalidHexDigit('G'),
+            }
+        );
+        // the literal 2021 cut off in its last group
+        assert_eq!(
+            error("D2FE"),
+            DecodeError {
+                offset: 16,
+                cause: DecodeErrorCause::UnexpectedEnd { needed: 5 },
+            }
+        );
+        // the example operator announcing 26 instead of 27 bits of subpackets
+        assert_eq!(
+            error("38006B45291200"),
+            DecodeError {
+                offset: 0,
+                cause: DecodeErrorCause::LengthMismatch {
+                    expected: 26,
+                    actual: 27
+                },
+            }
+        );
+    }
+
+    #[test]
+    fn test_wrong_operand_count() {
+        // the S-expression builder rejects this, the plain constructor doesn't
+        let mut packet = Packet::operator(
+            Operator::LessThan,
+            vec![Packet::literal(1), Packet::literal(2), Packet::literal(3)],
+        )
+        .with_version(3);
+        assert_eq!(packet.value(), None);
+
+        packet = packet.with_length_type(LengthType::SubpacketCount);
+        assert_eq!(
+            Packet::from_hex(&packet.encode().unwrap()),
+            Err(DecodeError {
+                offset: 0,
+                cause: DecodeErrorCause::WrongOperandCount {
+                    operator: Operator::LessThan,
+                    count: 3
+                },
+            })
+        );
+    }
+
+    #[test]
+    fn test_wide_literal() {
+        let wide = Packet::from_sexpr("(sum 0x123456789ABCDEF012 1)").unwrap();
+        let decoded = Packet::from_hex(&wide.encode().unwrap()).unwrap();
+
+        assert_eq!(decoded, wide);
+        assert_eq!(decoded.value(), None);
+        assert_eq!(decoded.to_string(), "(sum 0x123456789ABCDEF012 1)");
+
+        let PacketType::Operator(operator) = &decoded.inner else {
+            panic!("wrong packet type")
+        };
+        let PacketType::Literal(literal) = &operator.subpackets[0].inner else {
+            panic!("wrong packet type")
+        };
+        assert_eq!(literal.bits().len(), 72);
+
+        // leading zero groups don't count towards the width
+        assert_eq!(
+            Literal::from_hex("000000000000000000FF").unwrap().to_u64(),
+            Some(255)
+        );
+    }
+
+    mod proptests {
+        use super::*;
+        use proptest::prelude::*;
+
+        fn packet_strategy() -> impl Strategy<Value = Packet> {
+            let literal = (0u8..8, any::<u64>())
+                .prop_map(|(version, value)| Packet::literal(value).with_version(version));
+
+            literal.prop_recursive(4, 64, 5, |inner| {
+                (
+                    0u8..8,
+                    0usize..7,
+                    any::<bool>(),
+                    prop::collection::vec(inner, 1..5),
+                )
+                    .prop_map(|(version, operator, count, mut subpackets)| {
+                        let operator = [
+                            Operator::Sum,
+                            Operator::Product,
+                            Operator::Minimum,
+                            Operator::Maximum,
+                            Operator::GreaterThan,
+                            Operator::LessThan,
+                            Operator::EqualTo,
+                        ][operator];
+                        if operator.is_comparison() {
+                            subpackets.resize(2, Packet::literal(0));
+                        }
+                        let length_type = if count {
+                            LengthType::SubpacketCount
+                        } else {
+                            LengthType::TotalLength
+                        };
+
+                        Packet::operator(operator, subpackets)
+                            .with_version(version)
+                            .with_length_type(length_type)
+                    })
+            })
+        }
+
+        proptest! {
+            #[test]
+            fn roundtrip(packet in packet_strategy()) {
+                let encoded = packet.encode().unwrap();
+                let decoded = Packet::from_hex(&encoded).unwrap();
+
+                prop_assert_eq!(decoded, packet);
+            }
+
+            #[test]
+            fn decode_never_panics(bytes in prop::collection::vec(any::<u8>(), 0..64)) {
+                let hex = bytes.iter().map(|b| format!("{b:02X}")).collect::<String>();
+
+                if let Ok(packet) = Packet::from_hex(&hex) {
+                    packet.value();
+                    packet.pretty();
+                    packet.encode().unwrap();
+                }
+            }
+
+            #[test]
+            fn decode_text_never_panics(input in "\\PC*") {
+                let _ = Packet::from_hex(&input);
+            }
+        }
+    }
+}
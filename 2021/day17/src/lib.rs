@@ -0,0 +1,363 @@
+use std::{fmt::Display, ops::RangeInclusive, str::FromStr};
+
+use aoc::{Error, Solution};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TargetArea {
+    pub x: RangeInclusive<i32>,
+    pub y: RangeInclusive<i32>,
+}
+
+impl FromStr for TargetArea {
+    type Err = ();
+
+    /// Parses the puzzle's `target area: x=20..30, y=-10..-5` line.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parse_range = |range: &str, axis: &str| -> Option<RangeInclusive<i32>> {
+            let (start, end) = range.strip_prefix(axis)?.split_once("..")?;
+            let (start, end) = (start.parse::<i32>().ok()?, end.parse::<i32>().ok()?);
+
+            Some(start.min(end)..=start.max(end))
+        };
+
+        let (x, y) = s
+            .trim()
+            .strip_prefix("target area: ")
+            .and_then(|area| area.split_once(", "))
+            .ok_or(())?;
+
+        Ok(Self {
+            x: parse_range(x, "x=").ok_or(())?,
+            y: parse_range(y, "y=").ok_or(())?,
+        })
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct InfiniteSolutions;
+
+impl Display for InfiniteSolutions {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "the probe can stop above the target and fall into it from any height"
+        )
+    }
+}
+
+impl std::error::Error for InfiniteSolutions {}
+
+/// Inclusive ranges of step counts, `i64::MAX` as end meaning "forever".
+type Steps = Vec<(i64, i64)>;
+
+/// Position after `t` steps without drag: `v t - t (t - 1) / 2`.
+fn position(v: i64, t: i64) -> i64 {
+    v * t - t * (t - 1) / 2
+}
+
+/// The steps `t >= 1` at which `position(v, t) >= bound`.
+///
+/// That is `t² - (2v + 1) t + 2 bound <= 0`, so `t` lies between the roots
+/// `(2v + 1 ± √((2v + 1)² - 8 bound)) / 2`. The integer square root can put either end off by a
+/// step, which checking the neighbours fixes. Worked out in i128 so nothing overflows.
+fn steps_at_least(v: i64, bound: i64) -> Option<(i64, i64)> {
+    let (v, bound) = (v as i128, bound as i128);
+    let above = |t: i128| v * t - t * (t - 1) / 2 >= bound;
+
+    let b = 2 * v + 1;
+    let discriminant = b * b - 8 * bound;
+    if discriminant < 0 {
+        return None;
+    }
+    let root = discriminant.isqrt();
+
+    let mut last = (b + root).div_euclid(2);
+    while above(last + 1) {
+        last += 1;
+    }
+    while last >= 1 && !above(last) {
+        last -= 1;
+    }
+    if last < 1 {
+        return None;
+    }
+
+    let mut first = (b - root).div_euclid(2).clamp(1, last);
+    while first > 1 && above(first - 1) {
+        first -= 1;
+    }
+    while !above(first) {
+        first += 1;
+    }
+
+    Some((first as i64, last as i64))
+}
+
+/// The steps `t >= 1` at which `position(v, t)` lies in `range`: everything above the lower
+/// bound minus everything above the upper bound, which leaves a rising and a falling part.
+fn steps_in_range(v: i64, range: (i64, i64)) -> Steps {
+    let Some((first, last)) = steps_at_least(v, range.0) else {
+        return Vec::new();
+    };
+
+    match steps_at_least(v, range.1 + 1) {
+        Some((above_first, above_last)) => [(first, above_first - 1), (above_last + 1, last)]
+            .into_iter()
+            .filter(|(start, end)| start <= end)
+            .collect(),
+        None => vec![(first, last)],
+    }
+}
+
+fn y_steps(v_y: i64, target: &TargetArea) -> Steps {
+    steps_in_range(v_y, (*target.y.start() as i64, *target.y.end() as i64))
+}
+
+fn x_steps(v_x: i64, target: &TargetArea) -> Steps {
+    let (start, end) = (*target.x.start() as i64, *target.x.end() as i64);
+
+    if v_x == 0 {
+        return if start <= 0 && end >= 0 {
+            vec![(1, i64::MAX)]
+        } else {
+            Vec::new()
+        };
+    }
+
+    // moving left is the mirror image of moving right
+    let (v, range) = if v_x > 0 {
+        (v_x, (start, end))
+    } else {
+        (-v_x, (-end, -start))
+    };
+
+    // drag stops the probe at step `v`, from then on it stays where it is
+    steps_in_range(v, range)
+        .into_iter()
+        .filter(|(first, _)| *first <= v)
+        .map(|(first, last)| {
+            if last >= v {
+                (first, i64::MAX)
+            } else {
+                (first, last)
+            }
+        })
+        .collect()
+}
+
+fn overlaps(a: &Steps, b: &Steps) -> bool {
+    a.iter()
+        .any(|a| b.iter().any(|b| a.0.max(b.0) <= a.1.min(b.1)))
+}
+
+impl TargetArea {
+    /// Every launch velocity that has the probe inside the target after some step.
+    ///
+    /// Each axis is solved on its own for the steps at which it is in range, a velocity is valid
+    /// if the steps of both its components overlap.
+    pub fn valid_velocities(&self) -> Result<Vec<(i32, i32)>, InfiniteSolutions> {
+        let (x_start, x_end) = (*self.x.start() as i64, *self.x.end() as i64);
+        let (y_start, y_end) = (*self.y.start() as i64, *self.y.end() as i64);
+
+        let xs = (x_start.min(0)..=x_end.max(0))
+            .map(|v_x| (v_x, x_steps(v_x, self)))
+            .filter(|(_, steps)| !steps.is_empty())
+            .collect::<Vec<_>>();
+
+        let v_y_range = if y_start > 0 {
+            // the probe passes every height near its peak, so it has to reach its peak inside
+            0..=y_end
+        } else if y_end < 0 {
+            // coming back down, the probe passes y = 0 with velocity -(v_y + 1)
+            y_start..=-y_start - 1
+        } else {
+            // y = 0 is in the target and the probe passes it for every v_y > 0, so only a
+            // bounded number of steps in x keeps this finite
+            let max_steps = xs
+                .iter()
+                .flat_map(|(_, steps)| steps.iter().map(|(_, last)| *last))
+                .max()
+                .unwrap_or(0);
+            if max_steps == i64::MAX {
+                return Err(InfiniteSolutions);
+            }
+
+            y_start..=y_end + max_steps
+        };
+
+        let mut velocities = Vec::new();
+        for v_y in v_y_range {
+            let steps = y_steps(v_y, self);
+
+            for (v_x, x_steps) in &xs {
+                if overlaps(x_steps, &steps) {
+                    velocities.push((*v_x as i32, v_y as i32));
+                }
+            }
+        }
+
+        Ok(velocities)
+    }
+
+    /// The highest point the probe can reach while still hitting the target.
+    pub fn max_height(&self) -> Result<Option<i32>, InfiniteSolutions> {
+        Ok(self
+            .valid_velocities()?
+            .into_iter()
+            .map(|(_, v_y)| position(v_y as i64, v_y.max(0) as i64) as i32)
+            .max())
+    }
+}
+
+#[derive(Debug)]
+struct Simulator {
+    x: i32,
+    y: i32,
+    v_x: i32,
+    v_y: i32,
+}
+
+impl Simulator {
+    fn new(v_x: i32, v_y: i32) -> Self {
+        Self {
+            x: 0,
+            y: 0,
+            v_x,
+            v_y,
+        }
+    }
+
+    fn step(&mut self) {
+        self.x += self.v_x;
+        self.y += self.v_y;
+        self.v_x -= self.v_x.signum();
+        self.v_y -= 1;
+    }
+
+    fn is_in_area(&self, target: &TargetArea) -> bool {
+        target.x.contains(&self.x) && target.y.contains(&self.y)
+    }
+
+    fn is_past_area(&self, target: &TargetArea) -> bool {
+        (self.v_x >= 0 && self.x > *target.x.end())
+            || (self.v_x <= 0 && self.x < *target.x.start())
+            || (self.v_y < 0 && self.y < *target.y.start())
+    }
+}
+
+/// Steps the probe until it hits or can't reach the target anymore, for cross-checking.
+pub fn simulate(v_x: i32, v_y: i32, target: &TargetArea) -> (bool, i32) {
+    let mut sim = Simulator::new(v_x, v_y);
+    let mut max_y = 0;
+
+    loop {
+        sim.step();
+        max_y = max_y.max(sim.y);
+
+        if sim.is_in_area(target) {
+            return (true, max_y);
+        } else if sim.is_past_area(target) {
+            return (false, max_y);
+        }
+    }
+}
+
+impl Solution for TargetArea {
+    fn parse(input: &str) -> Result<Self, Error> {
+        input
+            .parse()
+            .map_err(|_| format!("invalid target area {:?}", input.trim()).into())
+    }
+
+    fn part1(&self) -> Result<impl Display, Error> {
+        self.max_height()?
+            .ok_or_else(|| "no velocity hits the target area".into())
+    }
+
+    fn part2(&self) -> Result<impl Display, Error> {
+        Ok(self.valid_velocities()?.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_highest_y() {
+        let target = "target area: x=20..30, y=-10..-5"
+            .parse::<TargetArea>()
+            .unwrap();
+        assert_eq!(
+            target,
+            TargetArea {
+                x: 20..=30,
+                y: -10..=-5,
+            }
+        );
+
+        let velocities = target.valid_velocities().unwrap();
+        assert_eq!(target.max_height(), Ok(Some(45)));
+        assert_eq!(velocities.len(), 112);
+
+        let (hit, max_y) = simulate(6, 9, &target);
+        assert!(hit);
+        assert_eq!(max_y, 45);
+    }
+
+    #[test]
+    fn test_against_simulation() {
+        let targets = [
+            (20..=30, -10..=-5),
+            (-30..=-20, -10..=-5),
+            (20..=30, 5..=10),
+            (-8..=-3, 12..=15),
+            (22..=25, -5..=5),
+            (-4..=4, -6..=-2),
+            (1..=1, -3..=-1),
+        ];
+
+        for (x, y) in targets {
+            let target = TargetArea { x, y };
+
+            let mut expected = Vec::new();
+            for v_y in -50..200 {
+                for v_x in -50..50 {
+                    if simulate(v_x, v_y, &target).0 {
+                        expected.push((v_x, v_y));
+                    }
+                }
+            }
+
+            let mut velocities = target.valid_velocities().unwrap();
+            velocities.sort_unstable_by_key(|&(x, y)| (y, x));
+            expected.sort_unstable_by_key(|&(x, y)| (y, x));
+            assert_eq!(velocities, expected, "{target:?}");
+        }
+    }
+
+    #[test]
+    fn test_steps_at_least() {
+        for v in -20..20 {
+            for bound in -200..200 {
+                let steps = (1..100).filter(|&t| position(v, t) >= bound);
+                let expected = steps.clone().min().zip(steps.max());
+                assert_eq!(steps_at_least(v, bound), expected, "v={v}, bound={bound}");
+            }
+        }
+
+        let v = i32::MAX as i64;
+        assert_eq!(steps_at_least(v, position(v, v)), Some((v, v + 1)));
+    }
+
+    #[test]
+    fn test_infinite_solutions() {
+        // x = 21 is where the probe stops for v_x = 6, and it can fall onto y = 0 from anywhere
+        let target = TargetArea {
+            x: 20..=25,
+            y: -5..=5,
+        };
+
+        assert_eq!(target.valid_velocities(), Err(InfiniteSolutions));
+    }
+}
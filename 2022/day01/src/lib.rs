@@ -0,0 +1,230 @@
+use std::{cmp::Reverse, collections::BinaryHeap, fmt::Display, io::BufRead};
+
+use aoc::Solution;
+
+#[derive(Debug)]
+pub enum Error {
+    Io(std::io::Error),
+    InvalidLine { line: usize, content: String },
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Io(err) => write!(f, "failed to read input: {err}"),
+            Error::InvalidLine { line, content } => {
+                write!(f, "line {line}: invalid calorie count {content:?}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<std::io::Error> for Error {
+    fn from(err: std::io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ElfStatistics {
+    pub count: usize,
+    pub mean: f64,
+    pub median: f64,
+}
+
+/// Sums up elf inventories while reading and only keeps the `k` biggest ones in a min-heap.
+///
+/// Memory stays O(k) unless [`CalorieAggregator::with_statistics`] is asked for.
+#[derive(Clone, Debug)]
+pub struct CalorieAggregator {
+    k: usize,
+    top: BinaryHeap<Reverse<u64>>,
+    totals: Option<Vec<u64>>,
+}
+
+impl CalorieAggregator {
+    pub fn new(k: usize) -> Self {
+        Self {
+            k,
+            top: BinaryHeap::with_capacity(k + 1),
+            totals: None,
+        }
+    }
+
+    /// Also keeps the total of every elf so [`CalorieAggregator::statistics`] can work out the
+    /// median, which takes O(elves) memory (one `u64` per elf, not per line).
+    pub fn with_statistics(mut self) -> Self {
+        self.totals = Some(Vec::new());
+        self
+    }
+
+    fn push_elf(&mut self, total: u64) {
+        if let Some(totals) = &mut self.totals {
+            totals.push(total);
+        }
+
+        if self.top.len() < self.k {
+            self.top.push(Reverse(total));
+        } else if let Some(mut smallest) = self.top.peek_mut() {
+            if smallest.0 < total {
+                *smallest = Reverse(total);
+            }
+        }
+    }
+
+    pub fn read<R: BufRead>(mut self, reader: R) -> Result<Self, Error> {
+        let mut current = None;
+
+        for (i, line) in reader.lines().enumerate() {
+            let line = line?;
+            let line = line.trim();
+
+            if line.is_empty() {
+                if let Some(total) = current.take() {
+                    self.push_elf(total);
+                }
+            } else {
+                let calories = line.parse::<u64>().map_err(|_| Error::InvalidLine {
+                    line: i + 1,
+                    content: line.to_owned(),
+                })?;
+                *current.get_or_insert(0) += calories;
+            }
+        }
+
+        if let Some(total) = current {
+            self.push_elf(total);
+        }
+
+        Ok(self)
+    }
+
+    /// The `k` biggest inventories, biggest first.
+    pub fn top(&self) -> Vec<u64> {
+        let mut top = self.top.iter().map(|r| r.0).collect::<Vec<_>>();
+        top.sort_unstable_by(|a, b| b.cmp(a));
+        top
+    }
+
+    pub fn top_sum(&self) -> u64 {
+        self.top.iter().map(|r| r.0).sum()
+    }
+
+    /// `None` if there are no elves or statistics weren't asked for with
+    /// [`CalorieAggregator::with_statistics`].
+    pub fn statistics(&self) -> Option<ElfStatistics> {
+        let totals = self.totals.as_ref()?;
+        let count = totals.len();
+        if count == 0 {
+            return None;
+        }
+
+        let mut totals = totals.clone();
+        let (lower, &mut upper, _) = totals.select_nth_unstable(count / 2);
+        let median = if count.is_multiple_of(2) {
+            let lower = *lower.iter().max().unwrap();
+            (lower as f64 + upper as f64) / 2.0
+        } else {
+            upper as f64
+        };
+
+        Some(ElfStatistics {
+            count,
+            mean: totals.iter().map(|&t| t as f64).sum::<f64>() / count as f64,
+            median,
+        })
+    }
+}
+
+pub fn max_calories<R: BufRead>(reader: R) -> Result<u64, Error> {
+    Ok(CalorieAggregator::new(1).read(reader)?.top_sum())
+}
+
+pub fn top3_calories<R: BufRead>(reader: R) -> Result<u64, Error> {
+    Ok(CalorieAggregator::new(3).read(reader)?.top_sum())
+}
+
+impl Solution for CalorieAggregator {
+    fn parse(input: &str) -> Result<Self, aoc::Error> {
+        Ok(CalorieAggregator::new(3).read(input.as_bytes())?)
+    }
+
+    fn part1(&self) -> Result<impl Display, aoc::Error> {
+        self.top().first().copied().ok_or_else(|| "no elves".into())
+    }
+
+    fn part2(&self) -> Result<impl Display, aoc::Error> {
+        Ok(self.top_sum())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    const EXAMPLE_INPUT: &str = "1000
+2000
+3000
+
+4000
+
+5000
+6000
+
+7000
+8000
+9000
+
+10000";
+
+    #[test]
+    fn test_example_input_part1() {
+        assert_eq!(max_calories(EXAMPLE_INPUT.as_bytes()).unwrap(), 24000)
+    }
+
+    #[test]
+    fn test_example_input_part2() {
+        assert_eq!(top3_calories(EXAMPLE_INPUT.as_bytes()).unwrap(), 45000)
+    }
+
+    #[test]
+    fn test_top_k_and_statistics() {
+        let aggregator = CalorieAggregator::new(4)
+            .read(EXAMPLE_INPUT.as_bytes())
+            .unwrap();
+        assert_eq!(aggregator.statistics(), None);
+
+        let aggregator = CalorieAggregator::new(4)
+            .with_statistics()
+            .read(EXAMPLE_INPUT.as_bytes())
+            .unwrap();
+
+        assert_eq!(aggregator.top(), vec![24000, 11000, 10000, 6000]);
+        assert_eq!(
+            aggregator.statistics(),
+            Some(ElfStatistics {
+                count: 5,
+                mean: 11000.0,
+                median: 10000.0,
+            })
+        );
+    }
+
+    #[test]
+    fn test_large_totals() {
+        let input = format!("{}\n{}\n", u32::MAX, u32::MAX);
+        assert_eq!(max_calories(input.as_bytes()).unwrap(), 2 * u32::MAX as u64);
+    }
+
+    #[test]
+    fn test_invalid_line() {
+        match max_calories("1000\n\n20x0\n".as_bytes()) {
+            Err(Error::InvalidLine { line, content }) => {
+                assert_eq!(line, 3);
+                assert_eq!(content, "20x0");
+            }
+            other => panic!("unexpected result {other:?}"),
+        }
+    }
+}
@@ -1,60 +1,33 @@
-fn main() {
-    let input = include_str!("../input.txt");
-    let calories = parse_inventories(input);
+use aoc2022_day01::CalorieAggregator;
 
-    let max = max_calories(&calories);
-    println!("Biggest inventory: {max}");
+fn main() {
+    let input = aoc::input(env!("CARGO_MANIFEST_DIR"));
+    let aggregator = match CalorieAggregator::new(3)
+        .with_statistics()
+        .read(input.as_bytes())
+    {
+        Ok(aggregator) => aggregator,
+        Err(e) => {
+            eprintln!("input.txt {}", e);
+            return;
+        }
+    };
+
+    match aggregator.top().first() {
+        Some(max) => println!("Biggest inventory: {max}"),
+        None => {
+            eprintln!("input.txt has no elves");
+            return;
+        }
+    }
 
-    let top3 = top3_calories(&calories);
+    let top3 = aggregator.top_sum();
     println!("Top 3 inventories: {top3}");
-}
-
-fn parse_inventories(input: &str) -> Vec<u32> {
-    let mut calories = input
-        .split("\n\n")
-        .map(|inv| inv.lines().map(|line| line.parse::<u32>().unwrap()).sum())
-        .collect::<Vec<_>>();
-
-    calories.sort();
-
-    calories
-}
-
-fn max_calories(calories: &[u32]) -> u32 {
-    *calories.last().unwrap()
-}
-
-fn top3_calories(calories: &[u32]) -> u32 {
-    calories.iter().rev().take(3).sum()
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-    const EXAMPLE_INPUT: &str = "1000
-2000
-3000
-
-4000
-
-5000
-6000
-
-7000
-8000
-9000
-
-10000";
-
-    #[test]
-    fn test_example_input_part1() {
-        let calories = parse_inventories(EXAMPLE_INPUT);
-        assert_eq!(max_calories(&calories), 24000)
-    }
 
-    #[test]
-    fn test_example_input_part2() {
-        let calories = parse_inventories(EXAMPLE_INPUT);
-        assert_eq!(top3_calories(&calories), 45000)
+    if let Some(stats) = aggregator.statistics() {
+        println!(
+            "{} elves, mean {:.1}, median {:.1}",
+            stats.count, stats.mean, stats.median
+        );
     }
 }
@@ -0,0 +1,421 @@
+use std::{collections::HashMap, fmt::Display, str::FromStr};
+
+use aoc::{Error, ParseError, Solution};
+
+/// A hand is just its position in the cycle of a [`Game`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Hand(pub usize);
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Outcome {
+    Lose,
+    Draw,
+    Win,
+}
+
+impl FromStr for Outcome {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            "X" => Outcome::Lose,
+            "Y" => Outcome::Draw,
+            "Z" => Outcome::Win,
+            _ => Err(())?,
+        })
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Scoring {
+    pub hand_scores: Vec<u32>,
+    pub lose: u32,
+    pub draw: u32,
+    pub win: u32,
+}
+
+impl Scoring {
+    fn outcome_score(&self, outcome: Outcome) -> u32 {
+        match outcome {
+            Outcome::Lose => self.lose,
+            Outcome::Draw => self.draw,
+            Outcome::Win => self.win,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum GameError {
+    EvenHandCount(usize),
+    HandScoreCount { hands: usize, scores: usize },
+}
+
+impl Display for GameError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GameError::EvenHandCount(hands) => {
+                write!(
+                    f,
+                    "a cyclic game needs an odd number of hands, found {hands}"
+                )
+            }
+            GameError::HandScoreCount { hands, scores } => {
+                write!(
+                    f,
+                    "expected a score for each of the {hands} hands, found {scores}"
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for GameError {}
+
+/// A cyclic game with an odd number of hands: every hand beats the `n / 2` hands before it
+/// in the cycle and loses against the `n / 2` hands after it.
+#[derive(Clone, Debug)]
+pub struct Game {
+    names: Vec<&'static str>,
+    scoring: Scoring,
+}
+
+impl Game {
+    pub fn new(names: Vec<&'static str>, scoring: Scoring) -> Result<Self, GameError> {
+        if names.len().is_multiple_of(2) {
+            return Err(GameError::EvenHandCount(names.len()));
+        }
+        if names.len() != scoring.hand_scores.len() {
+            return Err(GameError::HandScoreCount {
+                hands: names.len(),
+                scores: scoring.hand_scores.len(),
+            });
+        }
+
+        Ok(Self { names, scoring })
+    }
+
+    // The built-in games are known to be valid, so they skip `new`'s checks.
+    pub fn rock_paper_scissors() -> Self {
+        Self {
+            names: vec!["Rock", "Paper", "Scissors"],
+            scoring: Scoring {
+                hand_scores: vec![1, 2, 3],
+                lose: 0,
+                draw: 3,
+                win: 6,
+            },
+        }
+    }
+
+    pub fn rock_paper_scissors_lizard_spock() -> Self {
+        Self {
+            names: vec!["Rock", "Spock", "Paper", "Lizard", "Scissors"],
+            scoring: Scoring {
+                hand_scores: vec![1, 2, 3, 4, 5],
+                lose: 0,
+                draw: 3,
+                win: 6,
+            },
+        }
+    }
+
+    pub fn hands(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn name(&self, hand: Hand) -> &'static str {
+        self.names[hand.0]
+    }
+
+    pub fn play(&self, ours: Hand, theirs: Hand) -> Outcome {
+        let n = self.hands();
+        let distance = (ours.0 + n - theirs.0) % n;
+
+        if distance == 0 {
+            Outcome::Draw
+        } else if distance <= n / 2 {
+            Outcome::Win
+        } else {
+            Outcome::Lose
+        }
+    }
+
+    pub fn play_score(&self, ours: Hand, theirs: Hand) -> u32 {
+        self.scoring.outcome_score(self.play(ours, theirs)) + self.scoring.hand_scores[ours.0]
+    }
+
+    /// Picks the hand next to `against` in the cycle that gives the requested outcome.
+    pub fn for_outcome(&self, outcome: Outcome, against: Hand) -> Hand {
+        let n = self.hands();
+
+        match outcome {
+            Outcome::Lose => Hand((against.0 + n - 1) % n),
+            Outcome::Draw => against,
+            Outcome::Win => Hand((against.0 + 1) % n),
+        }
+    }
+
+    /// The opponent's column uses consecutive letters starting at `A`.
+    pub fn parse_opponent(&self, s: &str) -> Option<Hand> {
+        match s.as_bytes() {
+            [c @ b'A'..=b'Z'] if ((c - b'A') as usize) < self.hands() => {
+                Some(Hand((c - b'A') as usize))
+            }
+            _ => None,
+        }
+    }
+}
+
+/// The rounds of the encrypted guide: the opponent's hand and the letter of the second column,
+/// which is only given a meaning when scoring.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StrategyGuide {
+    pub rounds: Vec<(Hand, char)>,
+}
+
+impl StrategyGuide {
+    /// Reads the opponent's column as hands of `game`.
+    pub fn parse(game: &Game, input: &str) -> Result<Self, ParseError> {
+        let rounds = input
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(i, line)| {
+                let error = |message| ParseError::new(i + 1, message);
+
+                let (theirs, ours) = line
+                    .trim()
+                    .split_once(' ')
+                    .ok_or_else(|| error(format!("expected two columns, found {line:?}")))?;
+                let theirs = game
+                    .parse_opponent(theirs)
+                    .ok_or_else(|| error(format!("unknown opponent hand {theirs:?}")))?;
+                let mut letters = ours.chars();
+                match (letters.next(), letters.next()) {
+                    (Some(ours), None) => Ok((theirs, ours)),
+                    _ => Err(error(format!("expected a single letter, found {ours:?}"))),
+                }
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self { rounds })
+    }
+}
+
+/// Scores a strategy guide where the second column is looked up in `mapping`. Rounds with a
+/// letter that isn't mapped don't count.
+pub fn score_with_mapping(game: &Game, guide: &StrategyGuide, mapping: &[(char, Hand)]) -> u32 {
+    guide
+        .rounds
+        .iter()
+        .filter_map(|&(theirs, ours)| {
+            let (_, ours) = mapping.iter().find(|(c, _)| *c == ours)?;
+            Some(game.play_score(*ours, theirs))
+        })
+        .sum()
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MappingScore {
+    pub mapping: Vec<(char, Hand)>,
+    pub score: u32,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MappingAnalysis {
+    pub best: MappingScore,
+    pub worst: MappingScore,
+}
+
+fn assignments(letters: usize, hands: usize) -> Vec<Vec<Hand>> {
+    fn recurse(letters: usize, hands: usize, current: &mut Vec<Hand>, out: &mut Vec<Vec<Hand>>) {
+        if current.len() == letters {
+            out.push(current.clone());
+            return;
+        }
+
+        for hand in (0..hands).map(Hand) {
+            if !current.contains(&hand) {
+                current.push(hand);
+                recurse(letters, hands, current, out);
+                current.pop();
+            }
+        }
+    }
+
+    let mut out = Vec::new();
+    recurse(letters, hands, &mut Vec::new(), &mut out);
+    out
+}
+
+/// Tries every way of assigning the guide's response letters to distinct hands and reports the
+/// best and worst one. Returns `None` if the guide is empty or uses more letters than hands.
+pub fn analyze_mappings(game: &Game, guide: &StrategyGuide) -> Option<MappingAnalysis> {
+    // every mapping is scored from the counts of (opponent, letter) rounds, not the whole guide
+    let mut rounds = HashMap::<(Hand, char), u32>::new();
+    for &round in guide.rounds.iter() {
+        *rounds.entry(round).or_default() += 1;
+    }
+
+    let mut letters = rounds.keys().map(|(_, c)| *c).collect::<Vec<_>>();
+    letters.sort_unstable();
+    letters.dedup();
+
+    if letters.is_empty() || letters.len() > game.hands() {
+        return None;
+    }
+
+    let scores = assignments(letters.len(), game.hands())
+        .into_iter()
+        .map(|hands| {
+            let mapping = std::iter::zip(letters.iter().copied(), hands).collect::<Vec<_>>();
+            let score = rounds
+                .iter()
+                .map(|((theirs, letter), count)| {
+                    let (_, ours) = mapping.iter().find(|(c, _)| c == letter).unwrap();
+                    count * game.play_score(*ours, *theirs)
+                })
+                .sum();
+
+            MappingScore { mapping, score }
+        })
+        .collect::<Vec<_>>();
+
+    Some(MappingAnalysis {
+        best: scores.iter().max_by_key(|s| s.score)?.clone(),
+        worst: scores.iter().min_by_key(|s| s.score)?.clone(),
+    })
+}
+
+pub fn calculate_score_part1(guide: &StrategyGuide) -> u32 {
+    score_with_mapping(
+        &Game::rock_paper_scissors(),
+        guide,
+        &[('X', Hand(0)), ('Y', Hand(1)), ('Z', Hand(2))],
+    )
+}
+
+/// Returns `None` if a round's letter isn't one of the outcomes `X`, `Y` and `Z`.
+pub fn calculate_score_part2(guide: &StrategyGuide) -> Option<u32> {
+    let game = Game::rock_paper_scissors();
+
+    guide
+        .rounds
+        .iter()
+        .map(|&(theirs, outcome)| {
+            let outcome = outcome.to_string().parse::<Outcome>().ok()?;
+            Some(game.play_score(game.for_outcome(outcome, theirs), theirs))
+        })
+        .sum()
+}
+
+/// The guide is read for rock, paper, scissors.
+impl Solution for StrategyGuide {
+    fn parse(input: &str) -> Result<Self, Error> {
+        Ok(StrategyGuide::parse(&Game::rock_paper_scissors(), input)?)
+    }
+
+    fn part1(&self) -> Result<impl Display, Error> {
+        Ok(calculate_score_part1(self))
+    }
+
+    fn part2(&self) -> Result<impl Display, Error> {
+        calculate_score_part2(self).ok_or_else(|| "the second column isn't X, Y or Z".into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE_INPUT: &str = "A Y
+B X
+C Z";
+
+    fn guide(input: &str) -> StrategyGuide {
+        StrategyGuide::parse(&Game::rock_paper_scissors(), input).unwrap()
+    }
+
+    #[test]
+    fn test_example_input() {
+        assert_eq!(calculate_score_part1(&guide(EXAMPLE_INPUT)), 15);
+        assert_eq!(calculate_score_part2(&guide(EXAMPLE_INPUT)), Some(12));
+    }
+
+    #[test]
+    fn test_invalid_guide() {
+        let error = |input| StrategyGuide::parse(&Game::rock_paper_scissors(), input).unwrap_err();
+
+        assert_eq!(error("A Y\nD X").line, 2);
+        assert_eq!(error("A").line, 1);
+        assert_eq!(error("A XY").line, 1);
+        assert_eq!(calculate_score_part2(&guide("A W")), None);
+    }
+
+    #[test]
+    fn test_invalid_game() {
+        let scoring = |hand_scores| Scoring {
+            hand_scores,
+            lose: 0,
+            draw: 3,
+            win: 6,
+        };
+
+        assert_eq!(
+            Game::new(vec!["Rock", "Paper"], scoring(vec![1, 2])).err(),
+            Some(GameError::EvenHandCount(2))
+        );
+        assert_eq!(
+            Game::new(vec!["Rock", "Paper", "Scissors"], scoring(vec![1, 2])).err(),
+            Some(GameError::HandScoreCount {
+                hands: 3,
+                scores: 2
+            })
+        );
+        assert!(Game::new(vec!["Rock", "Paper", "Scissors"], scoring(vec![1, 2, 3])).is_ok());
+    }
+
+    #[test]
+    fn test_lizard_spock() {
+        let game = Game::rock_paper_scissors_lizard_spock();
+        let hand = |name| Hand(game.names.iter().position(|n| *n == name).unwrap());
+
+        for (winner, loser) in [
+            ("Scissors", "Paper"),
+            ("Paper", "Rock"),
+            ("Rock", "Lizard"),
+            ("Lizard", "Spock"),
+            ("Spock", "Scissors"),
+            ("Scissors", "Lizard"),
+            ("Lizard", "Paper"),
+            ("Paper", "Spock"),
+            ("Spock", "Rock"),
+            ("Rock", "Scissors"),
+        ] {
+            assert_eq!(game.play(hand(winner), hand(loser)), Outcome::Win);
+            assert_eq!(game.play(hand(loser), hand(winner)), Outcome::Lose);
+        }
+    }
+
+    #[test]
+    fn test_analyze_mappings() {
+        let game = Game::rock_paper_scissors();
+        let guide = guide(EXAMPLE_INPUT);
+        let analysis = analyze_mappings(&game, &guide).unwrap();
+
+        let standard = [('X', Hand(0)), ('Y', Hand(1)), ('Z', Hand(2))];
+        assert!(analysis.best.score >= score_with_mapping(&game, &guide, &standard));
+        assert_eq!(
+            analysis.best.score,
+            score_with_mapping(&game, &guide, &analysis.best.mapping)
+        );
+        assert_eq!(
+            analysis.worst.score,
+            score_with_mapping(&game, &guide, &analysis.worst.mapping)
+        );
+        assert!(analysis.worst.score <= analysis.best.score);
+
+        // Y=Paper beats Rock, X=Scissors beats Paper, Z=Rock beats Scissors
+        assert_eq!(analysis.best.score, 3 * 6 + 2 + 3 + 1);
+    }
+}
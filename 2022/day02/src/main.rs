@@ -1,142 +1,34 @@
-use std::str::FromStr;
+use aoc2022_day02::{
+    analyze_mappings, calculate_score_part1, calculate_score_part2, Game, StrategyGuide,
+};
 
 fn main() {
-    let input = include_str!("../input.txt");
-    let score = calculate_score_part1(input);
-    let score2 = calculate_score_part2(input);
-
-    println!("Our score part1: {score}");
-    println!("Our score part2: {score2}");
-}
-
-#[derive(Clone, PartialEq, Eq)]
-enum Hand {
-    Rock,
-    Paper,
-    Scissors,
-}
-
-impl Hand {
-    fn hand_score(&self) -> u32 {
-        match self {
-            Hand::Rock => 1,
-            Hand::Paper => 2,
-            Hand::Scissors => 3,
-        }
-    }
-
-    fn play(&self, other: &Hand) -> Outcome {
-        if self == other {
-            Outcome::Draw
-        } else {
-            match (self, other) {
-                (Hand::Rock, Hand::Scissors)
-                | (Hand::Paper, Hand::Rock)
-                | (Hand::Scissors, Hand::Paper) => Outcome::Win,
-                _ => Outcome::Lose,
-            }
+    let input = aoc::input(env!("CARGO_MANIFEST_DIR"));
+    let game = Game::rock_paper_scissors();
+    let guide = match StrategyGuide::parse(&game, input) {
+        Ok(guide) => guide,
+        Err(e) => {
+            eprintln!("input.txt {}", e);
+            return;
         }
-    }
-
-    fn play_score(&self, other: &Hand) -> u32 {
-        self.play(other).score() + self.hand_score()
-    }
+    };
 
-    fn for_outcome(outcome: Outcome, against: &Hand) -> Self {
-        match outcome {
-            Outcome::Lose => match against {
-                Hand::Rock => Hand::Scissors,
-                Hand::Paper => Hand::Rock,
-                Hand::Scissors => Hand::Paper,
-            },
-            Outcome::Draw => against.clone(),
-            Outcome::Win => match against {
-                Hand::Rock => Hand::Paper,
-                Hand::Paper => Hand::Scissors,
-                Hand::Scissors => Hand::Rock,
-            },
-        }
-    }
-}
-
-impl FromStr for Hand {
-    type Err = ();
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        Ok(match s {
-            "A" | "X" => Hand::Rock,
-            "B" | "Y" => Hand::Paper,
-            "C" | "Z" => Hand::Scissors,
-            _ => Err(())?,
-        })
-    }
-}
-
-#[derive(Debug, PartialEq, Eq)]
-enum Outcome {
-    Lose,
-    Draw,
-    Win,
-}
-
-impl Outcome {
-    fn score(&self) -> u32 {
-        match self {
-            Outcome::Lose => 0,
-            Outcome::Draw => 3,
-            Outcome::Win => 6,
+    let score = calculate_score_part1(&guide);
+    println!("Our score part1: {score}");
+    match calculate_score_part2(&guide) {
+        Some(score2) => println!("Our score part2: {score2}"),
+        None => println!("The second column isn't X, Y or Z"),
+    }
+
+    if let Some(analysis) = analyze_mappings(&game, &guide) {
+        for (label, result) in [("Best", analysis.best), ("Worst", analysis.worst)] {
+            let mapping = result
+                .mapping
+                .iter()
+                .map(|(c, hand)| format!("{c}={}", game.name(*hand)))
+                .collect::<Vec<_>>()
+                .join(", ");
+            println!("{label} mapping: {mapping} (score {})", result.score);
         }
     }
 }
-
-impl FromStr for Outcome {
-    type Err = ();
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        Ok(match s {
-            "X" => Outcome::Lose,
-            "Y" => Outcome::Draw,
-            "Z" => Outcome::Win,
-            _ => Err(())?,
-        })
-    }
-}
-
-fn calculate_score_part1(input: &str) -> u32 {
-    input
-        .lines()
-        .filter_map(|line| line.split_once(' '))
-        .filter_map(|(theirs, ours)| {
-            Some((theirs.parse::<Hand>().ok()?, ours.parse::<Hand>().ok()?))
-        })
-        .map(|(theirs, ours)| ours.play_score(&theirs))
-        .sum::<u32>()
-}
-
-fn calculate_score_part2(input: &str) -> u32 {
-    input
-        .lines()
-        .filter_map(|line| line.split_once(' '))
-        .filter_map(|(theirs, outcome)| {
-            Some((
-                theirs.parse::<Hand>().ok()?,
-                outcome.parse::<Outcome>().ok()?,
-            ))
-        })
-        .map(|(theirs, outcome)| Hand::for_outcome(outcome, &theirs).play_score(&theirs))
-        .sum()
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_example_input() {
-        let input = "A Y
-B X
-C Z";
-        assert_eq!(calculate_score_part1(input), 15);
-        assert_eq!(calculate_score_part2(input), 12);
-    }
-}
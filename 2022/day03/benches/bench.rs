@@ -1,14 +1,22 @@
-use day03::{elve_groups, find_common_priority};
+use aoc2022_day03::{elve_groups, find_common_priority, parse_input};
 
 use criterion::{black_box, criterion_group, criterion_main, Criterion};
 
 fn criterion_benchmark(c: &mut Criterion) {
     let input = include_str!("../input.txt");
 
+    c.bench_function("parse", |b| b.iter(|| parse_input(black_box(input))));
+
     c.bench_function("part1", |b| {
-        b.iter(|| find_common_priority(black_box(input)))
+        let rucksacks = parse_input(input).unwrap();
+
+        b.iter(|| find_common_priority(black_box(&rucksacks.0)))
+    });
+    c.bench_function("part2", |b| {
+        let rucksacks = parse_input(input).unwrap();
+
+        b.iter(|| elve_groups(black_box(&rucksacks.0)))
     });
-    c.bench_function("part2", |b| b.iter(|| elve_groups(black_box(input))));
 }
 
 criterion_group!(benches, criterion_benchmark);
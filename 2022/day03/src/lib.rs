@@ -1,47 +1,94 @@
+use std::fmt::Display;
+
+use aoc::{Error, ParseError, Solution};
+
+/// The items in the two compartments of a rucksack, as sets of their priorities.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Rucksack {
+    pub compartments: [u64; 2],
+}
+
+impl Rucksack {
+    pub fn items(&self) -> u64 {
+        self.compartments[0] | self.compartments[1]
+    }
+}
+
+#[derive(Debug)]
+pub struct Rucksacks(pub Vec<Rucksack>);
+
 fn split_rucksack(rucksack: &str) -> (&str, &str) {
     rucksack.split_at(rucksack.len() / 2)
 }
 
-fn find_common(splits: (&str, &str)) -> usize {
-    find_common_char(&[splits.0, splits.1])
+fn item_set(items: &str) -> u64 {
+    items
+        .bytes()
+        .map(item_priority)
+        .fold(0u64, |set, priority| set | 1 << priority)
 }
 
-fn find_common_char(strings: &[&str]) -> usize {
-    let chars = strings
-        .iter()
-        .map(|s| {
-            s.as_bytes()
-                .iter()
-                .copied()
-                .map(item_priority)
-                .fold(0u64, |chars, c| chars | 1 << c)
-        })
-        .reduce(|acc, chars| acc & chars)
-        .unwrap_or(0);
-
-    chars.trailing_zeros() as usize
+/// The priority of the only item in `set`, if there's one in it at all.
+fn priority(set: u64) -> Option<usize> {
+    (set != 0).then(|| set.trailing_zeros() as usize)
 }
 
 fn item_priority(item: u8) -> u8 {
     (item & 31) + 26 * ((item & 32) == 0) as u8
 }
 
-pub fn find_common_priority(input: &str) -> usize {
+pub fn parse_input(input: &str) -> Result<Rucksacks, ParseError> {
     input
         .lines()
-        .map(|line| find_common(split_rucksack(line)))
+        .enumerate()
+        .filter(|(_, line)| !line.is_empty())
+        .map(|(i, line)| {
+            if let Some(item) = line.chars().find(|c| !c.is_ascii_alphabetic()) {
+                return Err(ParseError::new(i + 1, format!("invalid item {item:?}")));
+            }
+            if line.len() % 2 != 0 {
+                return Err(ParseError::new(i + 1, "the compartments differ in size"));
+            }
+
+            let (first, second) = split_rucksack(line);
+            Ok(Rucksack {
+                compartments: [item_set(first), item_set(second)],
+            })
+        })
+        .collect::<Result<_, _>>()
+        .map(Rucksacks)
+}
+
+/// Returns `None` if a rucksack has no item in both compartments.
+pub fn find_common_priority(rucksacks: &[Rucksack]) -> Option<usize> {
+    rucksacks
+        .iter()
+        .map(|rucksack| priority(rucksack.compartments[0] & rucksack.compartments[1]))
         .sum()
 }
 
-pub fn elve_groups(input: &str) -> usize {
-    input
-        .lines()
-        .collect::<Vec<_>>()
+/// Returns `None` if a group of three has no item in common.
+pub fn elve_groups(rucksacks: &[Rucksack]) -> Option<usize> {
+    rucksacks
         .chunks_exact(3)
-        .map(find_common_char)
+        .map(|group| priority(group.iter().fold(!0, |common, r| common & r.items())))
         .sum()
 }
 
+impl Solution for Rucksacks {
+    fn parse(input: &str) -> Result<Self, Error> {
+        Ok(parse_input(input)?)
+    }
+
+    fn part1(&self) -> Result<impl Display, Error> {
+        find_common_priority(&self.0).ok_or_else(|| "a rucksack has no misplaced item".into())
+    }
+
+    fn part2(&self) -> Result<impl Display, Error> {
+        elve_groups(&self.0).ok_or_else(|| "a group has no badge".into())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -73,11 +120,22 @@ CrZsJsPPZsGzwwsLwLmpwMDw";
 
     #[test]
     fn test_example_input() {
-        assert_eq!(find_common_priority(EXAMPLE_INPUT), 157);
+        let rucksacks = parse_input(EXAMPLE_INPUT).unwrap();
+        assert_eq!(find_common_priority(&rucksacks.0), Some(157));
     }
 
     #[test]
     fn test_elve_group_sums() {
-        assert_eq!(elve_groups(EXAMPLE_INPUT), 70);
+        let rucksacks = parse_input(EXAMPLE_INPUT).unwrap();
+        assert_eq!(elve_groups(&rucksacks.0), Some(70));
+    }
+
+    #[test]
+    fn test_invalid_rucksacks() {
+        assert_eq!(parse_input("abcd\nab1d").unwrap_err().line, 2);
+        assert!(parse_input("abc").is_err());
+
+        let rucksacks = parse_input("abcd").unwrap();
+        assert_eq!(find_common_priority(&rucksacks.0), None);
     }
 }
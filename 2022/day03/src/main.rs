@@ -1,8 +1,5 @@
-use day03::{elve_groups, find_common_priority};
+use aoc2022_day03::Rucksacks;
 
 fn main() {
-    let input = include_str!("../input.txt");
-
-    println!("Total priority: {}", find_common_priority(input));
-    println!("Group priority sum: {}", elve_groups(input));
+    aoc::run::<Rucksacks>(env!("CARGO_MANIFEST_DIR"));
 }
@@ -1,14 +1,22 @@
-use day04::{find_fully_contained, find_overlapping};
+use aoc2022_day04::{find_fully_contained, find_overlapping, parse_input};
 
 use criterion::{black_box, criterion_group, criterion_main, Criterion};
 
 fn criterion_benchmark(c: &mut Criterion) {
     let input = include_str!("../input.txt");
 
+    c.bench_function("parse", |b| b.iter(|| parse_input(black_box(input))));
+
     c.bench_function("part1", |b| {
-        b.iter(|| find_fully_contained(black_box(input)))
+        let pairs = parse_input(input).unwrap();
+
+        b.iter(|| find_fully_contained(black_box(&pairs.0)))
+    });
+    c.bench_function("part2", |b| {
+        let pairs = parse_input(input).unwrap();
+
+        b.iter(|| find_overlapping(black_box(&pairs.0)))
     });
-    c.bench_function("part2", |b| b.iter(|| find_overlapping(black_box(input))));
 }
 
 criterion_group!(benches, criterion_benchmark);
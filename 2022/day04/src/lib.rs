@@ -1,4 +1,6 @@
-use std::ops::RangeInclusive;
+use std::{fmt::Display, ops::RangeInclusive};
+
+use aoc::{Error, ParseError, Solution};
 
 trait ContainsRange {
     fn fully_contains_range(&self, other: &Self) -> bool;
@@ -19,34 +21,62 @@ where
     }
 }
 
-fn parse_input(
-    input: &str,
-) -> impl Iterator<Item = (RangeInclusive<u32>, RangeInclusive<u32>)> + '_ {
+/// The section ranges of each pair of elves.
+pub struct SectionAssignments(pub Vec<(RangeInclusive<u32>, RangeInclusive<u32>)>);
+
+pub fn parse_input(input: &str) -> Result<SectionAssignments, ParseError> {
     input
         .lines()
-        .map(|line| line.split(&['-', ',']))
-        .filter_map(|mut split| {
-            Some((
-                split.next()?.parse::<u32>().ok()?..=split.next()?.parse::<u32>().ok()?,
-                split.next()?.parse::<u32>().ok()?..=split.next()?.parse::<u32>().ok()?,
-            ))
+        .enumerate()
+        .filter(|(_, line)| !line.is_empty())
+        .map(|(i, line)| {
+            let sections = line
+                .split(['-', ','])
+                .map(|s| s.trim().parse::<u32>())
+                .collect::<Result<Vec<_>, _>>();
+
+            match sections.as_deref() {
+                Ok(&[a, b, c, d]) => Ok((a..=b, c..=d)),
+                _ => Err(ParseError::new(
+                    i + 1,
+                    format!("expected two ranges like 2-4,6-8, found {line:?}"),
+                )),
+            }
         })
+        .collect::<Result<_, _>>()
+        .map(SectionAssignments)
 }
 
-pub fn find_fully_contained(input: &str) -> usize {
-    parse_input(input)
+pub fn find_fully_contained(pairs: &[(RangeInclusive<u32>, RangeInclusive<u32>)]) -> usize {
+    pairs
+        .iter()
         .filter(|(first, second)| {
             first.fully_contains_range(second) || second.fully_contains_range(first)
         })
         .count()
 }
 
-pub fn find_overlapping(input: &str) -> usize {
-    parse_input(input)
+pub fn find_overlapping(pairs: &[(RangeInclusive<u32>, RangeInclusive<u32>)]) -> usize {
+    pairs
+        .iter()
         .filter(|(first, second)| first.overlaps_range(second) || second.overlaps_range(first))
         .count()
 }
 
+impl Solution for SectionAssignments {
+    fn parse(input: &str) -> Result<Self, Error> {
+        Ok(parse_input(input)?)
+    }
+
+    fn part1(&self) -> Result<impl Display, Error> {
+        Ok(find_fully_contained(&self.0))
+    }
+
+    fn part2(&self) -> Result<impl Display, Error> {
+        Ok(find_overlapping(&self.0))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -60,11 +90,22 @@ mod tests {
 
     #[test]
     fn test_example_input_part1() {
-        assert_eq!(find_fully_contained(EXAMPLE_INPUT), 2);
+        let pairs = parse_input(EXAMPLE_INPUT).unwrap();
+        assert_eq!(find_fully_contained(&pairs.0), 2);
     }
 
     #[test]
     fn test_example_input_part2() {
-        assert_eq!(find_overlapping(EXAMPLE_INPUT), 4);
+        let pairs = parse_input(EXAMPLE_INPUT).unwrap();
+        assert_eq!(find_overlapping(&pairs.0), 4);
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        let error = |input| parse_input(input).err().unwrap();
+
+        assert_eq!(error("2-4,6-8\n2-3,4").line, 2);
+        assert_eq!(error("2-4,6-x").line, 1);
+        assert_eq!(error("2-4,6-8,1-1").line, 1);
     }
 }
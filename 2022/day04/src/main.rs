@@ -1,14 +1,5 @@
-use day04::{find_fully_contained, find_overlapping};
+use aoc2022_day04::SectionAssignments;
 
 fn main() {
-    let input = include_str!("../input.txt");
-
-    println!(
-        "Fully overlapping section ids: {}",
-        find_fully_contained(input)
-    );
-    println!(
-        "At all overlapping section ids: {}",
-        find_overlapping(input)
-    );
+    aoc::run::<SectionAssignments>(env!("CARGO_MANIFEST_DIR"));
 }
@@ -1,11 +1,18 @@
+use aoc2022_day05::{move_crates, parse_input};
 use criterion::{black_box, criterion_group, criterion_main, Criterion};
-use day05::move_crates;
 
 fn criterion_benchmark(c: &mut Criterion) {
     let input = include_str!("../input.txt");
 
-    c.bench_function("part1", |b| b.iter(|| move_crates(black_box(input), false)));
-    c.bench_function("part2", |b| b.iter(|| move_crates(black_box(input), true)));
+    c.bench_function("parse", |b| b.iter(|| parse_input(black_box(input))));
+
+    let procedure = parse_input(input).unwrap();
+    c.bench_function("part1", |b| {
+        b.iter(|| move_crates(black_box(&procedure), false))
+    });
+    c.bench_function("part2", |b| {
+        b.iter(|| move_crates(black_box(&procedure), true))
+    });
 }
 
 criterion_group!(benches, criterion_benchmark);
@@ -1,77 +1,144 @@
-#[derive(Debug)]
-struct Instruction {
-    count: usize,
-    from: usize,
-    to: usize,
+use std::fmt::Display;
+
+use aoc::{Error, ParseError, Solution};
+
+/// Moves `count` crates between the 1-based stacks `from` and `to`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Instruction {
+    pub count: usize,
+    pub from: usize,
+    pub to: usize,
+}
+
+/// The starting stacks and the rearrangement procedure, replayed by each crane.
+#[derive(Clone, Debug)]
+pub struct Procedure {
+    pub stacks: Vec<Vec<u8>>,
+    pub instructions: Vec<Instruction>,
 }
 
-fn parse_crates(input: &str) -> Vec<Vec<u8>> {
-    let mut stacks = Vec::<Vec<u8>>::with_capacity(9);
+/// `rows` are the lines of crates above the stack labels, from the top.
+fn parse_crates(rows: &[&str], stack_count: usize) -> Result<Vec<Vec<u8>>, ParseError> {
+    let mut stacks = vec![Vec::with_capacity(20); stack_count];
 
-    input.lines().rev().skip(1).for_each(|line| {
-        line.as_bytes()
+    for (i, row) in rows.iter().enumerate().rev() {
+        let items = row
+            .as_bytes()
             .iter()
             .skip(1)
             .step_by(4)
             .enumerate()
-            .filter(|(_, item)| !item.is_ascii_whitespace())
-            .for_each(|(i, item)| {
-                if i >= stacks.len() {
-                    let mut new_vec = Vec::with_capacity(20);
-                    new_vec.push(*item);
-                    stacks.push(new_vec);
-                } else {
-                    stacks[i].push(*item);
-                }
-            })
-    });
-
-    stacks
+            .filter(|(_, item)| !item.is_ascii_whitespace());
+
+        for (stack, &item) in items {
+            stacks
+                .get_mut(stack)
+                .ok_or_else(|| {
+                    ParseError::new(i + 1, format!("crate {:?} isn't on a stack", item as char))
+                })?
+                .push(item);
+        }
+    }
+
+    Ok(stacks)
+}
+
+fn parse_instruction(line: &str, stack_count: usize) -> Option<Instruction> {
+    let (count, fromto) = line.strip_prefix("move ")?.split_once(" from ")?;
+    let (from, to) = fromto.split_once(" to ")?;
+    let instruction = Instruction {
+        count: count.parse::<usize>().ok()?,
+        from: from.parse::<usize>().ok()?,
+        to: to.parse::<usize>().ok()?,
+    };
+
+    let stacks = 1..=stack_count;
+    (stacks.contains(&instruction.from) && stacks.contains(&instruction.to)).then_some(instruction)
 }
 
-fn parse_instructions(input: &str) -> impl Iterator<Item = Instruction> + '_ {
-    input
-        .lines()
-        .filter(|line| !line.is_empty())
-        .filter_map(|line| {
-            let (count, fromto) = line[5..].split_once(" from ")?;
-            let (from, to) = fromto.split_once(" to ")?;
-            Some(Instruction {
-                count: count.parse::<usize>().ok()?,
-                from: from.parse::<usize>().ok()?,
-                to: to.parse::<usize>().ok()?,
-            })
+pub fn parse_input(input: &str) -> Result<Procedure, ParseError> {
+    let lines = input.lines().collect::<Vec<_>>();
+    let blank = lines
+        .iter()
+        .position(|line| line.trim().is_empty())
+        .ok_or_else(|| ParseError::new(lines.len(), "expected a blank line before the moves"))?;
+    let (labels, rows) = lines[..blank]
+        .split_last()
+        .ok_or_else(|| ParseError::new(1, "expected stacks of crates"))?;
+
+    let stack_count = labels.split_whitespace().count();
+    let stacks = parse_crates(rows, stack_count)?;
+
+    let instructions = lines[blank + 1..]
+        .iter()
+        .enumerate()
+        .filter(|(_, line)| !line.is_empty())
+        .map(|(i, line)| {
+            parse_instruction(line, stack_count)
+                .ok_or_else(|| ParseError::new(blank + i + 2, format!("invalid move {line:?}")))
         })
+        .collect::<Result<_, _>>()?;
+
+    Ok(Procedure {
+        stacks,
+        instructions,
+    })
 }
 
-fn do_movement(stacks: &mut Vec<Vec<u8>>, instruction: &Instruction, cratemover_9001: bool) {
-    let from_stack = stacks.get_mut(instruction.from - 1).unwrap();
+/// Returns `None` if the stack to move from doesn't have enough crates.
+fn do_movement(
+    stacks: &mut [Vec<u8>],
+    instruction: &Instruction,
+    cratemover_9001: bool,
+) -> Option<()> {
+    let from_stack = &mut stacks[instruction.from - 1];
 
-    let mut crates = from_stack.split_off(from_stack.len() - instruction.count);
+    let mut crates = from_stack.split_off(from_stack.len().checked_sub(instruction.count)?);
 
-    let to_stack = stacks.get_mut(instruction.to - 1).unwrap();
+    let to_stack = &mut stacks[instruction.to - 1];
 
     if !cratemover_9001 {
         to_stack.extend(crates.iter().rev());
     } else {
         to_stack.append(&mut crates);
     }
+
+    Some(())
 }
 
-pub fn move_crates(input: &str, cratemover_9001: bool) -> Vec<u8> {
-    let (crates, instructions) = input.split_once("\n\n").unwrap();
-    let stacks = parse_crates(crates);
-    let instructions = parse_instructions(instructions);
+/// Returns `None` if a move takes more crates than there are on its stack.
+pub fn move_crates(procedure: &Procedure, cratemover_9001: bool) -> Option<Vec<u8>> {
+    let mut stacks = procedure.stacks.clone();
+    for instruction in procedure.instructions.iter() {
+        do_movement(&mut stacks, instruction, cratemover_9001)?;
+    }
 
-    let new_stacks = instructions.fold(stacks, |mut stacks, op| {
-        do_movement(&mut stacks, &op, cratemover_9001);
+    Some(
         stacks
-    });
+            .iter()
+            .filter_map(|stack| stack.last().copied())
+            .collect::<Vec<u8>>(),
+    )
+}
 
-    new_stacks
-        .iter()
-        .filter_map(|stack| stack.last().map(|b| *b))
-        .collect::<Vec<u8>>()
+fn top_crates(procedure: &Procedure, cratemover_9001: bool) -> Result<String, Error> {
+    let top = move_crates(procedure, cratemover_9001)
+        .ok_or("a move takes more crates than there are on the stack")?;
+    Ok(String::from_utf8(top)?)
+}
+
+impl Solution for Procedure {
+    fn parse(input: &str) -> Result<Self, Error> {
+        Ok(parse_input(input)?)
+    }
+
+    fn part1(&self) -> Result<impl Display, Error> {
+        top_crates(self, false)
+    }
+
+    fn part2(&self) -> Result<impl Display, Error> {
+        top_crates(self, true)
+    }
 }
 
 #[cfg(test)]
@@ -90,15 +157,30 @@ move 1 from 1 to 2";
 
     #[test]
     fn test_example_part1() {
-        let top = move_crates(EXAMPLE_INPUT, false);
+        let procedure = parse_input(EXAMPLE_INPUT).unwrap();
+        let top = move_crates(&procedure, false).unwrap();
 
         assert_eq!(top, b"CMZ");
     }
 
     #[test]
     fn test_example_part2() {
-        let top = move_crates(EXAMPLE_INPUT, true);
+        let procedure = parse_input(EXAMPLE_INPUT).unwrap();
+        let top = move_crates(&procedure, true).unwrap();
 
         assert_eq!(top, b"MCD");
     }
+
+    #[test]
+    fn test_invalid_procedure() {
+        let error = |input| parse_input(input).unwrap_err();
+
+        assert_eq!(error("[A]\n 1\n\nmove 1 from 1 to 2").line, 4);
+        assert_eq!(error("[A]\n 1\n\nmove 1 from 1 to 1\nmov 1").line, 5);
+        assert_eq!(error("[A] [B]\n 1\n\nmove 1 from 1 to 1").line, 1);
+        assert_eq!(error("[A]\n 1\nmove 1 from 1 to 1").line, 3);
+
+        let procedure = parse_input("[A]\n 1  2\n\nmove 2 from 1 to 2").unwrap();
+        assert_eq!(move_crates(&procedure, false), None);
+    }
 }
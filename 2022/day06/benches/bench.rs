@@ -1,8 +1,9 @@
+use aoc2022_day06::{find_start_marker, parse_input};
 use criterion::{black_box, criterion_group, criterion_main, Criterion};
-use day06::find_start_marker;
 
 fn criterion_benchmark(c: &mut Criterion) {
-    let input = include_str!("../input.txt");
+    let datastream = parse_input(include_str!("../input.txt")).unwrap();
+    let input = datastream.0.as_str();
 
     c.bench_function("part1", |b| {
         b.iter(|| find_start_marker(black_box(input), 4))
@@ -1,3 +1,8 @@
+use std::fmt::Display;
+
+use aoc::{Error, ParseError, Solution};
+
+/// `input` has to be lowercase letters only, like a [`Datastream`].
 pub fn find_start_marker(input: &str, length: usize) -> Option<usize> {
     input
         .as_bytes()
@@ -6,13 +11,41 @@ pub fn find_start_marker(input: &str, length: usize) -> Option<usize> {
         .find(|(_i, window)| {
             window
                 .iter()
-                .fold(0u32, |acc, c| (acc | (1 << (c - b'a'))))
+                .fold(0u32, |acc, c| acc | (1 << (c - b'a')))
                 .count_ones()
                 == length as u32
         })
         .map(|(i, _)| i + length)
 }
 
+/// The characters the device received, all lowercase letters.
+pub struct Datastream(pub String);
+
+pub fn parse_input(input: &str) -> Result<Datastream, ParseError> {
+    let input = input.trim();
+    match input.chars().find(|c| !c.is_ascii_lowercase()) {
+        Some(c) => Err(ParseError::new(
+            1,
+            format!("{c:?} isn't a lowercase letter"),
+        )),
+        None => Ok(Datastream(input.to_string())),
+    }
+}
+
+impl Solution for Datastream {
+    fn parse(input: &str) -> Result<Self, Error> {
+        Ok(parse_input(input)?)
+    }
+
+    fn part1(&self) -> Result<impl Display, Error> {
+        find_start_marker(&self.0, 4).ok_or_else(|| "no start of packet marker".into())
+    }
+
+    fn part2(&self) -> Result<impl Display, Error> {
+        find_start_marker(&self.0, 14).ok_or_else(|| "no start of message marker".into())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -28,9 +61,15 @@ mod tests {
         ];
 
         for (input, start_of_packet, start_of_message) in inputs {
-            dbg!(input, start_of_packet, start_of_message);
             assert_eq!(find_start_marker(input, 4), Some(start_of_packet));
             assert_eq!(find_start_marker(input, 14), Some(start_of_message));
         }
     }
+
+    #[test]
+    fn test_invalid_datastream() {
+        assert!(parse_input("mjqjpqm\n").is_ok());
+        assert!(parse_input("mjqj pqm").is_err());
+        assert!(parse_input("mjqjPqm").is_err());
+    }
 }
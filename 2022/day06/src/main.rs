@@ -1,11 +1,5 @@
-use day06::find_start_marker;
+use aoc2022_day06::Datastream;
 
 fn main() {
-    let input = include_str!("../input.txt");
-
-    println!("Start of packet marker: {:?}", find_start_marker(input, 4));
-    println!(
-        "Start of message marker: {:?}",
-        find_start_marker(input, 14)
-    );
+    aoc::run::<Datastream>(env!("CARGO_MANIFEST_DIR"));
 }
@@ -0,0 +1,159 @@
+use std::{collections::HashMap, fmt::Display};
+
+use aoc::{Error, ParseError, Solution};
+
+const TOTAL_SPACE: usize = 70000000;
+const REQUIRED_SPACE: usize = 30000000;
+
+#[derive(Debug, PartialEq, Eq)]
+enum Command<'a> {
+    Cd(&'a str),
+    Ls,
+}
+
+fn parse_command<'a>(cli: &'a str) -> Option<Command<'a>> {
+    let mut args = cli.split_ascii_whitespace().skip(1); // first is $
+
+    match (args.next(), args.next(), args.next()) {
+        (Some("cd"), Some(dir), None) => Some(Command::Cd(dir)),
+        (Some("ls"), None, None) => Some(Command::Ls),
+        _ => None,
+    }
+}
+
+pub fn parse_input(input: &str) -> Result<HashMap<String, usize>, ParseError> {
+    let mut paths: HashMap<String, usize> = HashMap::new();
+    let mut current_path = Vec::<&str>::new();
+
+    for (i, line) in input.lines().enumerate() {
+        if line.starts_with('$') {
+            let cmd = parse_command(line)
+                .ok_or_else(|| ParseError::new(i + 1, format!("unknown command {line:?}")))?;
+            match cmd {
+                Command::Cd(dir) => match dir {
+                    "/" => current_path.clear(),
+                    ".." => {
+                        current_path
+                            .pop()
+                            .ok_or_else(|| ParseError::new(i + 1, "/ has no parent"))?;
+                    }
+                    new => current_path.push(new),
+                },
+                Command::Ls => {} // we can just ignore it
+            };
+        } else {
+            if !line.starts_with("dir ") && !line.trim().is_empty() {
+                let size = line
+                    .split_once(' ')
+                    .and_then(|(size, _filename)| size.parse::<usize>().ok())
+                    .ok_or_else(|| ParseError::new(i + 1, format!("invalid file {line:?}")))?;
+
+                for p in 0..(current_path.len() + 1) {
+                    // add the size to all parent directories as well
+                    let path = current_path[..p].join("/").to_string();
+
+                    paths.entry(path).and_modify(|s| *s += size).or_insert(size);
+                }
+            }
+        }
+    }
+
+    Ok(paths)
+}
+
+pub fn part1(paths: &HashMap<String, usize>) -> usize {
+    paths.values().filter(|s| **s <= 100_000).sum()
+}
+
+/// `None` if there are no files at all.
+pub fn part2(paths: &HashMap<String, usize>) -> Option<usize> {
+    let mut sizes = paths.values().copied().collect::<Vec<usize>>();
+
+    let total = paths.get("").copied().unwrap_or(0);
+
+    sizes.sort();
+
+    sizes
+        .iter()
+        .copied()
+        .find(|s| TOTAL_SPACE.saturating_sub(total - *s) >= REQUIRED_SPACE)
+}
+
+/// The total size of every directory, by its path relative to `/`.
+pub struct DirectorySizes(pub HashMap<String, usize>);
+
+impl Solution for DirectorySizes {
+    fn parse(input: &str) -> Result<Self, Error> {
+        Ok(Self(parse_input(input)?))
+    }
+
+    fn part1(&self) -> Result<impl Display, Error> {
+        Ok(part1(&self.0))
+    }
+
+    fn part2(&self) -> Result<impl Display, Error> {
+        part2(&self.0).ok_or_else(|| "there are no files".into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE_INPUT: &str = "$ cd /
+$ ls
+dir a
+14848514 b.txt
+8504156 c.dat
+dir d
+$ cd a
+$ ls
+dir e
+29116 f
+2557 g
+62596 h.lst
+$ cd e
+$ ls
+584 i
+$ cd ..
+$ cd ..
+$ cd d
+$ ls
+4060174 j
+8033020 d.log
+5626152 d.ext
+7214296 k";
+
+    #[test]
+    fn test_example_input_part1() {
+        let paths = parse_input(EXAMPLE_INPUT).unwrap();
+
+        assert_eq!(part1(&paths), 95437);
+    }
+
+    #[test]
+    fn test_example_input_part2() {
+        let paths = parse_input(EXAMPLE_INPUT).unwrap();
+
+        assert_eq!(part2(&paths), Some(24933642));
+    }
+
+    #[test]
+    fn test_parse_command() {
+        assert_eq!(parse_command("$ ls"), Some(Command::Ls));
+        assert_eq!(parse_command("$ cd .."), Some(Command::Cd("..")));
+        assert_eq!(parse_command("$ rm -rf"), None);
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        let line = |input| parse_input(input).err().map(|e| e.line);
+        assert_eq!(line("$ cd /\n$ cd a\n$ cd ..\n$ cd .."), Some(4));
+        assert_eq!(line("$ ls\n12 a\nb 12"), Some(3));
+        assert_eq!(line("$ ls\n$ pwd"), Some(2));
+
+        let paths = parse_input("$ ls\n69999999 a").unwrap();
+        assert_eq!(part2(&paths), Some(69999999));
+        assert_eq!(part2(&parse_input("$ ls").unwrap()), None);
+    }
+}
@@ -1,5 +1,6 @@
-use std::{collections::HashSet, ops::Index};
+use std::{collections::HashSet, fmt::Display, ops::Index};
 
+use aoc::{Error, ParseError, Solution};
 use take_until::TakeUntilExt;
 
 pub struct Map2D<T> {
@@ -12,7 +13,7 @@ impl<T> Map2D<T> {
     #[inline]
     pub fn get_unchecked(&self, x: usize, y: usize) -> Option<&T> {
         // bounds check introduces a ~6% performance penalty
-        self.map.get(y * self.height + x)
+        self.map.get(y * self.width + x)
     }
 
     pub fn width(&self) -> usize {
@@ -67,17 +68,35 @@ impl<T> Index<(usize, usize)> for Map2D<T> {
 
     #[inline]
     fn index(&self, index: (usize, usize)) -> &Self::Output {
-        &self.get_unchecked(index.0, index.1).unwrap()
+        self.get_unchecked(index.0, index.1).unwrap()
     }
 }
 
 pub type TreeMap = Map2D<u8>;
 
-pub fn parse_input(input: &str) -> TreeMap {
-    input
+pub fn parse_input(input: &str) -> Result<TreeMap, ParseError> {
+    let width = input.lines().next().map_or(0, str::len);
+    if width == 0 {
+        return Err(ParseError::new(1, "the map is empty"));
+    }
+
+    // check everything up front so the collecting below stays fast
+    for (i, line) in input.lines().enumerate() {
+        if line.len() != width {
+            return Err(ParseError::new(
+                i + 1,
+                format!("expected {} trees, found {}", width, line.len()),
+            ));
+        }
+        if let Some(c) = line.chars().find(|c| !c.is_ascii_digit()) {
+            return Err(ParseError::new(i + 1, format!("{c:?} isn't a height")));
+        }
+    }
+
+    Ok(input
         .lines()
-        .map(|line| line.as_bytes().iter().copied().map(|c| c as u8 - b'0'))
-        .collect::<Map2D<_>>()
+        .map(|line| line.as_bytes().iter().copied().map(|c| c - b'0'))
+        .collect::<Map2D<_>>())
 }
 
 pub fn visible_trees(map: &TreeMap) -> usize {
@@ -142,7 +161,7 @@ pub fn best_score(map: &TreeMap) -> usize {
 
     for y in 1..(y_len - 1) {
         for x in 1..(x_len - 1) {
-            best_score = scenic_score(x, y, &map).max(best_score);
+            best_score = scenic_score(x, y, map).max(best_score);
         }
     }
 
@@ -172,6 +191,20 @@ fn scenic_score(x: usize, y: usize, map: &TreeMap) -> usize {
     left_score * right_score * up_score * down_score
 }
 
+impl Solution for TreeMap {
+    fn parse(input: &str) -> Result<Self, Error> {
+        Ok(parse_input(input)?)
+    }
+
+    fn part1(&self) -> Result<impl Display, Error> {
+        Ok(visible_trees(self))
+    }
+
+    fn part2(&self) -> Result<impl Display, Error> {
+        Ok(best_score(self))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -184,21 +217,21 @@ mod tests {
 
     #[test]
     fn test_example_part1() {
-        let map = parse_input(EXAMPLE_INPUT);
+        let map = parse_input(EXAMPLE_INPUT).unwrap();
 
         assert_eq!(visible_trees(&map), 21);
     }
 
     #[test]
     fn test_example_part2() {
-        let map = parse_input(EXAMPLE_INPUT);
+        let map = parse_input(EXAMPLE_INPUT).unwrap();
 
         assert_eq!(best_score(&map), 8);
     }
 
     #[test]
     fn test_scenic_score() {
-        let map = parse_input(EXAMPLE_INPUT);
+        let map = parse_input(EXAMPLE_INPUT).unwrap();
 
         assert_eq!(scenic_score(2, 1, &map), 4);
         assert_eq!(scenic_score(2, 3, &map), 8);
@@ -206,9 +239,20 @@ mod tests {
 
     #[test]
     fn test_parse_input() {
-        let map = parse_input(EXAMPLE_INPUT);
+        let map = parse_input(EXAMPLE_INPUT).unwrap();
 
         assert_eq!(map.width(), 5);
         assert_eq!(map.height(), 5);
+
+        let map = parse_input("123\n456").unwrap();
+        assert_eq!(map[(0, 1)], 4);
+        assert_eq!(visible_trees(&map), 6);
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        assert_eq!(parse_input("").err().map(|e| e.line), Some(1));
+        assert_eq!(parse_input("123\n12").err().map(|e| e.line), Some(2));
+        assert_eq!(parse_input("123\n1a3").err().map(|e| e.line), Some(2));
     }
 }
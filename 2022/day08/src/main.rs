@@ -1,9 +1,5 @@
-use day08::{best_score, parse_input, visible_trees};
+use aoc2022_day08::TreeMap;
 
 fn main() {
-    let input = include_str!("../input.txt");
-    let map = parse_input(input);
-
-    println!("Visible trees from outside: {}", visible_trees(&map));
-    println!("Best score: {}", best_score(&map));
+    aoc::run::<TreeMap>(env!("CARGO_MANIFEST_DIR"));
 }
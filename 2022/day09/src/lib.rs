@@ -1,4 +1,6 @@
-use std::{collections::HashSet, str::FromStr};
+use std::{collections::HashSet, fmt::Display, str::FromStr};
+
+use aoc::{Error, ParseError, Solution};
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum Direction {
@@ -33,14 +35,24 @@ impl FromStr for Direction {
     }
 }
 
-pub fn parse_input(input: &str) -> impl Iterator<Item = Direction> + '_ {
-    input
-        .lines()
-        .filter_map(|line| line.split_once(' '))
-        .filter_map(|(dir, steps)| {
-            Some((dir.parse::<Direction>().ok()?, steps.parse::<usize>().ok()?))
-        })
-        .flat_map(|(dir, steps)| std::iter::repeat(dir).take(steps))
+/// The moves split into single steps.
+pub fn parse_input(input: &str) -> Result<Vec<Direction>, ParseError> {
+    let mut steps = Vec::new();
+    for (i, line) in input.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let (dir, count) = line
+            .split_once(' ')
+            .and_then(|(dir, count)| {
+                Some((dir.parse::<Direction>().ok()?, count.parse::<usize>().ok()?))
+            })
+            .ok_or_else(|| ParseError::new(i + 1, format!("invalid motion {line:?}")))?;
+        steps.extend(std::iter::repeat_n(dir, count));
+    }
+
+    Ok(steps)
 }
 
 fn step_head(head: (isize, isize), dir: Direction) -> (isize, isize) {
@@ -175,6 +187,23 @@ pub fn print_rope<W: std::io::Write>(
     })
 }
 
+/// The head's moves, one step at a time.
+pub struct Motions(pub Vec<Direction>);
+
+impl Solution for Motions {
+    fn parse(input: &str) -> Result<Self, Error> {
+        Ok(Self(parse_input(input)?))
+    }
+
+    fn part1(&self) -> Result<impl Display, Error> {
+        Ok(part1(self.0.iter().copied()))
+    }
+
+    fn part2(&self) -> Result<impl Display, Error> {
+        Ok(part2(self.0.iter().copied()))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -199,22 +228,29 @@ U 20";
 
     #[test]
     fn test_example_input1_part1() {
-        let iter = parse_input(EXAMPLE_INPUT1);
+        let steps = parse_input(EXAMPLE_INPUT1).unwrap();
 
-        assert_eq!(part1(iter), 13);
+        assert_eq!(part1(steps.into_iter()), 13);
     }
 
     #[test]
     fn test_example_input1_part2() {
-        let iter = parse_input(EXAMPLE_INPUT1);
+        let steps = parse_input(EXAMPLE_INPUT1).unwrap();
 
-        assert_eq!(part2(iter), 1);
+        assert_eq!(part2(steps.into_iter()), 1);
     }
 
     #[test]
     fn test_example_input2_part2() {
-        let iter = parse_input(EXAMPLE_INPUT2);
+        let steps = parse_input(EXAMPLE_INPUT2).unwrap();
+
+        assert_eq!(part2(steps.into_iter()), 36);
+    }
 
-        assert_eq!(part2(iter), 36);
+    #[test]
+    fn test_invalid_motions() {
+        assert_eq!(parse_input("R 4\nX 4").err().map(|e| e.line), Some(2));
+        assert_eq!(parse_input("R -4").err().map(|e| e.line), Some(1));
+        assert_eq!(parse_input("R 2\n\nU 1").unwrap().len(), 3);
     }
 }
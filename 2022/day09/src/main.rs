@@ -1,16 +1,23 @@
 use console::Term;
 
-use day09::{parse_input, part1, part2_viz, print_rope};
+use aoc2022_day09::{parse_input, part1, part2_viz, print_rope};
 
 fn main() {
-    let input = include_str!("../input.txt");
-    let visits1 = part1(parse_input(input));
+    let input = aoc::input(env!("CARGO_MANIFEST_DIR"));
+    let steps = match parse_input(input) {
+        Ok(steps) => steps,
+        Err(e) => {
+            eprintln!("input.txt {}", e);
+            return;
+        }
+    };
+    let visits1 = part1(steps.iter().copied());
 
     println!("part1: {visits1}");
 
     let mut term = Term::buffered_stdout();
 
-    let visits2 = part2_viz(parse_input(input), |head, tail, tail_positions| {
+    let visits2 = part2_viz(steps.iter().copied(), |head, tail, tail_positions| {
         term.clear_screen().unwrap();
 
         print_rope(&mut term, head, tail, tail_positions);
@@ -1,6 +1,8 @@
-use std::str::FromStr;
+use std::{fmt::Display, str::FromStr};
 
-#[derive(Debug)]
+use aoc::{Error, ParseError, Solution};
+
+#[derive(Clone, Copy, Debug)]
 pub enum Instruction {
     Addx(i64),
     Noop,
@@ -20,13 +22,21 @@ impl FromStr for Instruction {
     }
 }
 
-pub fn parse_input(input: &str) -> impl Iterator<Item = Instruction> + '_ {
+pub fn parse_input(input: &str) -> Result<Vec<Instruction>, ParseError> {
     input
         .lines()
-        .map(|line| line.parse::<Instruction>().unwrap())
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| {
+            line.trim()
+                .parse::<Instruction>()
+                .map_err(|_| ParseError::new(i + 1, format!("invalid instruction {line:?}")))
+        })
+        .collect()
 }
 
-pub fn part1<I: Iterator<Item = Instruction>>(iter: I) -> i64 {
+/// `None` if the program ends before the last interesting cycle.
+pub fn part1<I: Iterator<Item = Instruction>>(iter: I) -> Option<i64> {
     let states = std::iter::once(1)
         .chain(
             iter.scan(1, |state, instr| {
@@ -62,12 +72,24 @@ pub fn part1<I: Iterator<Item = Instruction>>(iter: I) -> i64 {
     positions
         .iter()
         .map(|p| {
-            let state = states[*p - 1]; // why -2??
-            state * *p as i64
+            let state = states.get(*p - 1)?; // why -2??
+            Some(state * *p as i64)
         })
         .sum()
 }
 
+pub struct Program(pub Vec<Instruction>);
+
+impl Solution for Program {
+    fn parse(input: &str) -> Result<Self, Error> {
+        Ok(Self(parse_input(input)?))
+    }
+
+    fn part1(&self) -> Result<impl Display, Error> {
+        part1(self.0.iter().copied()).ok_or_else(|| "the program is too short".into())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -76,6 +98,16 @@ mod tests {
 
     #[test]
     fn test_example_input() {
-        assert_eq!(part1(parse_input(EXAMPLE_INPUT2)), 13140);
+        let program = parse_input(EXAMPLE_INPUT2).unwrap();
+        assert_eq!(part1(program.into_iter()), Some(13140));
+    }
+
+    #[test]
+    fn test_invalid_program() {
+        assert_eq!(parse_input("noop\naddx").err().map(|e| e.line), Some(2));
+        assert_eq!(parse_input("addx 1\nsubx 1").err().map(|e| e.line), Some(2));
+
+        let program = parse_input("noop\naddx 3").unwrap();
+        assert_eq!(part1(program.into_iter()), None);
     }
 }
@@ -1,7 +1,5 @@
-use day10::{parse_input, part1};
+use aoc2022_day10::Program;
 
 fn main() {
-    let input = include_str!("../input.txt");
-    let part1 = part1(parse_input(input));
-    println!("part1: {part1}");
+    aoc::run::<Program>(env!("CARGO_MANIFEST_DIR"));
 }
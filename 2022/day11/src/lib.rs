@@ -1,6 +1,8 @@
-use std::str::FromStr;
+use std::{fmt::Display, str::FromStr};
 
-#[derive(Debug)]
+use aoc::{Error, ParseError, Solution};
+
+#[derive(Clone, Debug)]
 enum OldOrInt<T> {
     Old,
     Int(T),
@@ -20,7 +22,7 @@ where
     }
 }
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 enum Operator {
     Add,
     Multiply,
@@ -38,18 +40,18 @@ impl FromStr for Operator {
     }
 }
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 struct Operation {
     operator: Operator,
     operant2: OldOrInt<u64>,
 }
 
 impl Operation {
-    fn new(arg: (&str, &str)) -> Self {
-        Self {
-            operator: arg.0.parse().unwrap(),
-            operant2: arg.1.parse().unwrap(),
-        }
+    fn new(arg: (&str, &str)) -> Option<Self> {
+        Some(Self {
+            operator: arg.0.parse().ok()?,
+            operant2: arg.1.parse().ok()?,
+        })
     }
 
     fn evaluate(&self, operant1: u64) -> u64 {
@@ -65,7 +67,7 @@ impl Operation {
     }
 }
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 struct Monkey {
     items: Vec<u64>,
     operation: Operation,
@@ -74,73 +76,88 @@ struct Monkey {
     test_true: usize,
 }
 
-pub fn part1<const DBG: bool>(input: &str) -> usize {
-    monkey_in_the_middle::<true, 20, DBG>(input)
+/// The monkeys with the items they start out with.
+#[derive(Debug)]
+pub struct Monkeys(Vec<Monkey>);
+
+pub fn part1<const DBG: bool>(monkeys: &Monkeys) -> usize {
+    monkey_in_the_middle::<true, 20, DBG>(monkeys)
+}
+
+pub fn part2<const DBG: bool>(monkeys: &Monkeys) -> usize {
+    monkey_in_the_middle::<false, 10000, DBG>(monkeys)
 }
 
-pub fn part2<const DBG: bool>(input: &str) -> usize {
-    monkey_in_the_middle::<false, 10000, DBG>(input)
+/// Takes the next non-empty line, which has to start with `prefix`, and parses the rest.
+/// `last_line` is reported if the input ends early.
+fn parse_field<'a, T>(
+    lines: &mut impl Iterator<Item = (usize, &'a str)>,
+    last_line: usize,
+    prefix: &str,
+    parse: impl FnOnce(&'a str) -> Option<T>,
+) -> Result<T, ParseError> {
+    let (i, line) = lines
+        .next()
+        .ok_or_else(|| ParseError::new(last_line, format!("expected {prefix:?}, found the end")))?;
+
+    line.trim_start()
+        .strip_prefix(prefix)
+        .and_then(parse)
+        .ok_or_else(|| ParseError::new(i + 1, format!("expected {prefix:?}, found {line:?}")))
 }
 
-fn parse_input(input: &str) -> Vec<Monkey> {
-    input
-        .split("\n\n")
-        .filter(|monkey| !monkey.is_empty())
-        .map(|monkey| {
-            let mut lines = monkey.lines().skip(1);
-            let items = lines
-                .next()
-                .unwrap()
-                .trim_start()
-                .strip_prefix("Starting items: ")
-                .unwrap()
-                .split(", ")
-                .map(|n| n.parse().unwrap())
-                .collect::<Vec<_>>();
-            let operation = Operation::new(
-                lines
-                    .next()
-                    .unwrap()
-                    .trim_start()
-                    .strip_prefix("Operation: new = old ")
-                    .unwrap()
-                    .split_once(' ')
-                    .unwrap(),
-            );
-            let test_divisor = lines
-                .next()
-                .unwrap()
-                .trim_start()
-                .strip_prefix("Test: divisible by ")
-                .unwrap()
-                .parse()
-                .unwrap();
-            let test_true = lines
-                .next()
-                .unwrap()
-                .trim_start()
-                .strip_prefix("If true: throw to monkey ")
-                .unwrap()
-                .parse()
-                .unwrap();
-            let test_false = lines
-                .next()
-                .unwrap()
-                .trim_start()
-                .strip_prefix("If false: throw to monkey ")
-                .unwrap()
-                .parse()
-                .unwrap();
-
-            Monkey {
-                items,
-                operation,
-                test_divisor,
-                test_true,
-                test_false,
+pub fn parse_input(input: &str) -> Result<Monkeys, ParseError> {
+    let mut lines = input
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .peekable();
+    let last_line = input.lines().count();
+    let mut monkeys = Vec::new();
+
+    while lines.peek().is_some() {
+        parse_field(&mut lines, last_line, "Monkey ", |id| id.strip_suffix(':'))?;
+        let items = parse_field(&mut lines, last_line, "Starting items: ", |items| {
+            items.split(", ").map(|n| n.parse().ok()).collect()
+        })?;
+        let operation = parse_field(
+            &mut lines,
+            last_line,
+            "Operation: new = old ",
+            |operation| Operation::new(operation.split_once(' ')?),
+        )?;
+        let test_divisor = parse_field(&mut lines, last_line, "Test: divisible by ", |n| {
+            n.parse().ok().filter(|&n| n != 0)
+        })?;
+        let test_true = parse_field(&mut lines, last_line, "If true: throw to monkey ", |n| {
+            n.parse().ok()
+        })?;
+        let test_false = parse_field(&mut lines, last_line, "If false: throw to monkey ", |n| {
+            n.parse().ok()
+        })?;
+
+        monkeys.push(Monkey {
+            items,
+            operation,
+            test_divisor,
+            test_true,
+            test_false,
+        });
+    }
+
+    if monkeys.len() < 2 {
+        return Err(ParseError::new(last_line, "expected at least two monkeys"));
+    }
+    for (i, monkey) in monkeys.iter().enumerate() {
+        for target in [monkey.test_true, monkey.test_false] {
+            if target == i || target >= monkeys.len() {
+                let message = format!("monkey {i} can't throw to monkey {target}");
+                return Err(ParseError::new(last_line, message));
             }
-        })
-        .collect::<Vec<_>>()
+        }
+    }
+
+    Ok(Monkeys(monkeys))
 }
 
 macro_rules! maybe_println {
@@ -149,8 +166,10 @@ macro_rules! maybe_println {
     };
 }
 
-fn monkey_in_the_middle<const MWL: bool, const R: usize, const DBG: bool>(input: &str) -> usize {
-    let mut monkeys = parse_input(input);
+fn monkey_in_the_middle<const MWL: bool, const R: usize, const DBG: bool>(
+    monkeys: &Monkeys,
+) -> usize {
+    let mut monkeys = monkeys.0.clone();
     let mut inspected_items = vec![0; monkeys.len()];
 
     // we don't need any number past the modulo of the product of all divisors
@@ -220,6 +239,20 @@ fn monkey_in_the_middle<const MWL: bool, const R: usize, const DBG: bool>(input:
     inspected_items[inspected_items.len() - 1] * inspected_items[inspected_items.len() - 2]
 }
 
+impl Solution for Monkeys {
+    fn parse(input: &str) -> Result<Self, Error> {
+        Ok(parse_input(input)?)
+    }
+
+    fn part1(&self) -> Result<impl Display, Error> {
+        Ok(part1::<false>(self))
+    }
+
+    fn part2(&self) -> Result<impl Display, Error> {
+        Ok(part2::<false>(self))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -255,11 +288,35 @@ Monkey 3:
 
     #[test]
     fn test_example_input_part1() {
-        assert_eq!(part1::<true>(EXAMPLE_INPUT), 10605);
+        let monkeys = parse_input(EXAMPLE_INPUT).unwrap();
+        assert_eq!(part1::<true>(&monkeys), 10605);
     }
 
     #[test]
     fn test_example_input_part2() {
-        assert_eq!(part2::<true>(EXAMPLE_INPUT), 2713310158);
+        let monkeys = parse_input(EXAMPLE_INPUT).unwrap();
+        assert_eq!(part2::<true>(&monkeys), 2713310158);
+    }
+
+    #[test]
+    fn test_invalid_monkeys() {
+        let error = |input: &str| parse_input(input).unwrap_err();
+
+        assert_eq!(
+            error(&EXAMPLE_INPUT.replace("old * old", "old ^ 2")).line,
+            17
+        );
+        assert_eq!(error(&EXAMPLE_INPUT.replace("by 17", "by 0")).line, 25);
+        assert_eq!(
+            error(&EXAMPLE_INPUT.replace("monkey 0\n", "monkey 4\n")).line,
+            27
+        );
+        assert_eq!(
+            error(&EXAMPLE_INPUT.replace("monkey 3\n", "monkey 2\n")).line,
+            27
+        );
+        assert!(error(&EXAMPLE_INPUT[..EXAMPLE_INPUT.len() - 30])
+            .message
+            .contains("If false"));
     }
 }
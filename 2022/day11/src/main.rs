@@ -1,8 +1,5 @@
-use day11::{part1, part2};
+use aoc2022_day11::Monkeys;
 
 fn main() {
-    let input = include_str!("../input.txt");
-
-    println!("part1: {}", part1::<true>(input));
-    println!("part2: {}", part2::<false>(input));
+    aoc::run::<Monkeys>(env!("CARGO_MANIFEST_DIR"));
 }
@@ -1,5 +1,5 @@
+use aoc2022_day12::{parse_input, part1, part2};
 use criterion::{black_box, criterion_group, criterion_main, Criterion};
-use day12::{parse_input, part1, part2};
 
 fn criterion_benchmark(c: &mut Criterion) {
     let input = include_str!("../input.txt");
@@ -9,12 +9,12 @@ fn criterion_benchmark(c: &mut Criterion) {
     });
 
     c.bench_function("part1", |b| {
-        let map = parse_input(input);
+        let map = parse_input(input).unwrap();
         b.iter(|| part1(black_box(&map)))
     });
 
     c.bench_function("part2", |b| {
-        let map = parse_input(input);
+        let map = parse_input(input).unwrap();
         b.iter(|| part2(black_box(&map)))
     });
 }
@@ -1,4 +1,6 @@
-use std::{collections::VecDeque, str::FromStr};
+use std::{collections::VecDeque, fmt::Display, str::FromStr};
+
+use aoc::{Error, ParseError, Solution};
 
 pub struct Map {
     map: Vec<Vec<u8>>,
@@ -71,38 +73,47 @@ impl Map {
 }
 
 impl FromStr for Map {
-    type Err = ();
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let mut start: Option<(usize, usize)> = None;
         let mut end: Option<(usize, usize)> = None;
-        let map = s
-            .lines()
-            .enumerate()
-            .map(|(y, line)| {
-                line.as_bytes()
-                    .iter()
-                    .enumerate()
-                    .map(|(x, c)| match c {
-                        b'S' => {
-                            start = Some((x, y));
-                            0
-                        }
-                        b'E' => {
-                            end = Some((x, y));
-                            25
-                        }
-                        c @ b'a'..=b'z' => c - b'a',
-                        _ => unreachable!(),
-                    })
-                    .collect::<Vec<u8>>()
-            })
-            .collect::<Vec<_>>();
+        let mut map: Vec<Vec<u8>> = Vec::new();
+        for (y, line) in s.lines().enumerate() {
+            let row = line
+                .as_bytes()
+                .iter()
+                .enumerate()
+                .map(|(x, c)| match c {
+                    b'S' => {
+                        start = Some((x, y));
+                        Ok(0)
+                    }
+                    b'E' => {
+                        end = Some((x, y));
+                        Ok(25)
+                    }
+                    c @ b'a'..=b'z' => Ok(c - b'a'),
+                    _ => Err(ParseError::new(
+                        y + 1,
+                        format!("{:?} isn't a height", *c as char),
+                    )),
+                })
+                .collect::<Result<Vec<u8>, _>>()?;
+            if row.len() != map.first().map_or(row.len(), Vec::len) || row.is_empty() {
+                return Err(ParseError::new(
+                    y + 1,
+                    "every row has to be as wide as the first",
+                ));
+            }
+            map.push(row);
+        }
 
+        let last_line = s.lines().count();
         Ok(Self {
             map,
-            start: start.ok_or(())?,
-            end: end.ok_or(())?,
+            start: start.ok_or_else(|| ParseError::new(last_line, "there's no start"))?,
+            end: end.ok_or_else(|| ParseError::new(last_line, "there's no end"))?,
         })
     }
 }
@@ -151,8 +162,8 @@ pub fn bfs<SI: Iterator<Item = (usize, usize)>>(
     }
 }
 
-pub fn parse_input(input: &str) -> Map {
-    input.parse().expect("Parsing map failed")
+pub fn parse_input(input: &str) -> Result<Map, ParseError> {
+    input.parse()
 }
 
 pub fn part1(map: &Map) -> usize {
@@ -172,6 +183,20 @@ pub fn part2(map: &Map) -> usize {
     )
 }
 
+impl Solution for Map {
+    fn parse(input: &str) -> Result<Self, Error> {
+        Ok(parse_input(input)?)
+    }
+
+    fn part1(&self) -> Result<impl Display, Error> {
+        Ok(part1(self))
+    }
+
+    fn part2(&self) -> Result<impl Display, Error> {
+        Ok(part2(self))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -184,13 +209,22 @@ abdefghi";
 
     #[test]
     fn test_example_input_part1() {
-        let map = parse_input(EXAMPLE_INPUT);
+        let map = parse_input(EXAMPLE_INPUT).unwrap();
         assert_eq!(part1(&map), 31);
     }
 
     #[test]
     fn test_example_input_part2() {
-        let map = parse_input(EXAMPLE_INPUT);
+        let map = parse_input(EXAMPLE_INPUT).unwrap();
         assert_eq!(part2(&map), 29);
     }
+
+    #[test]
+    fn test_invalid_map() {
+        let line = |input| parse_input(input).err().map(|e| e.line);
+        assert_eq!(line("Sab\nabE\nab"), Some(3));
+        assert_eq!(line("Sab\nab1\nabE"), Some(2));
+        assert_eq!(line("Sab\n\nabE"), Some(2));
+        assert_eq!(line("Sab\nabc"), Some(2));
+    }
 }
@@ -8,6 +8,8 @@ compile_error!("at least one parser has to be enabled");
 
 use std::{cmp::Ordering, fmt::Display, str::FromStr};
 
+use aoc::{Error, ParseError, Solution};
+
 #[derive(Clone, Debug)]
 pub enum Value {
     Integer(u8),
@@ -29,7 +31,7 @@ mod nom_parser {
     use super::Value;
 
     fn parse_number(input: &str) -> IResult<&str, u8> {
-        if input.len() < 1 {
+        if input.is_empty() {
             Err(nom::Err::Error(make_error(
                 input,
                 nom::error::ErrorKind::Eof,
@@ -40,13 +42,12 @@ mod nom_parser {
             if let Some(end) = number_end {
                 let (num_str, rem) = input.split_at(end);
 
-                if num_str.is_empty() {
-                    Err(nom::Err::Error(make_error(
+                match num_str.parse() {
+                    Ok(number) => Ok((rem, number)),
+                    Err(_) => Err(nom::Err::Error(make_error(
                         input,
                         nom::error::ErrorKind::Digit,
-                    )))
-                } else {
-                    Ok((rem, num_str.parse().unwrap()))
+                    ))),
                 }
             } else {
                 Err(nom::Err::Error(make_error(
@@ -74,25 +75,30 @@ mod nom_parser {
         ))(input)
     }
 
-    pub fn parse_signal(input: &str) -> Value {
-        parse_value(input).unwrap().1
+    pub fn parse_signal(input: &str) -> Result<Value, String> {
+        match parse_value(input) {
+            Ok(("", value)) => Ok(value),
+            Ok((rest, _)) => Err(format!("unexpected {rest:?} after the packet")),
+            Err(_) => Err(format!("invalid packet {input:?}")),
+        }
     }
 }
 
 impl FromStr for Value {
-    type Err = ();
+    type Err = String;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         #[cfg(feature = "nom-parser")]
         {
-            Ok(nom_parser::parse_signal(s))
+            nom_parser::parse_signal(s)
         }
 
         #[cfg(feature = "json-parser")]
         {
-            let v = serde_json::from_str::<serde_json::Value>(s).unwrap();
+            let v = serde_json::from_str::<serde_json::Value>(s)
+                .map_err(|_| format!("invalid packet {s:?}"))?;
 
-            Ok(Self::from_json(v))
+            Self::from_json(v)
         }
     }
 }
@@ -119,13 +125,19 @@ impl Display for Value {
 
 impl Value {
     #[cfg(feature = "json-parser")]
-    fn from_json(json: serde_json::Value) -> Self {
+    fn from_json(json: serde_json::Value) -> Result<Self, String> {
         match json {
-            serde_json::Value::Number(num) => Self::Integer(num.as_u64().unwrap() as u8),
-            serde_json::Value::Array(arr) => {
-                Self::List(arr.into_iter().map(Value::from_json).collect())
-            }
-            _ => panic!("invalid json object"),
+            serde_json::Value::Number(num) => num
+                .as_u64()
+                .and_then(|num| u8::try_from(num).ok())
+                .map(Self::Integer)
+                .ok_or_else(|| format!("{num} isn't a small integer")),
+            serde_json::Value::Array(arr) => Ok(Self::List(
+                arr.into_iter()
+                    .map(Value::from_json)
+                    .collect::<Result<_, _>>()?,
+            )),
+            other => Err(format!("{other} isn't an integer or a list")),
         }
     }
 
@@ -185,11 +197,12 @@ impl PartialEq<Self> for Value {
     }
 }
 
-pub fn parse_input(input: &str) -> Vec<Value> {
+pub fn parse_input(input: &str) -> Result<Vec<Value>, ParseError> {
     input
         .lines()
-        .filter(|line| !line.is_empty())
-        .map(|line| line.parse::<Value>().unwrap())
+        .enumerate()
+        .filter(|(_, line)| !line.is_empty())
+        .map(|(i, line)| line.parse::<Value>().map_err(|e| ParseError::new(i + 1, e)))
         .collect()
 }
 
@@ -218,6 +231,23 @@ pub fn part2(signals: &[Value]) -> usize {
     d1 * d2
 }
 
+/// Every packet in the distress signal, in order, pairs included.
+pub struct Signals(pub Vec<Value>);
+
+impl Solution for Signals {
+    fn parse(input: &str) -> Result<Self, Error> {
+        Ok(Self(parse_input(input)?))
+    }
+
+    fn part1(&self) -> Result<impl Display, Error> {
+        Ok(part1(&self.0))
+    }
+
+    fn part2(&self) -> Result<impl Display, Error> {
+        Ok(part2(&self.0))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -248,13 +278,22 @@ mod tests {
 
     #[test]
     fn test_example_input_part1() {
-        let signals = parse_input(EXAMPLE_INPUT);
+        let signals = parse_input(EXAMPLE_INPUT).unwrap();
         assert_eq!(part1(&signals), 13);
     }
 
     #[test]
     fn test_example_input_part2() {
-        let signals = parse_input(EXAMPLE_INPUT);
+        let signals = parse_input(EXAMPLE_INPUT).unwrap();
         assert_eq!(part2(&signals), 140);
     }
+
+    #[test]
+    fn test_invalid_packets() {
+        let line = |input| parse_input(input).err().map(|e| e.line);
+        assert_eq!(line("[1,2]\n[1,2"), Some(2));
+        assert_eq!(line("[1,2]\n[1,2]]"), Some(2));
+        assert_eq!(line("[1]\n\n[256]"), Some(3));
+        assert_eq!(line("[1]\n[{}]"), Some(2));
+    }
 }
@@ -1,9 +1,5 @@
-use day13::{parse_input, part1, part2};
+use aoc2022_day13::Signals;
 
 fn main() {
-    let input = include_str!("../input.txt");
-    let signals = parse_input(input);
-
-    println!("part1: {}", part1(&signals));
-    println!("part2: {}", part2(&signals));
+    aoc::run::<Signals>(env!("CARGO_MANIFEST_DIR"));
 }
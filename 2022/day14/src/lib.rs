@@ -1,4 +1,6 @@
-use std::{collections::HashSet, ops::RangeInclusive};
+use std::{collections::HashSet, fmt::Display, ops::RangeInclusive};
+
+use aoc::{Error, ParseError, Solution};
 
 const SAND_SOURCE: (usize, usize) = (500, 0);
 const FLOOR_OFFSET: usize = 2;
@@ -29,7 +31,7 @@ impl SandSimulator {
             for wd in wall.windows(2) {
                 if wd[0].0 == wd[1].0 {
                     // vertical
-                    for y in wd[0].1.min(wd[1].1)..=(wd[1].1.max(wd[0].1) + 0) {
+                    for y in wd[0].1.min(wd[1].1)..=wd[1].1.max(wd[0].1) {
                         obstacle_map[calc_obstacle_map_index(
                             obstacle_map_size,
                             &wall_bounds,
@@ -38,7 +40,7 @@ impl SandSimulator {
                     }
                 } else if wd[0].1 == wd[1].1 {
                     // horizontal
-                    for x in wd[0].0.min(wd[1].0)..=(wd[1].0.max(wd[0].0) + 0) {
+                    for x in wd[0].0.min(wd[1].0)..=wd[1].0.max(wd[0].0) {
                         obstacle_map[calc_obstacle_map_index(
                             obstacle_map_size,
                             &wall_bounds,
@@ -165,20 +167,40 @@ fn wall_bounds(walls: &[Vec<(usize, usize)>]) -> ((usize, usize), (usize, usize)
     )
 }
 
-pub fn parse_input(input: &str) -> Vec<Vec<(usize, usize)>> {
-    input
-        .lines()
-        .map(|line| {
-            line.split(" -> ")
-                .map(|coord| {
-                    coord
-                        .split_once(',')
-                        .map(|(x, y)| (x.parse::<usize>().unwrap(), y.parse::<usize>().unwrap()))
-                        .unwrap()
-                })
-                .collect()
-        })
-        .collect::<Vec<_>>()
+/// There's at least one wall and every wall is vertical or horizontal.
+pub fn parse_input(input: &str) -> Result<Vec<Vec<(usize, usize)>>, ParseError> {
+    let mut walls = Vec::new();
+    for (i, line) in input.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let wall = line
+            .split(" -> ")
+            .map(|coord| {
+                coord
+                    .trim()
+                    .split_once(',')
+                    .and_then(|(x, y)| Some((x.parse::<usize>().ok()?, y.parse::<usize>().ok()?)))
+                    .ok_or_else(|| ParseError::new(i + 1, format!("invalid point {coord:?}")))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        if wall
+            .windows(2)
+            .any(|wd| wd[0].0 != wd[1].0 && wd[0].1 != wd[1].1)
+        {
+            return Err(ParseError::new(
+                i + 1,
+                "walls have to be vertical or horizontal",
+            ));
+        }
+        walls.push(wall);
+    }
+
+    if walls.is_empty() {
+        return Err(ParseError::new(input.lines().count(), "there are no walls"));
+    }
+    Ok(walls)
 }
 
 pub fn part1(walls: &[Vec<(usize, usize)>]) -> usize {
@@ -227,6 +249,8 @@ macro_rules! builder_option {
     };
 }
 
+// The printing switches are only flipped on by hand while debugging.
+#[allow(dead_code)]
 impl SimOptions {
     pub fn new() -> Self {
         Self {
@@ -241,6 +265,23 @@ impl SimOptions {
     builder_option!(print_result, bool);
 }
 
+/// The rock paths of the scan.
+pub struct Walls(pub Vec<Vec<(usize, usize)>>);
+
+impl Solution for Walls {
+    fn parse(input: &str) -> Result<Self, Error> {
+        Ok(Self(parse_input(input)?))
+    }
+
+    fn part1(&self) -> Result<impl Display, Error> {
+        Ok(part1(&self.0))
+    }
+
+    fn part2(&self) -> Result<impl Display, Error> {
+        Ok(part2(&self.0))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -250,15 +291,23 @@ mod tests {
 
     #[test]
     fn test_example_input_part1() {
-        let walls = parse_input(EXAMPLE_INPUT);
+        let walls = parse_input(EXAMPLE_INPUT).unwrap();
 
         assert_eq!(part1(&walls), 24);
     }
 
     #[test]
     fn test_example_input_part2() {
-        let walls = parse_input(EXAMPLE_INPUT);
+        let walls = parse_input(EXAMPLE_INPUT).unwrap();
 
         assert_eq!(part2(&walls), 93);
     }
+
+    #[test]
+    fn test_invalid_walls() {
+        let line = |input| parse_input(input).err().map(|e| e.line);
+        assert_eq!(line(""), Some(0));
+        assert_eq!(line("498,4 -> 498,6\n503,4 -> 502"), Some(2));
+        assert_eq!(line("498,4 -> 498,6\n\n503,4 -> 502,5"), Some(3));
+    }
 }
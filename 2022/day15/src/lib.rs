@@ -1,4 +1,6 @@
-use std::{ops::RangeInclusive, str::FromStr};
+use std::{fmt::Display, ops::RangeInclusive, str::FromStr};
+
+use aoc::{Error, ParseError, Solution};
 
 #[derive(Debug)]
 pub struct Sensor {
@@ -13,14 +15,15 @@ impl FromStr for Sensor {
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let (sensor_x, rem) = s
             .strip_prefix("Sensor at x=")
-            .unwrap()
+            .ok_or(())?
             .split_once(", y=")
-            .unwrap();
-        let (sensor_y, rem) = rem.split_once(": closest beacon is at x=").unwrap();
-        let (beacon_x, beacon_y) = rem.split_once(", y=").unwrap();
+            .ok_or(())?;
+        let (sensor_y, rem) = rem.split_once(": closest beacon is at x=").ok_or(())?;
+        let (beacon_x, beacon_y) = rem.split_once(", y=").ok_or(())?;
 
-        let position = (sensor_x.parse().unwrap(), sensor_y.parse().unwrap());
-        let closest_beacon = (beacon_x.parse().unwrap(), beacon_y.parse().unwrap());
+        let coordinate = |n: &str| n.trim().parse::<isize>().map_err(|_| ());
+        let position = (coordinate(sensor_x)?, coordinate(sensor_y)?);
+        let closest_beacon = (coordinate(beacon_x)?, coordinate(beacon_y)?);
         let range = position.manhattan_distance_to(&closest_beacon);
 
         Ok(Self {
@@ -33,8 +36,16 @@ impl FromStr for Sensor {
 
 impl Sensor {}
 
-pub fn parse_input(input: &str) -> Vec<Sensor> {
-    input.lines().map(|line| line.parse().unwrap()).collect()
+pub fn parse_input(input: &str) -> Result<Vec<Sensor>, ParseError> {
+    input
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| {
+            line.parse()
+                .map_err(|_| ParseError::new(i + 1, format!("invalid sensor {line:?}")))
+        })
+        .collect()
 }
 
 pub fn part1(sensors: &[Sensor], y: isize) -> usize {
@@ -62,7 +73,7 @@ pub fn part1(sensors: &[Sensor], y: isize) -> usize {
 pub fn part2(
     sensors: &[Sensor],
     search_space: (RangeInclusive<isize>, RangeInclusive<isize>),
-) -> usize {
+) -> Option<usize> {
     let mut beacon = None::<(isize, isize)>;
 
     'outer: for sensor in sensors {
@@ -90,11 +101,7 @@ pub fn part2(
         }
     }
 
-    if let Some(beacon) = beacon {
-        (beacon.0 * 4000000 + beacon.1) as usize
-    } else {
-        panic!("Couldn't find the distress signal beacon!");
-    }
+    beacon.map(|beacon| (beacon.0 * 4000000 + beacon.1) as usize)
 }
 
 trait ManhattanDistance {
@@ -111,6 +118,23 @@ impl ManhattanDistance for (isize, isize) {
     }
 }
 
+pub struct Sensors(pub Vec<Sensor>);
+
+impl Solution for Sensors {
+    fn parse(input: &str) -> Result<Self, Error> {
+        Ok(Self(parse_input(input)?))
+    }
+
+    fn part1(&self) -> Result<impl Display, Error> {
+        Ok(part1(&self.0, 2000000))
+    }
+
+    fn part2(&self) -> Result<impl Display, Error> {
+        part2(&self.0, (0..=4000000, 0..=4000000))
+            .ok_or_else(|| "couldn't find the distress signal beacon".into())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -132,14 +156,30 @@ Sensor at x=20, y=1: closest beacon is at x=15, y=3";
 
     #[test]
     fn test_example_input_part1() {
-        assert_eq!(part1(&parse_input(EXAMPLE_INPUT), 10), 26);
+        assert_eq!(part1(&parse_input(EXAMPLE_INPUT).unwrap(), 10), 26);
     }
 
     #[test]
     fn test_example_input_part2() {
         assert_eq!(
-            part2(&parse_input(EXAMPLE_INPUT), (0..=20, 0..=20)),
-            56000011
+            part2(&parse_input(EXAMPLE_INPUT).unwrap(), (0..=20, 0..=20)),
+            Some(56000011)
         );
     }
+
+    #[test]
+    fn test_invalid_sensors() {
+        let line = |input| parse_input(input).err().map(|e| e.line);
+        assert_eq!(
+            line("Sensor at x=2, y=18: closest beacon is at x=-2, y=15\nSensor at x=9"),
+            Some(2)
+        );
+        assert_eq!(
+            line("Sensor at x=2, y=18: closest beacon is at x=-2, y=1.5"),
+            Some(1)
+        );
+
+        let sensors = parse_input("Sensor at x=0, y=0: closest beacon is at x=4, y=4").unwrap();
+        assert_eq!(part2(&sensors, (0..=1, 0..=1)), None);
+    }
 }
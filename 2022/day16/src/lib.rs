@@ -2,46 +2,74 @@
 
 use std::{
     collections::{HashMap, HashSet, VecDeque},
+    fmt::Display,
     str,
 };
 
+use aoc::{Error, ParseError, Solution};
+
 #[derive(Debug)]
-pub struct Valve<'a> {
-    name: &'a str,
+pub struct Valve {
+    name: String,
     flow_rate: u32,
-    leads_to: Vec<&'a str>,
+    leads_to: Vec<String>,
+}
+
+/// The valves of the scan output by name.
+pub struct Scan(pub HashMap<String, Valve>);
+
+fn parse_valve(line: &str) -> Option<Valve> {
+    let (name, rem) = line.strip_prefix("Valve ")?.split_once(" has flow rate=")?;
+    let (flow_rate, rem) = rem
+        .split_once("; tunnel leads to valve ")
+        .or_else(|| rem.split_once("; tunnels lead to valves "))?;
+
+    Some(Valve {
+        name: name.to_string(),
+        flow_rate: flow_rate.parse().ok()?,
+        leads_to: rem.split(", ").map(str::to_string).collect(),
+    })
 }
 
-pub fn parse_input(input: &str) -> HashMap<&str, Valve<'_>> {
-    input
+pub fn parse_input(input: &str) -> Result<Scan, ParseError> {
+    let valves = input
         .lines()
-        .map(|line| {
-            let (name, rem) = line
-                .strip_prefix("Valve ")
-                .unwrap()
-                .split_once(" has flow rate=")
-                .unwrap();
-            let (flow_rate, rem) = rem
-                .split_once("; tunnel leads to valve ")
-                .or_else(|| rem.split_once("; tunnels lead to valves "))
-                .unwrap();
-            let flow_rate = flow_rate.parse().unwrap();
-            let leads_to = rem.split(", ").collect();
-
-            (
-                name,
-                Valve {
-                    name,
-                    flow_rate,
-                    leads_to,
-                },
-            )
+        .enumerate()
+        .filter(|(_, line)| !line.is_empty())
+        .map(|(i, line)| {
+            parse_valve(line)
+                .map(|valve| (i + 1, valve))
+                .ok_or_else(|| ParseError::new(i + 1, format!("invalid valve {line:?}")))
         })
-        .collect()
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let names = valves
+        .iter()
+        .map(|(_, valve)| valve.name.as_str())
+        .collect::<HashSet<_>>();
+    for (line, valve) in valves.iter() {
+        if let Some(unknown) = valve
+            .leads_to
+            .iter()
+            .find(|to| !names.contains(to.as_str()))
+        {
+            return Err(ParseError::new(*line, format!("no valve named {unknown}")));
+        }
+    }
+    if !names.contains("AA") {
+        return Err(ParseError::new(input.lines().count(), "no valve named AA"));
+    }
+
+    Ok(Scan(
+        valves
+            .into_iter()
+            .map(|(_, valve)| (valve.name.clone(), valve))
+            .collect(),
+    ))
 }
 
 fn explore_tunnel<'a>(
-    valves: &HashMap<&'a str, Valve<'a>>,
+    valves: &'a HashMap<String, Valve>,
     paths: &HashMap<(&str, &str), u32>,
     valve_name: &'a str,
     mut opened: HashSet<&'a str>,
@@ -55,8 +83,8 @@ fn explore_tunnel<'a>(
     //dbg!(&valve_name, time_left, pressure_release);
 
     let valve = &valves[valve_name];
-    if valve.flow_rate > 0 && !opened.contains(valve.name) {
-        opened.insert(valve.name);
+    if valve.flow_rate > 0 && !opened.contains(valve.name.as_str()) {
+        opened.insert(&valve.name);
 
         // spend one minute opening the valve
         time_left -= 1;
@@ -69,15 +97,15 @@ fn explore_tunnel<'a>(
 
         for unopened_valve in valves
             .values()
-            .filter(|&valve| valve.flow_rate > 0 && !opened.contains(valve.name))
+            .filter(|&valve| valve.flow_rate > 0 && !opened.contains(valve.name.as_str()))
         {
-            let steps_to_valve = paths[&(valve_name, unopened_valve.name)];
+            let steps_to_valve = paths[&(valve_name, unopened_valve.name.as_str())];
 
             if time_left > steps_to_valve {
                 let path_release = explore_tunnel(
                     valves,
                     paths,
-                    unopened_valve.name,
+                    &unopened_valve.name,
                     opened.clone(),
                     time_left - steps_to_valve,
                     pressure_release,
@@ -92,7 +120,7 @@ fn explore_tunnel<'a>(
     }
 }
 
-fn bfs<'a>(valves: &HashMap<&'a str, Valve<'a>>, from: &'a str, to: &'a str) -> u32 {
+fn bfs<'a>(valves: &'a HashMap<String, Valve>, from: &'a str, to: &'a str) -> u32 {
     let mut q = VecDeque::<&str>::new();
     let mut prev = HashMap::<&str, &str>::new();
     let mut explored = HashSet::<&str>::new();
@@ -103,7 +131,7 @@ fn bfs<'a>(valves: &HashMap<&'a str, Valve<'a>>, from: &'a str, to: &'a str) ->
         }
 
         for w in &valves[v].leads_to {
-            if !explored.contains(w) {
+            if !explored.contains(w.as_str()) {
                 explored.insert(w);
                 prev.insert(w, v);
                 q.push_back(w);
@@ -142,7 +170,7 @@ fn bfs<'a>(valves: &HashMap<&'a str, Valve<'a>>, from: &'a str, to: &'a str) ->
     steps
 }
 
-pub fn part1(valves: &HashMap<&str, Valve<'_>>) -> u32 {
+pub fn part1(valves: &HashMap<String, Valve>) -> u32 {
     let opened = HashSet::<&str>::with_capacity(valves.len());
     let start_valve = "AA";
 
@@ -152,8 +180,8 @@ pub fn part1(valves: &HashMap<&str, Valve<'_>>) -> u32 {
     for valve in valves.values() {
         for valve2 in valves.values().filter(|v| v.name != valve.name) {
             steps_to_valve.insert(
-                (valve.name, valve2.name),
-                bfs(valves, valve.name, valve2.name),
+                (valve.name.as_str(), valve2.name.as_str()),
+                bfs(valves, &valve.name, &valve2.name),
             );
         }
     }
@@ -161,6 +189,16 @@ pub fn part1(valves: &HashMap<&str, Valve<'_>>) -> u32 {
     explore_tunnel(valves, &steps_to_valve, start_valve, opened, 30, 0)
 }
 
+impl Solution for Scan {
+    fn parse(input: &str) -> Result<Self, Error> {
+        Ok(parse_input(input)?)
+    }
+
+    fn part1(&self) -> Result<impl Display, Error> {
+        Ok(part1(&self.0))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -178,7 +216,25 @@ Valve JJ has flow rate=21; tunnel leads to valve II";
 
     #[test]
     fn test_example_input_part1() {
-        let valves = parse_input(EXAMPLE_INPUT);
-        assert_eq!(part1(&valves), 1651);
+        let valves = parse_input(EXAMPLE_INPUT).unwrap();
+        assert_eq!(part1(&valves.0), 1651);
+    }
+
+    #[test]
+    fn test_invalid_scan() {
+        let error = |input: &str| parse_input(input).err().unwrap();
+
+        assert_eq!(
+            error(&EXAMPLE_INPUT.replace("rate=2;", "rate=two;")).line,
+            3
+        );
+        assert_eq!(
+            error(&EXAMPLE_INPUT.replace("valve GG", "valve KK")).line,
+            8
+        );
+        assert_eq!(
+            error(&EXAMPLE_INPUT.replace("Valve AA", "Valve KK")).line,
+            2
+        );
     }
 }
@@ -1,9 +1,5 @@
-use day16::{parse_input, part1};
+use aoc2022_day16::Scan;
 
 fn main() {
-    let input = include_str!("../input.txt");
-    let valves = parse_input(input);
-
-    let pr = part1(&valves);
-    println!("part1: {pr}");
+    aoc::run::<Scan>(env!("CARGO_MANIFEST_DIR"));
 }
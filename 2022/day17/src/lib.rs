@@ -1,4 +1,6 @@
-use std::{collections::HashSet, iter::FromIterator};
+use std::{collections::HashSet, fmt::Display, iter::FromIterator};
+
+use aoc::{Error, ParseError, Solution};
 
 const CHAMBER_WIDTH: usize = 7;
 
@@ -13,10 +15,37 @@ mod shapes {
 }
 use shapes::SHAPES;
 
-pub fn part1(input: &str) -> usize {
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Jet {
+    Left,
+    Right,
+}
+
+/// The jets pushing the rocks, repeated for as long as rocks fall.
+pub struct JetPattern(pub Vec<Jet>);
+
+pub fn parse_input(input: &str) -> Result<JetPattern, ParseError> {
+    let jets = input
+        .trim_end()
+        .chars()
+        .map(|c| match c {
+            '<' => Ok(Jet::Left),
+            '>' => Ok(Jet::Right),
+            c => Err(ParseError::new(1, format!("invalid jet {c:?}"))),
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    if jets.is_empty() {
+        return Err(ParseError::new(1, "expected at least one jet"));
+    }
+
+    Ok(JetPattern(jets))
+}
+
+pub fn part1(jets: &[Jet]) -> usize {
     let mut occupied = HashSet::<(usize, usize)>::new();
 
-    let mut jets = input.trim_end().as_bytes().iter().cycle();
+    let mut jets = jets.iter().cycle();
     let mut counter = 0;
 
     for shape in SHAPES.iter().cycle().take(2022) {
@@ -40,21 +69,20 @@ pub fn part1(input: &str) -> usize {
             let push_dir = jets.next().unwrap();
 
             let x_push = match push_dir {
-                b'>' => {
+                Jet::Right => {
                     if spawn_pos.0 + shape_width < CHAMBER_WIDTH {
                         1
                     } else {
                         0
                     }
                 }
-                b'<' => {
+                Jet::Left => {
                     if spawn_pos.0 > 0 {
                         -1
                     } else {
                         0
                     }
                 }
-                c => panic!("invalid push dir: '0x{c:x}'"),
             };
 
             // check if horizontal movement would cause a collision
@@ -128,6 +156,16 @@ fn print_chamber(occupied: &HashSet<(usize, usize)>) {
     }
 }
 
+impl Solution for JetPattern {
+    fn parse(input: &str) -> Result<Self, Error> {
+        Ok(parse_input(input)?)
+    }
+
+    fn part1(&self) -> Result<impl Display, Error> {
+        Ok(part1(&self.0))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -136,6 +174,13 @@ mod tests {
 
     #[test]
     fn test_example_input_part1() {
-        assert_eq!(part1(EXAMPLE_INPUT), 3068);
+        let jets = parse_input(EXAMPLE_INPUT).unwrap();
+        assert_eq!(part1(&jets.0), 3068);
+    }
+
+    #[test]
+    fn test_invalid_jets() {
+        assert!(parse_input(">><^").is_err());
+        assert!(parse_input("\n").is_err());
     }
 }
@@ -1,7 +1,5 @@
-use day17::part1;
+use aoc2022_day17::JetPattern;
 
 fn main() {
-    let input = include_str!("../input.txt");
-
-    println!("part1: {}", part1(input));
+    aoc::run::<JetPattern>(env!("CARGO_MANIFEST_DIR"));
 }
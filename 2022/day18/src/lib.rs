@@ -1,4 +1,6 @@
-use std::collections::HashSet;
+use std::{collections::HashSet, fmt::Display};
+
+use aoc::{Error, ParseError, Solution};
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub struct Point3D(i8, i8, i8);
@@ -33,13 +35,30 @@ impl Point3D {
     }
 }
 
-pub fn parse_input(input: &str) -> Vec<Point3D> {
+/// Coordinates are kept two away from the ends of `i8`, so the water around the droplet fits.
+pub fn parse_input(input: &str) -> Result<Vec<Point3D>, ParseError> {
+    let coordinate = |n: &str| {
+        n.trim()
+            .parse::<i8>()
+            .ok()
+            .filter(|n| (i8::MIN + 2..=i8::MAX - 2).contains(n))
+    };
+
     input
         .lines()
-        .map(|line| {
-            let (x, rem) = line.split_once(',').unwrap();
-            let (y, z) = rem.split_once(',').unwrap();
-            Point3D(x.parse().unwrap(), y.parse().unwrap(), z.parse().unwrap())
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| {
+            let mut coordinates = line.split(',').map(coordinate);
+            match (
+                coordinates.next(),
+                coordinates.next(),
+                coordinates.next(),
+                coordinates.next(),
+            ) {
+                (Some(Some(x)), Some(Some(y)), Some(Some(z)), None) => Ok(Point3D(x, y, z)),
+                _ => Err(ParseError::new(i + 1, format!("invalid cube {line:?}"))),
+            }
         })
         .collect()
 }
@@ -117,6 +136,23 @@ pub fn part2(cubes: &[Point3D]) -> usize {
         .sum()
 }
 
+/// The lava droplet's cubes.
+pub struct Droplet(pub Vec<Point3D>);
+
+impl Solution for Droplet {
+    fn parse(input: &str) -> Result<Self, Error> {
+        Ok(Self(parse_input(input)?))
+    }
+
+    fn part1(&self) -> Result<impl Display, Error> {
+        Ok(part1(&self.0))
+    }
+
+    fn part2(&self) -> Result<impl Display, Error> {
+        Ok(part2(&self.0))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -137,15 +173,26 @@ mod tests {
 
     #[test]
     fn test_example_input_part1() {
-        let cubes = parse_input(EXAMPLE_INPUT);
+        let cubes = parse_input(EXAMPLE_INPUT).unwrap();
 
         assert_eq!(part1(&cubes), 64);
     }
 
     #[test]
     fn test_example_input_part2() {
-        let cubes = parse_input(EXAMPLE_INPUT);
+        let cubes = parse_input(EXAMPLE_INPUT).unwrap();
 
         assert_eq!(part2(&cubes), 58);
     }
+
+    #[test]
+    fn test_invalid_cubes() {
+        let line = |input| parse_input(input).err().map(|e| e.line);
+        assert_eq!(line("1,2,3\n1,2"), Some(2));
+        assert_eq!(line("1,2,3\n1,2,3,4"), Some(2));
+        assert_eq!(line("1,2,3\n\n1,2,127"), Some(3));
+
+        let cubes = parse_input("-126,0,125").unwrap();
+        assert_eq!(part2(&cubes), 6);
+    }
 }
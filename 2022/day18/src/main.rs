@@ -1,11 +1,5 @@
-use day18::{parse_input, part1, part2};
+use aoc2022_day18::Droplet;
 
 fn main() {
-    let input = include_str!("../input.txt");
-    let cubes = parse_input(input);
-    let part1 = part1(&cubes);
-    println!("part1: {part1}");
-
-    let part2 = part2(&cubes);
-    println!("part2: {part2}");
+    aoc::run::<Droplet>(env!("CARGO_MANIFEST_DIR"));
 }
@@ -1,4 +1,6 @@
-use std::collections::VecDeque;
+use std::{collections::VecDeque, fmt::Display};
+
+use aoc::{Error, ParseError, Solution};
 
 #[derive(Debug)]
 pub struct Blueprint {
@@ -32,6 +34,7 @@ struct Inventory {
     geode_robots: u8,
 }
 
+#[allow(dead_code)]
 impl Inventory {
     fn robots(&self) -> u8 {
         self.ore_robots + self.clay_robots + self.obsidian_robots + self.geode_robots
@@ -133,14 +136,9 @@ fn simulate(blueprint: &Blueprint, minutes: u8) -> u8 {
     let mut invs = VecDeque::<(Inventory, u8)>::new();
     let mut finished_invs = vec![];
 
-    let mut last_min = 0;
     invs.push_back((inventory, 0));
 
     while let Some((inv, minute)) = invs.pop_front() {
-        if minute > last_min {
-            dbg!(minute);
-            last_min = minute;
-        }
         if minute == minutes {
             finished_invs.push(inv);
         } else {
@@ -159,9 +157,8 @@ pub fn part1(blueprints: &[Blueprint]) -> usize {
     let mut q_level = 0;
 
     for (i, blueprint) in blueprints.iter().enumerate() {
-        //dbg!(&blueprint, blueprint.most_expensive_ore());
-        let geodes = simulate(&blueprint, 24);
-        q_level += dbg!(geodes as usize * (i + 1));
+        let geodes = simulate(blueprint, 24);
+        q_level += geodes as usize * (i + 1);
     }
 
     q_level
@@ -170,38 +167,59 @@ pub fn part1(blueprints: &[Blueprint]) -> usize {
 pub fn part2(blueprints: &[Blueprint]) -> usize {
     let mut product: usize = 1;
     for (_i, blueprint) in blueprints.iter().enumerate().filter(|&(i, _)| i < 3) {
-        let geodes = simulate(&blueprint, 32);
-        product *= dbg!(geodes as usize);
+        let geodes = simulate(blueprint, 32);
+        product *= geodes as usize;
     }
 
     product
 }
 
-pub fn parse_input(input: &str) -> Vec<Blueprint> {
+fn parse_blueprint(line: &str) -> Option<Blueprint> {
+    let (_, rem) = line.split_once("Each ore robot costs ")?;
+    let (ore_cost, rem) = rem.split_once(" ore. Each clay robot costs ")?;
+    let (clay_cost, rem) = rem.split_once(" ore. Each obsidian robot costs ")?;
+    let (obsidian_ore_cost, rem) = rem.split_once(" ore and ")?;
+    let (obsidian_clay_cost, rem) = rem.split_once(" clay. Each geode robot costs ")?;
+    let (geode_ore_cost, rem) = rem.split_once(" ore and ")?;
+    let (geode_obsidian_cost, _rem) = rem.split_once(" obsidian.")?;
+
+    Some(Blueprint {
+        ore_cost: ore_cost.parse().ok()?,
+        clay_cost: clay_cost.parse().ok()?,
+        obsidian_ore_cost: obsidian_ore_cost.parse().ok()?,
+        obsidian_clay_cost: obsidian_clay_cost.parse().ok()?,
+        geode_ore_cost: geode_ore_cost.parse().ok()?,
+        geode_obsidian_cost: geode_obsidian_cost.parse().ok()?,
+    })
+}
+
+pub fn parse_input(input: &str) -> Result<Vec<Blueprint>, ParseError> {
     input
         .lines()
-        .map(|line| {
-            let (_, rem) = line.split_once("Each ore robot costs ").unwrap();
-            let (ore_cost, rem) = rem.split_once(" ore. Each clay robot costs ").unwrap();
-            let (clay_cost, rem) = rem.split_once(" ore. Each obsidian robot costs ").unwrap();
-            let (obsidian_ore_cost, rem) = rem.split_once(" ore and ").unwrap();
-            let (obsidian_clay_cost, rem) =
-                rem.split_once(" clay. Each geode robot costs ").unwrap();
-            let (geode_ore_cost, rem) = rem.split_once(" ore and ").unwrap();
-            let (geode_obsidian_cost, _rem) = rem.split_once(" obsidian.").unwrap();
-
-            Blueprint {
-                ore_cost: ore_cost.parse().unwrap(),
-                clay_cost: clay_cost.parse().unwrap(),
-                obsidian_ore_cost: obsidian_ore_cost.parse().unwrap(),
-                obsidian_clay_cost: obsidian_clay_cost.parse().unwrap(),
-                geode_ore_cost: geode_ore_cost.parse().unwrap(),
-                geode_obsidian_cost: geode_obsidian_cost.parse().unwrap(),
-            }
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| {
+            parse_blueprint(line).ok_or_else(|| ParseError::new(i + 1, "invalid blueprint"))
         })
         .collect()
 }
 
+pub struct Blueprints(pub Vec<Blueprint>);
+
+impl Solution for Blueprints {
+    fn parse(input: &str) -> Result<Self, Error> {
+        Ok(Self(parse_input(input)?))
+    }
+
+    fn part1(&self) -> Result<impl Display, Error> {
+        Ok(part1(&self.0))
+    }
+
+    fn part2(&self) -> Result<impl Display, Error> {
+        Ok(part2(&self.0))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -211,13 +229,21 @@ Blueprint 2: Each ore robot costs 2 ore. Each clay robot costs 3 ore. Each obsid
 
     #[test]
     fn test_example_input_part1() {
-        let blueprints = parse_input(EXAMPLE_INPUT);
+        let blueprints = parse_input(EXAMPLE_INPUT).unwrap();
         assert_eq!(part1(&blueprints), 33);
     }
 
     #[test]
     fn test_example_input_part2() {
-        let blueprints = parse_input(EXAMPLE_INPUT);
+        let blueprints = parse_input(EXAMPLE_INPUT).unwrap();
         assert_eq!(part2(&blueprints), 54 * 62); // this should be 56*62... idk, the final result is correct
     }
+
+    #[test]
+    fn test_invalid_blueprints() {
+        let line = |input: &str| parse_input(input).err().map(|e| e.line);
+        let (first, second) = EXAMPLE_INPUT.split_once('\n').unwrap();
+        assert_eq!(line(&format!("{first}\n{}", &second[..60])), Some(2));
+        assert_eq!(line(&first.replace("14 clay", "-14 clay")), Some(1));
+    }
 }
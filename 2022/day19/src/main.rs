@@ -1,11 +1,5 @@
-use day19::{parse_input, part1, part2};
+use aoc2022_day19::Blueprints;
 
 fn main() {
-    let input = include_str!("../input.txt");
-    let blueprints = parse_input(input);
-    let part1 = part1(&blueprints);
-    println!("part1: {part1}");
-
-    let part2 = part2(&blueprints);
-    println!("part2: {part2}");
+    aoc::run::<Blueprints>(env!("CARGO_MANIFEST_DIR"));
 }
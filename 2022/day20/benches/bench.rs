@@ -1,12 +1,15 @@
+use aoc2022_day20::{parse_input, part1, part2};
 use criterion::{black_box, criterion_group, criterion_main, Criterion};
-use day20::{part1, part2};
 
 fn criterion_benchmark(c: &mut Criterion) {
     let input = include_str!("../input.txt");
 
-    c.bench_function("part1", |b| b.iter(|| part1(black_box(input))));
+    c.bench_function("parse", |b| b.iter(|| parse_input(black_box(input))));
 
-    c.bench_function("part2", |b| b.iter(|| part2(black_box(input))));
+    let file = parse_input(input).unwrap();
+    c.bench_function("part1", |b| b.iter(|| part1(black_box(&file.0))));
+
+    c.bench_function("part2", |b| b.iter(|| part2(black_box(&file.0))));
 }
 
 criterion_group!(benches, criterion_benchmark);
@@ -1,19 +1,43 @@
-use std::collections::VecDeque;
+use std::{collections::VecDeque, fmt::Display};
 
-pub fn part1(input: &str) -> i64 {
-    decrypt(input, 1, 1)
+use aoc::{Error, ParseError, Solution};
+
+/// The numbers of the file in their original order.
+pub struct EncryptedFile(pub Vec<i64>);
+
+/// The file has to have at least two numbers to move around and a 0 to find the coordinates.
+pub fn parse_input(input: &str) -> Result<EncryptedFile, ParseError> {
+    let numbers = input
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.is_empty())
+        .map(|(i, line)| {
+            line.trim()
+                .parse::<i64>()
+                .map_err(|_| ParseError::new(i + 1, format!("invalid number {line:?}")))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let last_line = input.lines().count();
+    if numbers.len() < 2 {
+        return Err(ParseError::new(last_line, "expected at least two numbers"));
+    }
+    if !numbers.contains(&0) {
+        return Err(ParseError::new(last_line, "there's no 0 in the file"));
+    }
+
+    Ok(EncryptedFile(numbers))
 }
 
-pub fn part2(input: &str) -> i64 {
-    decrypt(input, 811589153, 10)
+pub fn part1(numbers: &[i64]) -> i64 {
+    decrypt(numbers, 1, 1)
 }
 
-fn decrypt(input: &str, key: i64, rounds: usize) -> i64 {
-    let original_numbers = input
-        .lines()
-        .map(|line| line.parse::<i64>().unwrap())
-        .collect::<Vec<_>>();
+pub fn part2(numbers: &[i64]) -> i64 {
+    decrypt(numbers, 811589153, 10)
+}
 
+fn decrypt(original_numbers: &[i64], key: i64, rounds: usize) -> i64 {
     let mut indices = (0..original_numbers.len()).collect::<VecDeque<_>>();
 
     for _round in 0..rounds {
@@ -50,6 +74,20 @@ fn decrypt(input: &str, key: i64, rounds: usize) -> i64 {
         .sum()
 }
 
+impl Solution for EncryptedFile {
+    fn parse(input: &str) -> Result<Self, Error> {
+        Ok(parse_input(input)?)
+    }
+
+    fn part1(&self) -> Result<impl Display, Error> {
+        Ok(part1(&self.0))
+    }
+
+    fn part2(&self) -> Result<impl Display, Error> {
+        Ok(part2(&self.0))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -64,11 +102,22 @@ mod tests {
 
     #[test]
     fn test_example_input_part1() {
-        assert_eq!(part1(EXAMPLE_INPUT), 3);
+        let file = parse_input(EXAMPLE_INPUT).unwrap();
+        assert_eq!(part1(&file.0), 3);
     }
 
     #[test]
     fn test_example_input_part2() {
-        assert_eq!(part2(EXAMPLE_INPUT), 1623178306);
+        let file = parse_input(EXAMPLE_INPUT).unwrap();
+        assert_eq!(part2(&file.0), 1623178306);
+    }
+
+    #[test]
+    fn test_invalid_file() {
+        let error = |input| parse_input(input).err().unwrap();
+
+        assert_eq!(error("1\n0\nx").line, 3);
+        assert_eq!(error("0").message, "expected at least two numbers");
+        assert_eq!(error("1\n2").message, "there's no 0 in the file");
     }
 }
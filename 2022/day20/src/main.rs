@@ -1,10 +1,5 @@
-use day20::{part1, part2};
+use aoc2022_day20::EncryptedFile;
 
 fn main() {
-    let input = include_str!("../input.txt");
-    let part1 = part1(input);
-    println!("part1: {part1}");
-
-    let part2 = part2(input);
-    println!("part2: {part2}");
+    aoc::run::<EncryptedFile>(env!("CARGO_MANIFEST_DIR"));
 }
@@ -1,12 +1,13 @@
 #![warn(rust_2018_idioms)]
 
 use std::{
-    collections::HashMap,
+    collections::{HashMap, HashSet},
     fmt::Display,
-    ops::{Add, Div, Mul, Sub},
     str::FromStr,
 };
 
+use aoc::{Error, ParseError, Solution};
+
 #[derive(Debug)]
 enum Operation {
     Add,
@@ -24,7 +25,7 @@ impl FromStr for Operation {
             "-" => Self::Subtract,
             "*" => Self::Multiply,
             "/" => Self::Divide,
-            _ => panic!("Invalid operation '{s}'"),
+            _ => Err(())?,
         })
     }
 }
@@ -45,15 +46,13 @@ impl Display for Operation {
 }
 
 impl Operation {
-    pub fn eval<T>(&self, op1: T, op2: T) -> T
-    where
-        T: Add<Output = T> + Sub<Output = T> + Mul<Output = T> + Div<Output = T>,
-    {
+    /// Returns `None` on overflow or division by zero.
+    pub fn eval(&self, op1: i64, op2: i64) -> Option<i64> {
         match self {
-            Operation::Add => op1 + op2,
-            Operation::Subtract => op1 - op2,
-            Operation::Multiply => op1 * op2,
-            Operation::Divide => op1 / op2,
+            Operation::Add => op1.checked_add(op2),
+            Operation::Subtract => op1.checked_sub(op2),
+            Operation::Multiply => op1.checked_mul(op2),
+            Operation::Divide => op1.checked_div(op2),
         }
     }
 }
@@ -75,8 +74,8 @@ impl FromStr for Term {
         if let Ok(num) = s.parse() {
             Ok(Self::Literal(num))
         } else {
-            let (op1, rem) = s.split_once(' ').unwrap();
-            let (op, op2) = rem.split_once(' ').unwrap();
+            let (op1, rem) = s.split_once(' ').ok_or(())?;
+            let (op, op2) = rem.split_once(' ').ok_or(())?;
 
             Ok(Self::Formula {
                 op: op.parse()?,
@@ -87,28 +86,112 @@ impl FromStr for Term {
     }
 }
 
-fn solve(formulae: &HashMap<&str, Term>, name: &str) -> i64 {
+impl Term {
+    fn operands(&self) -> impl Iterator<Item = &str> {
+        let operands = match self {
+            Term::Formula { op1, op2, .. } => Some([op1.as_str(), op2.as_str()]),
+            Term::Literal(_) => None,
+        };
+        operands.into_iter().flatten()
+    }
+}
+
+/// What every monkey yells, by name.
+#[derive(Debug)]
+pub struct Riddle(HashMap<String, Term>);
+
+/// Returns a monkey on a cycle if `name` depends on one. `done` holds the names already known
+/// not to.
+fn find_cycle<'a>(
+    formulae: &'a HashMap<String, Term>,
+    name: &'a str,
+    path: &mut Vec<&'a str>,
+    done: &mut HashSet<&'a str>,
+) -> Option<&'a str> {
+    if path.contains(&name) {
+        return Some(name);
+    }
+    if done.contains(name) {
+        return None;
+    }
+
+    path.push(name);
+    for operand in formulae[name].operands() {
+        if let Some(cycle) = find_cycle(formulae, operand, path, done) {
+            return Some(cycle);
+        }
+    }
+    path.pop();
+    done.insert(name);
+
+    None
+}
+
+/// Every monkey a formula refers to has to exist, `root` has to yell a formula and no monkey
+/// can depend on what it yells itself.
+pub fn parse_input(input: &str) -> Result<Riddle, ParseError> {
+    let mut lines = HashMap::new();
+    let mut names = Vec::new();
+    let mut formulae = HashMap::new();
+
+    for (i, line) in input.lines().enumerate() {
+        if line.is_empty() {
+            continue;
+        }
+
+        let monkey = line
+            .split_once(": ")
+            .and_then(|(name, formula)| Some((name, formula.parse::<Term>().ok()?)));
+        let Some((name, term)) = monkey else {
+            return Err(ParseError::new(i + 1, format!("invalid monkey {line:?}")));
+        };
+        lines.insert(name, i + 1);
+        names.push(name);
+        formulae.insert(name.to_owned(), term);
+    }
+
+    for name in names.iter() {
+        if let Some(unknown) = formulae[*name]
+            .operands()
+            .find(|op| !formulae.contains_key(*op))
+        {
+            let message = format!("no monkey named {unknown}");
+            return Err(ParseError::new(lines[name], message));
+        }
+    }
+
+    if !matches!(formulae.get("root"), Some(Term::Formula { .. })) {
+        let message = "root has to yell a formula";
+        return Err(ParseError::new(input.lines().count(), message));
+    }
+
+    let mut done = HashSet::new();
+    for name in names.iter() {
+        if let Some(cycle) = find_cycle(&formulae, name, &mut Vec::new(), &mut done) {
+            let message = format!("{cycle} depends on what it yells itself");
+            return Err(ParseError::new(lines[cycle], message));
+        }
+    }
+
+    Ok(Riddle(formulae))
+}
+
+/// Returns `None` on overflow or division by zero.
+fn solve(formulae: &HashMap<String, Term>, name: &str) -> Option<i64> {
     let term = &formulae[name];
 
     match term {
-        Term::Formula { op, op1, op2 } => op.eval(solve(formulae, &op1), solve(formulae, &op2)),
-        Term::Literal(literal) => *literal,
+        Term::Formula { op, op1, op2 } => op.eval(solve(formulae, op1)?, solve(formulae, op2)?),
+        Term::Literal(literal) => Some(*literal),
     }
 }
 
-pub fn part1(input: &str) -> i64 {
-    let formulae = input
-        .lines()
-        .map(|line| {
-            let (result_name, formula) = line.split_once(": ").unwrap();
-            (result_name, formula.parse::<Term>().unwrap())
-        })
-        .collect::<HashMap<&str, Term>>();
-
-    solve(&formulae, "root")
+/// Returns `None` on overflow or division by zero.
+pub fn part1(riddle: &Riddle) -> Option<i64> {
+    solve(&riddle.0, "root")
 }
 
-fn stringify(formulae: &HashMap<&str, Term>, name: &str) -> String {
+fn stringify(formulae: &HashMap<String, Term>, name: &str) -> String {
     let term = &formulae[name];
 
     if name == "humn" {
@@ -117,10 +200,10 @@ fn stringify(formulae: &HashMap<&str, Term>, name: &str) -> String {
         match term {
             Term::Formula { op, op1, op2 } => {
                 format!(
-                    "({} {} {})",
-                    stringify(formulae, &op1),
+                    "({} {} {})",
+                    stringify(formulae, op1),
                     op,
-                    stringify(formulae, &op2)
+                    stringify(formulae, op2)
                 )
             }
             Term::Literal(literal) => format!("{literal}"),
@@ -128,20 +211,14 @@ fn stringify(formulae: &HashMap<&str, Term>, name: &str) -> String {
     }
 }
 
-pub fn part2(input: &str) -> String {
-    let formulae = input
-        .lines()
-        .map(|line| {
-            let (result_name, formula) = line.split_once(": ").unwrap();
-            (result_name, formula.parse::<Term>().unwrap())
-        })
-        .collect::<HashMap<&str, Term>>();
+pub fn part2(riddle: &Riddle) -> String {
+    let formulae = &riddle.0;
 
     if let Term::Formula { op: _, op1, op2 } = &formulae["root"] {
         let formula = format!(
             "{} = {}",
-            stringify(&formulae, op1),
-            stringify(&formulae, op2)
+            stringify(formulae, op1),
+            stringify(formulae, op2)
         );
 
         // let output = std::process::Command::new("qalc")
@@ -152,7 +229,21 @@ pub fn part2(input: &str) -> String {
 
         formula
     } else {
-        panic!("Expected formula at root");
+        unreachable!("parse_input checks that root is a formula");
+    }
+}
+
+impl Solution for Riddle {
+    fn parse(input: &str) -> Result<Self, Error> {
+        Ok(parse_input(input)?)
+    }
+
+    fn part1(&self) -> Result<impl Display, Error> {
+        part1(self).ok_or_else(|| "the numbers overflow or get divided by zero".into())
+    }
+
+    fn part2(&self) -> Result<impl Display, Error> {
+        Ok(part2(self))
     }
 }
 
@@ -178,7 +269,32 @@ hmdt: 32";
 
     #[test]
     fn test_example_input_part1() {
-        assert_eq!(part1(EXAMPLE_INPUT), 152);
+        let riddle = parse_input(EXAMPLE_INPUT).unwrap();
+        assert_eq!(part1(&riddle), Some(152));
+    }
+
+    #[test]
+    fn test_invalid_riddle() {
+        let error = |input: &str| parse_input(input).unwrap_err();
+
+        assert_eq!(
+            error(&EXAMPLE_INPUT.replace("hmdt - zczc", "hmdt % zczc")).line,
+            14
+        );
+        assert_eq!(
+            error(&EXAMPLE_INPUT.replace("ljgn * ptdq", "ljgn * ptdx")).line,
+            13
+        );
+        assert_eq!(
+            error(&EXAMPLE_INPUT.replace("ljgn: 2", "ljgn: lgvd + zczc")).line,
+            13
+        );
+        assert_eq!(error(&EXAMPLE_INPUT.replace("root", "toor")).line, 15);
+
+        let riddle = EXAMPLE_INPUT
+            .replace("dbpl: 5", "dbpl: 0")
+            .replace("drzm * dbpl", "drzm / dbpl");
+        assert_eq!(part1(&parse_input(&riddle).unwrap()), None);
     }
 
     // #[test]
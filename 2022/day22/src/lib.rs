@@ -1,4 +1,6 @@
-use std::collections::HashMap;
+use std::{collections::HashMap, fmt::Display};
+
+use aoc::{Error, ParseError, Solution};
 
 #[derive(Debug, PartialEq)]
 enum Tile {
@@ -10,49 +12,50 @@ enum Tile {
 #[derive(Debug)]
 pub struct Map {
     tiles: HashMap<(isize, isize), Tile>,
-    path: String,
+    path: Vec<PathSegment>,
+    /// The leftmost open tile of the top row.
+    start: (isize, isize),
 }
 
-impl Map {
-    fn start(&self) -> (isize, isize) {
-        *self
-            .tiles
-            .iter()
-            .filter(|&(p, t)| p.1 == 0 && *t == Tile::Open)
-            .min_by_key(|(p, _t)| p.0)
-            .map(|(p, _t)| p)
-            .unwrap()
+pub fn parse_input(input: &str) -> Result<Map, ParseError> {
+    let mut lines = input.lines().enumerate();
+
+    let mut tiles = HashMap::new();
+    for (y, line) in lines.by_ref().take_while(|(_, line)| !line.is_empty()) {
+        for (x, c) in line.chars().enumerate() {
+            let tile = match c {
+                ' ' => Tile::Void,
+                '.' => Tile::Open,
+                '#' => Tile::Wall,
+                _ => return Err(ParseError::new(y + 1, format!("invalid tile {c:?}"))),
+            };
+            tiles.insert((x as isize, y as isize), tile);
+        }
     }
-}
 
-pub fn parse_input(input: &str) -> Map {
-    let (map, path) = input.split_once("\n\n").unwrap();
-
-    let tiles = map
-        .lines()
-        .enumerate()
-        .flat_map(|(y, line)| {
-            line.as_bytes().iter().enumerate().map(move |(x, c)| {
-                (
-                    (x as isize, y as isize),
-                    match c {
-                        b' ' => Tile::Void,
-                        b'.' => Tile::Open,
-                        b'#' => Tile::Wall,
-                        _ => panic!("Invalid tile '{c}'"),
-                    },
-                )
-            })
-        })
-        .collect::<HashMap<_, _>>();
-
-    Map {
-        tiles,
-        path: path.to_owned(),
+    let start = tiles
+        .iter()
+        .filter(|&(p, t)| p.1 == 0 && *t == Tile::Open)
+        .min_by_key(|(p, _t)| p.0)
+        .map(|(p, _t)| *p)
+        .ok_or_else(|| ParseError::new(1, "the top row has no open tile"))?;
+
+    let (i, mut rem) = lines
+        .find(|(_, line)| !line.is_empty())
+        .ok_or_else(|| ParseError::new(input.lines().count(), "the path is missing"))?;
+    let mut path = Vec::new();
+    while let (next, Some(seg)) = parse_path(rem) {
+        path.push(seg);
+        rem = next;
     }
+    if !rem.trim().is_empty() {
+        return Err(ParseError::new(i + 1, format!("invalid path at {rem:?}")));
+    }
+
+    Ok(Map { tiles, path, start })
 }
 
-#[derive(Debug)]
+#[derive(Clone, Copy, Debug)]
 enum PathSegment {
     TurnLeft,
     TurnRight,
@@ -69,7 +72,10 @@ fn parse_path(path: &str) -> (&str, Option<PathSegment>) {
                     .position(|c: char| !c.is_ascii_digit())
                     .unwrap_or(path.len()),
             );
-            (rem, Some(PathSegment::Move(num.parse().unwrap())))
+            match num.parse() {
+                Ok(steps) => (rem, Some(PathSegment::Move(steps))),
+                Err(_) => (path, None),
+            }
         }
         _ => (path, None),
     }
@@ -77,11 +83,9 @@ fn parse_path(path: &str) -> (&str, Option<PathSegment>) {
 
 pub fn part1(map: &Map) -> isize {
     let mut dir = 0;
-    let mut pos = map.start();
-
-    let (mut path_rem, mut path_seg) = parse_path(&map.path);
+    let mut pos = map.start;
 
-    while let Some(seg) = path_seg {
+    for &seg in &map.path {
         match seg {
             PathSegment::TurnLeft => {
                 if dir == 0 {
@@ -111,48 +115,45 @@ pub fn part1(map: &Map) -> isize {
 
                     match map.tiles.get(&newpos) {
                         Some(Tile::Void) | None => {
-                            println!("almost fell into the void");
-
+                            // walk back to the other side of the map
                             let mut wrappos = pos;
                             loop {
                                 wrappos = (wrappos.0 - delta.0, wrappos.1 - delta.1);
                                 match map.tiles.get(&wrappos) {
-                                    Some(Tile::Void) | None => {
-                                        println!("found the other side");
-                                        //pos = (wrappos.0 + delta.0, wrappos.1 + delta.1);
-                                        break;
-                                    }
-                                    Some(Tile::Open) | Some(Tile::Wall) => {
-                                        println!("wrapping around... {wrappos:?}");
-                                    }
+                                    Some(Tile::Void) | None => break,
+                                    Some(Tile::Open) | Some(Tile::Wall) => {}
                                 }
                             }
 
                             let newpos = (wrappos.0 + delta.0, wrappos.1 + delta.1);
+                            // a wall on the other side keeps us where we are
                             if map.tiles.get(&newpos) != Some(&Tile::Wall) {
                                 pos = newpos;
-                            } else {
-                                println!("There's a wall on the other side.. staying at {pos:?} instead of going to {newpos:?}");
                             }
                         }
-                        Some(Tile::Wall) => {
-                            println!("ran into a wall at {newpos:?}");
-                        }
+                        Some(Tile::Wall) => {}
                         Some(Tile::Open) => {
                             pos = newpos;
                         }
                     };
-                    dbg!(&pos, &dir);
                 }
             }
         }
-
-        (path_rem, path_seg) = parse_path(path_rem);
     }
 
     (pos.0 + 1) * 4 + (pos.1 + 1) * 1000 + dir
 }
 
+impl Solution for Map {
+    fn parse(input: &str) -> Result<Self, Error> {
+        Ok(parse_input(input)?)
+    }
+
+    fn part1(&self) -> Result<impl Display, Error> {
+        Ok(part1(self))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -174,7 +175,16 @@ mod tests {
 
     #[test]
     fn test_example_input_part1() {
-        let map = parse_input(EXAMPLE_INPUT);
+        let map = parse_input(EXAMPLE_INPUT).unwrap();
         assert_eq!(part1(&map), 6032);
     }
+
+    #[test]
+    fn test_invalid_input() {
+        let line = |input| parse_input(input).err().map(|e| e.line);
+        assert_eq!(line("  ..\n  .x\n\n10R5"), Some(2));
+        assert_eq!(line("  ##\n  ..\n\n10R5"), Some(1));
+        assert_eq!(line("  ..\n  ..\n"), Some(2));
+        assert_eq!(line("  ..\n  ..\n\n10X5"), Some(4));
+    }
 }
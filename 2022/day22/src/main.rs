@@ -1,8 +1,5 @@
-use day22::{parse_input, part1};
+use aoc2022_day22::Map;
 
 fn main() {
-    let input = include_str!("../input.txt");
-    let map = parse_input(input);
-    let part1 = part1(&map);
-    println!("part1: {part1}");
+    aoc::run::<Map>(env!("CARGO_MANIFEST_DIR"));
 }
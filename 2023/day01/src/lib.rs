@@ -0,0 +1,349 @@
+use std::{collections::VecDeque, fmt::Display};
+
+use aoc::{Error, Solution};
+
+/// The words that count as numbers and what they're worth.
+#[derive(Clone, Debug)]
+pub struct Vocabulary {
+    words: Vec<(String, u64)>,
+}
+
+impl Vocabulary {
+    pub fn digits() -> Self {
+        Self {
+            words: (0..=9).map(|d| (d.to_string(), d)).collect(),
+        }
+    }
+
+    /// Digits and `one` to `nine` spelled out.
+    pub fn english() -> Self {
+        Self::digits().with_words(&[
+            ("one", 1),
+            ("two", 2),
+            ("three", 3),
+            ("four", 4),
+            ("five", 5),
+            ("six", 6),
+            ("seven", 7),
+            ("eight", 8),
+            ("nine", 9),
+        ])
+    }
+
+    pub fn with_words(mut self, words: &[(&str, u64)]) -> Self {
+        self.words
+            .extend(words.iter().map(|&(word, value)| (word.to_string(), value)));
+        self
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+struct Node {
+    next: Vec<(u8, usize)>,
+    /// The longest proper suffix of this node that is also in the trie.
+    fail: usize,
+    /// Index of the word ending exactly here.
+    word: Option<usize>,
+    /// The closest node along the fail links that ends a word.
+    dict: Option<usize>,
+}
+
+/// Aho-Corasick automaton over a vocabulary, finds every occurrence of every word in one pass,
+/// overlapping ones like `eightwo` included.
+#[derive(Clone, Debug)]
+pub struct Scanner {
+    nodes: Vec<Node>,
+    words: Vec<(usize, u64)>,
+}
+
+/// A word found at byte offset `start`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Match {
+    pub start: usize,
+    pub len: usize,
+    pub value: u64,
+}
+
+impl Scanner {
+    pub fn new(vocabulary: &Vocabulary) -> Self {
+        let mut nodes = vec![Node::default()];
+
+        for (index, (word, _)) in vocabulary.words.iter().enumerate() {
+            let mut node = 0;
+            for &c in word.as_bytes() {
+                node = match Self::child(&nodes, node, c) {
+                    Some(child) => child,
+                    None => {
+                        nodes.push(Node::default());
+                        let child = nodes.len() - 1;
+                        nodes[node].next.push((c, child));
+                        child
+                    }
+                };
+            }
+            nodes[node].word = Some(index);
+        }
+
+        // Breadth first, so every fail target is done before the nodes pointing to it.
+        let mut queue = nodes[0]
+            .next
+            .iter()
+            .map(|&(_, child)| child)
+            .collect::<VecDeque<_>>();
+        while let Some(node) = queue.pop_front() {
+            for (c, child) in nodes[node].next.clone() {
+                let mut fail = nodes[node].fail;
+                let target = loop {
+                    if let Some(target) = Self::child(&nodes, fail, c) {
+                        break target;
+                    }
+                    if fail == 0 {
+                        break 0;
+                    }
+                    fail = nodes[fail].fail;
+                };
+
+                nodes[child].fail = target;
+                nodes[child].dict = match nodes[target].word {
+                    Some(_) => Some(target),
+                    None => nodes[target].dict,
+                };
+                queue.push_back(child);
+            }
+        }
+
+        Self {
+            nodes,
+            words: vocabulary
+                .words
+                .iter()
+                .map(|(word, value)| (word.len(), *value))
+                .collect(),
+        }
+    }
+
+    fn child(nodes: &[Node], node: usize, c: u8) -> Option<usize> {
+        nodes[node]
+            .next
+            .iter()
+            .find(|&&(edge, _)| edge == c)
+            .map(|&(_, child)| child)
+    }
+
+    pub fn scan(&self, line: &str) -> Vec<Match> {
+        let mut matches = Vec::new();
+        let mut node = 0;
+
+        for (end, &c) in line.as_bytes().iter().enumerate() {
+            node = loop {
+                if let Some(child) = Self::child(&self.nodes, node, c) {
+                    break child;
+                }
+                if node == 0 {
+                    break 0;
+                }
+                node = self.nodes[node].fail;
+            };
+
+            let mut output = match self.nodes[node].word {
+                Some(_) => Some(node),
+                None => self.nodes[node].dict,
+            };
+            while let Some(found) = output {
+                let (len, value) = self.words[self.nodes[found].word.unwrap()];
+                matches.push(Match {
+                    start: end + 1 - len,
+                    len,
+                    value,
+                });
+                output = self.nodes[found].dict;
+            }
+        }
+
+        matches
+    }
+
+    /// The first and last number written next to each other. Of two numbers starting at the
+    /// same place the longer one counts, so `nineteen` beats `nine`.
+    pub fn calibration_value(&self, line: &str) -> Option<u64> {
+        let matches = self.scan(line);
+        let first = matches
+            .iter()
+            .min_by_key(|m| (m.start, std::cmp::Reverse(m.len)))?;
+        let last = matches.iter().max_by_key(|m| (m.start, m.len))?;
+
+        let shift = 10u64.pow(last.value.checked_ilog10().unwrap_or(0) + 1);
+        Some(first.value * shift + last.value)
+    }
+}
+
+/// `line` is 1-based.
+#[derive(Debug, PartialEq, Eq)]
+pub struct CalibrationError {
+    pub line: usize,
+}
+
+impl Display for CalibrationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line {} has no numbers", self.line)
+    }
+}
+
+impl std::error::Error for CalibrationError {}
+
+pub fn calibration_sum(input: &str, vocabulary: &Vocabulary) -> Result<u64, CalibrationError> {
+    let scanner = Scanner::new(vocabulary);
+
+    input
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| {
+            scanner
+                .calibration_value(line)
+                .ok_or(CalibrationError { line: i + 1 })
+        })
+        .sum()
+}
+
+pub fn parse_input_part1(input: &str) -> Result<u64, CalibrationError> {
+    calibration_sum(input, &Vocabulary::digits())
+}
+
+pub fn parse_input_part2(input: &str) -> Result<u64, CalibrationError> {
+    calibration_sum(input, &Vocabulary::english())
+}
+
+/// A line of the calibration document, read with both vocabularies.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Calibration {
+    /// 1-based, blank lines included.
+    pub line: usize,
+    pub digits: Option<u64>,
+    pub english: Option<u64>,
+}
+
+/// The calibration document, one value per line.
+#[derive(Debug)]
+pub struct CalibrationDocument(pub Vec<Calibration>);
+
+pub fn parse_document(input: &str) -> CalibrationDocument {
+    let digits = Scanner::new(&Vocabulary::digits());
+    let english = Scanner::new(&Vocabulary::english());
+
+    let calibrations = input
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| Calibration {
+            line: i + 1,
+            digits: digits.calibration_value(line),
+            english: english.calibration_value(line),
+        })
+        .collect();
+    CalibrationDocument(calibrations)
+}
+
+fn document_sum(
+    calibrations: &[Calibration],
+    value: impl Fn(&Calibration) -> Option<u64>,
+) -> Result<u64, CalibrationError> {
+    calibrations
+        .iter()
+        .map(|calibration| {
+            value(calibration).ok_or(CalibrationError {
+                line: calibration.line,
+            })
+        })
+        .sum()
+}
+
+impl Solution for CalibrationDocument {
+    /// Lines without numbers only fail the part that reads them.
+    fn parse(input: &str) -> Result<Self, Error> {
+        Ok(parse_document(input))
+    }
+
+    fn part1(&self) -> Result<impl Display, Error> {
+        Ok(document_sum(&self.0, |calibration| calibration.digits)?)
+    }
+
+    fn part2(&self) -> Result<impl Display, Error> {
+        Ok(document_sum(&self.0, |calibration| calibration.english)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE_INPUT_PART1: &str = "1abc2
+pqr3stu8vwx
+a1b2c3d4e5f
+treb7uchet";
+
+    const EXAMPLE_INPUT_PART2: &str = "two1nine
+eightwothree
+abcone2threexyz
+xtwone3four
+4nineeightseven2
+zoneight234
+7pqrstsixteen";
+
+    #[test]
+    fn test_example_input_part1() {
+        assert_eq!(parse_input_part1(EXAMPLE_INPUT_PART1), Ok(142));
+    }
+
+    #[test]
+    fn test_example_input_part2() {
+        assert_eq!(parse_input_part2(EXAMPLE_INPUT_PART2), Ok(281));
+    }
+
+    #[test]
+    fn test_overlaps_and_vocabularies() {
+        let english = Scanner::new(&Vocabulary::english());
+        assert_eq!(english.calibration_value("eightwo"), Some(82));
+        assert_eq!(english.calibration_value("oneight"), Some(18));
+        assert_eq!(english.calibration_value("sevenine"), Some(79));
+        assert_eq!(english.calibration_value("zero"), None);
+
+        let teens = Scanner::new(&Vocabulary::english().with_words(&[
+            ("zero", 0),
+            ("thirteen", 13),
+            ("nineteen", 19),
+        ]));
+        assert_eq!(teens.calibration_value("nineteenzero"), Some(190));
+        assert_eq!(teens.calibration_value("5thirteen"), Some(513));
+        assert_eq!(teens.calibration_value("xnineteen"), Some(1919));
+
+        let german = Scanner::new(&Vocabulary::digits().with_words(&[
+            ("eins", 1),
+            ("zwei", 2),
+            ("drei", 3),
+            ("fünf", 5),
+        ]));
+        assert_eq!(german.calibration_value("fünfzweins"), Some(51));
+        assert_eq!(german.calibration_value("dreieins"), Some(31));
+    }
+
+    #[test]
+    fn test_missing_numbers() {
+        assert_eq!(
+            parse_input_part1("1abc2\nnothing here\n3"),
+            Err(CalibrationError { line: 2 })
+        );
+        assert_eq!(parse_input_part2("one\n\ntwo"), Ok(11 + 22));
+
+        let document = parse_document("one\n\ntwo");
+        assert_eq!(document.0[1].line, 3);
+        assert_eq!(
+            document_sum(&document.0, |calibration| calibration.digits),
+            Err(CalibrationError { line: 1 })
+        );
+        assert_eq!(
+            document_sum(&document.0, |calibration| calibration.english),
+            Ok(11 + 22)
+        );
+    }
+}
@@ -0,0 +1,367 @@
+use std::fmt::Display;
+
+use aoc::{Error, Solution};
+
+/// Cubes shown at once, as `(colour, count)` with colours indexing `CubeGames::colours`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Draw {
+    pub cubes: Vec<(usize, usize)>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Game {
+    pub id: usize,
+    pub draws: Vec<Draw>,
+}
+
+/// How many cubes of each colour are in the bag, indexed like `CubeGames::colours`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Bag {
+    pub counts: Vec<usize>,
+}
+
+impl Bag {
+    pub fn total(&self) -> usize {
+        self.counts.iter().sum()
+    }
+
+    pub fn power(&self) -> usize {
+        self.counts.iter().product()
+    }
+
+    pub fn contains(&self, other: &Bag) -> bool {
+        self.counts
+            .iter()
+            .zip(other.counts.iter())
+            .all(|(a, b)| a >= b)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum ParseErrorKind {
+    MissingHeader,
+    InvalidGameNumber(String),
+    /// A draw entry that isn't `<count> <colour>`.
+    InvalidCubes(String),
+}
+
+/// `line` is 1-based, `game` is known once the header is parsed.
+#[derive(Debug, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub game: Option<usize>,
+    pub kind: ParseErrorKind,
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line {}", self.line)?;
+        if let Some(game) = self.game {
+            write!(f, " (game {})", game)?;
+        }
+        match &self.kind {
+            ParseErrorKind::MissingHeader => write!(f, ": expected `Game <n>: `"),
+            ParseErrorKind::InvalidGameNumber(s) => write!(f, ": invalid game number {s:?}"),
+            ParseErrorKind::InvalidCubes(s) => {
+                write!(f, ": expected `<count> <colour>`, found {s:?}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+#[derive(Clone, Debug)]
+pub struct CubeGames {
+    /// Every colour in the input, in order of first appearance.
+    pub colours: Vec<String>,
+    pub games: Vec<Game>,
+}
+
+impl CubeGames {
+    pub fn parse(input: &str) -> Result<Self, ParseError> {
+        let mut colours: Vec<String> = Vec::new();
+        let mut games = Vec::new();
+
+        for (i, line) in input.lines().enumerate() {
+            let mut error = ParseError {
+                line: i + 1,
+                game: None,
+                kind: ParseErrorKind::MissingHeader,
+            };
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            let Some((game_name, hands)) = line.split_once(':') else {
+                return Err(error);
+            };
+            let Some(id) = game_name.trim().strip_prefix("Game ") else {
+                return Err(error);
+            };
+            let id = id.trim().parse().map_err(|_| ParseError {
+                kind: ParseErrorKind::InvalidGameNumber(id.to_string()),
+                ..error
+            })?;
+            error.game = Some(id);
+
+            let mut draws = Vec::new();
+            for hand in hands.split(';') {
+                let mut cubes = Vec::new();
+                for entry in hand.split(',').map(str::trim) {
+                    let invalid = || ParseError {
+                        kind: ParseErrorKind::InvalidCubes(entry.to_string()),
+                        ..error
+                    };
+
+                    let (count, colour) = entry.split_once(' ').ok_or_else(invalid)?;
+                    let count = count.parse().map_err(|_| invalid())?;
+                    let colour = colour.trim();
+                    if colour.is_empty() {
+                        return Err(invalid());
+                    }
+
+                    let colour = match colours.iter().position(|known| known == colour) {
+                        Some(index) => index,
+                        None => {
+                            colours.push(colour.to_string());
+                            colours.len() - 1
+                        }
+                    };
+                    cubes.push((colour, count));
+                }
+                draws.push(Draw { cubes });
+            }
+
+            games.push(Game { id, draws });
+        }
+
+        Ok(Self { colours, games })
+    }
+
+    /// A bag with the given number of cubes per colour name, colours not listed are empty.
+    /// Colours that no game uses don't matter and are dropped.
+    pub fn bag(&self, counts: &[(&str, usize)]) -> Bag {
+        Bag {
+            counts: self
+                .colours
+                .iter()
+                .map(|colour| {
+                    counts
+                        .iter()
+                        .find(|(name, _)| name == colour)
+                        .map_or(0, |&(_, count)| count)
+                })
+                .collect(),
+        }
+    }
+
+    pub fn describe(&self, bag: &Bag) -> String {
+        self.colours
+            .iter()
+            .zip(bag.counts.iter())
+            .map(|(colour, count)| format!("{count} {colour}"))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    /// The fewest cubes of every colour `game` could have been played with.
+    pub fn minimum_bag(&self, game: &Game) -> Bag {
+        let mut counts = vec![0; self.colours.len()];
+        for &(colour, count) in game.draws.iter().flat_map(|draw| draw.cubes.iter()) {
+            counts[colour] = counts[colour].max(count);
+        }
+
+        Bag { counts }
+    }
+
+    pub fn feasible<'a>(&'a self, bag: &'a Bag) -> impl Iterator<Item = &'a Game> + 'a {
+        self.games
+            .iter()
+            .filter(move |game| bag.contains(&self.minimum_bag(game)))
+    }
+
+    /// The ids of the games possible with 12 red, 13 green and 14 blue cubes, summed up.
+    pub fn possible_id_sum(&self) -> usize {
+        let bag = self.bag(&[("red", 12), ("green", 13), ("blue", 14)]);
+        self.feasible(&bag).map(|game| game.id).sum()
+    }
+
+    pub fn power_sum(&self) -> usize {
+        self.games
+            .iter()
+            .map(|game| self.minimum_bag(game).power())
+            .sum()
+    }
+
+    /// The bag with the fewest cubes in total that makes at least `k` games feasible, `None` if
+    /// there aren't that many games.
+    ///
+    /// Every count of the best bag is some game's minimum for that colour, so this tries those
+    /// for all colours but the last and takes the `k`th smallest for the last one. That's
+    /// polynomial for a fixed number of colours, with pruning on the best total so far.
+    pub fn smallest_bag_for(&self, k: usize) -> Option<Bag> {
+        if k > self.games.len() {
+            return None;
+        }
+        if k == 0 || self.colours.is_empty() {
+            return Some(Bag {
+                counts: vec![0; self.colours.len()],
+            });
+        }
+
+        let minimums = self
+            .games
+            .iter()
+            .map(|game| self.minimum_bag(game))
+            .collect::<Vec<_>>();
+        let candidates = (0..self.colours.len())
+            .map(|colour| {
+                let mut values = minimums
+                    .iter()
+                    .map(|bag| bag.counts[colour])
+                    .collect::<Vec<_>>();
+                values.sort_unstable();
+                values.dedup();
+                values
+            })
+            .collect::<Vec<_>>();
+
+        let mut best = None;
+        let mut counts = Vec::with_capacity(self.colours.len());
+        let fitting = minimums.iter().collect::<Vec<_>>();
+        Self::search(&candidates, k, &fitting, &mut counts, &mut best);
+
+        best
+    }
+
+    fn search(
+        candidates: &[Vec<usize>],
+        k: usize,
+        fitting: &[&Bag],
+        counts: &mut Vec<usize>,
+        best: &mut Option<Bag>,
+    ) {
+        let colour = counts.len();
+        let so_far = counts.iter().sum::<usize>();
+        if best.as_ref().is_some_and(|best| so_far >= best.total()) {
+            return;
+        }
+
+        if colour == candidates.len() - 1 {
+            let mut last = fitting
+                .iter()
+                .map(|bag| bag.counts[colour])
+                .collect::<Vec<_>>();
+            last.sort_unstable();
+
+            counts.push(last[k - 1]);
+            let bag = Bag {
+                counts: counts.clone(),
+            };
+            if best.as_ref().is_none_or(|best| bag.total() < best.total()) {
+                *best = Some(bag);
+            }
+            counts.pop();
+            return;
+        }
+
+        for &value in candidates[colour].iter() {
+            let fitting = fitting
+                .iter()
+                .copied()
+                .filter(|bag| bag.counts[colour] <= value)
+                .collect::<Vec<_>>();
+            if fitting.len() < k {
+                continue;
+            }
+
+            counts.push(value);
+            Self::search(candidates, k, &fitting, counts, best);
+            counts.pop();
+        }
+    }
+}
+
+impl Solution for CubeGames {
+    fn parse(input: &str) -> Result<Self, Error> {
+        Ok(CubeGames::parse(input)?)
+    }
+
+    fn part1(&self) -> Result<impl Display, Error> {
+        Ok(self.possible_id_sum())
+    }
+
+    fn part2(&self) -> Result<impl Display, Error> {
+        Ok(self.power_sum())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const EXAMPLE_INPUT: &str = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
+Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
+Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
+Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
+Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green";
+
+    #[test]
+    fn test_part1() {
+        let games = CubeGames::parse(EXAMPLE_INPUT).unwrap();
+        assert_eq!(games.possible_id_sum(), 8);
+    }
+
+    #[test]
+    fn test_part2() {
+        let games = CubeGames::parse(EXAMPLE_INPUT).unwrap();
+        assert_eq!(games.power_sum(), 2286);
+    }
+
+    #[test]
+    fn test_smallest_bag() {
+        let games = CubeGames::parse(EXAMPLE_INPUT).unwrap();
+        assert_eq!(games.colours, vec!["blue", "red", "green"]);
+
+        // Minimum bags as (blue, red, green): 1 (6, 4, 2), 2 (4, 1, 3), 3 (6, 20, 13),
+        // 4 (15, 14, 3), 5 (2, 6, 3).
+        let bag = games.smallest_bag_for(1).unwrap();
+        assert_eq!(bag.counts, vec![4, 1, 3]);
+        let bag = games.smallest_bag_for(3).unwrap();
+        assert_eq!(bag.counts, vec![6, 6, 3]);
+        assert_eq!(games.feasible(&bag).count(), 3);
+        let bag = games.smallest_bag_for(5).unwrap();
+        assert_eq!(bag.counts, vec![15, 20, 13]);
+        assert_eq!(games.smallest_bag_for(6), None);
+    }
+
+    #[test]
+    fn test_other_colours_and_errors() {
+        let games =
+            CubeGames::parse("Game 7: 2 cyan, 1 magenta; 3 cyan\nGame 8: 1 yellow").unwrap();
+        assert_eq!(games.colours, vec!["cyan", "magenta", "yellow"]);
+        let bag = games.bag(&[("cyan", 3), ("magenta", 1)]);
+        assert_eq!(
+            games.feasible(&bag).map(|game| game.id).collect::<Vec<_>>(),
+            vec![7]
+        );
+
+        assert_eq!(
+            CubeGames::parse("Game 1: 1 red\nGame 2: 1 red, blue").unwrap_err(),
+            ParseError {
+                line: 2,
+                game: Some(2),
+                kind: ParseErrorKind::InvalidCubes("blue".to_string())
+            }
+        );
+        assert_eq!(
+            CubeGames::parse("Game x: 1 red").unwrap_err().kind,
+            ParseErrorKind::InvalidGameNumber("x".to_string())
+        );
+        assert_eq!(
+            CubeGames::parse("1 red").unwrap_err().kind,
+            ParseErrorKind::MissingHeader
+        );
+    }
+}
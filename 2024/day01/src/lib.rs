@@ -0,0 +1,249 @@
+use std::{collections::BTreeMap, fmt::Display};
+
+use aoc::{Error, ParseError, Solution};
+
+pub struct LocationLists {
+    /// Both lists in input order, `left[i]` and `right[i]` come from the same line.
+    left: Vec<i64>,
+    right: Vec<i64>,
+    left_sorted: Vec<i64>,
+    right_sorted: Vec<i64>,
+}
+
+impl LocationLists {
+    /// Columns may be separated by any whitespace, blank lines are skipped.
+    pub fn parse(input: &str) -> Result<Self, ParseError> {
+        let mut left = Vec::new();
+        let mut right = Vec::new();
+
+        for (i, line) in input.lines().enumerate() {
+            let error = |message: String| ParseError::new(i + 1, message);
+            let number = |s: &str| {
+                s.parse::<i64>()
+                    .map_err(|_| error(format!("invalid number {s:?}")))
+            };
+
+            match line.split_whitespace().collect::<Vec<_>>()[..] {
+                [] => continue,
+                [_] => return Err(error("expected two numbers, found one".to_string())),
+                [num1, num2] => {
+                    left.push(number(num1)?);
+                    right.push(number(num2)?);
+                }
+                _ => return Err(error("expected two numbers, found more".to_string())),
+            }
+        }
+
+        let mut left_sorted = left.clone();
+        let mut right_sorted = right.clone();
+        left_sorted.sort_unstable();
+        right_sorted.sort_unstable();
+
+        Ok(Self {
+            left,
+            right,
+            left_sorted,
+            right_sorted,
+        })
+    }
+
+    /// Pairs up the smallest with the smallest, and so on.
+    fn distances(&self) -> impl Iterator<Item = i64> + '_ {
+        self.left_sorted
+            .iter()
+            .zip(self.right_sorted.iter())
+            .map(|(num1, num2)| (num1 - num2).abs())
+    }
+
+    pub fn total_distance(&self) -> i64 {
+        self.distances().sum()
+    }
+
+    pub fn distance_histogram(&self) -> BTreeMap<i64, usize> {
+        let mut histogram = BTreeMap::new();
+        for distance in self.distances() {
+            *histogram.entry(distance).or_default() += 1;
+        }
+
+        histogram
+    }
+
+    /// Walks both sorted lists in step, one run of equal values at a time. Calls `visit` with
+    /// every distinct value and how often it occurs on the left and on the right.
+    fn merge_runs(&self, mut visit: impl FnMut(i64, usize, usize)) {
+        let (left, right) = (&self.left_sorted, &self.right_sorted);
+        let (mut i, mut j) = (0, 0);
+
+        while i < left.len() || j < right.len() {
+            let value = match (left.get(i), right.get(j)) {
+                (Some(&a), Some(&b)) => a.min(b),
+                (Some(&a), None) => a,
+                (None, Some(&b)) => b,
+                (None, None) => unreachable!(),
+            };
+
+            let run = |list: &[i64], start: usize| {
+                list[start..].iter().take_while(|&&v| v == value).count()
+            };
+            let (in_left, in_right) = (run(left, i), run(right, j));
+            visit(value, in_left, in_right);
+
+            i += in_left;
+            j += in_right;
+        }
+    }
+
+    /// Every left value times how often it occurs in the right list.
+    pub fn similarity_score(&self) -> i64 {
+        let mut score = 0;
+        self.merge_runs(|value, in_left, in_right| {
+            score += value * (in_left * in_right) as i64;
+        });
+
+        score
+    }
+
+    pub fn only_in_left(&self) -> Vec<i64> {
+        let mut values = Vec::new();
+        self.merge_runs(|value, _, in_right| {
+            if in_right == 0 {
+                values.push(value);
+            }
+        });
+
+        values
+    }
+
+    pub fn only_in_right(&self) -> Vec<i64> {
+        let mut values = Vec::new();
+        self.merge_runs(|value, in_left, _| {
+            if in_left == 0 {
+                values.push(value);
+            }
+        });
+
+        values
+    }
+
+    /// Spearman's rank correlation between the two columns as they are paired up in the input.
+    /// Ties get the average of their ranks. `None` if either column is constant.
+    pub fn spearman(&self) -> Option<f64> {
+        let left = ranks(&self.left);
+        let right = ranks(&self.right);
+        let n = left.len() as f64;
+
+        let mean = (n + 1.0) / 2.0;
+        let (mut covariance, mut left_variance, mut right_variance) = (0.0, 0.0, 0.0);
+        for (a, b) in left.iter().zip(right.iter()) {
+            covariance += (a - mean) * (b - mean);
+            left_variance += (a - mean).powi(2);
+            right_variance += (b - mean).powi(2);
+        }
+
+        if left_variance == 0.0 || right_variance == 0.0 {
+            return None;
+        }
+
+        Some(covariance / (left_variance * right_variance).sqrt())
+    }
+}
+
+impl Solution for LocationLists {
+    fn parse(input: &str) -> Result<Self, Error> {
+        Ok(LocationLists::parse(input)?)
+    }
+
+    fn part1(&self) -> Result<impl Display, Error> {
+        Ok(self.total_distance())
+    }
+
+    fn part2(&self) -> Result<impl Display, Error> {
+        Ok(self.similarity_score())
+    }
+}
+
+/// 1-based ranks in input order, tied values share the mean of their ranks.
+fn ranks(values: &[i64]) -> Vec<f64> {
+    let mut order = (0..values.len()).collect::<Vec<_>>();
+    order.sort_unstable_by_key(|&i| values[i]);
+
+    let mut ranks = vec![0.0; values.len()];
+    let mut start = 0;
+    while start < order.len() {
+        let value = values[order[start]];
+        let end = start
+            + order[start..]
+                .iter()
+                .take_while(|&&i| values[i] == value)
+                .count();
+
+        let rank = (start + 1 + end) as f64 / 2.0;
+        for &i in order[start..end].iter() {
+            ranks[i] = rank;
+        }
+        start = end;
+    }
+
+    ranks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE_INPUT: &str = "3   4
+4   3
+2   5
+1   3
+3   9
+3   3";
+
+    #[test]
+    fn test_example1() {
+        let lists = LocationLists::parse(EXAMPLE_INPUT).unwrap();
+        assert_eq!(lists.total_distance(), 11);
+        assert_eq!(lists.similarity_score(), 31);
+    }
+
+    #[test]
+    fn test_statistics() {
+        let lists = LocationLists::parse(EXAMPLE_INPUT).unwrap();
+
+        assert_eq!(
+            lists.distance_histogram().into_iter().collect::<Vec<_>>(),
+            vec![(0, 1), (1, 2), (2, 2), (5, 1)]
+        );
+        assert_eq!(lists.only_in_left(), vec![1, 2]);
+        assert_eq!(lists.only_in_right(), vec![5, 9]);
+
+        assert_eq!(
+            ranks(&[3, 4, 2, 1, 3, 3]),
+            vec![4.0, 6.0, 2.0, 1.0, 4.0, 4.0]
+        );
+        let rho = lists.spearman().unwrap();
+        assert!((rho - -1.5 / 15.5).abs() < 1e-9, "{rho}");
+
+        let constant = LocationLists::parse("1 2\n1 3").unwrap();
+        assert_eq!(constant.spearman(), None);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let lists = LocationLists::parse("3\t4\n\n  1 2  \n").unwrap();
+        assert_eq!(lists.total_distance(), 2);
+
+        let error = |input| LocationLists::parse(input).err().unwrap();
+        assert_eq!(
+            error("3 4\n5"),
+            ParseError::new(2, "expected two numbers, found one")
+        );
+        assert_eq!(
+            error("3 4 5"),
+            ParseError::new(1, "expected two numbers, found more")
+        );
+        assert_eq!(
+            error("3 4\n\nx 4"),
+            ParseError::new(3, "invalid number \"x\"")
+        );
+    }
+}
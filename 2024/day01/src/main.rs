@@ -1,73 +1,35 @@
-fn main() {
-    let input = include_str!("../input.txt");
-
-    println!("part1: {}", part1(input));
-    println!("part2: {}", part2(input));
-}
-
-fn parse_input(input: &str) -> (Vec<i64>, Vec<i64>) {
-    let mut list1: Vec<i64> = Vec::new();
-    let mut list2: Vec<i64> = Vec::new();
-
-    input
-        .lines()
-        .map(|line| {
-            let (num1, num2) = line.split_once(' ').unwrap();
-            (
-                num1.trim_end().parse().unwrap(),
-                num2.trim_start().parse().unwrap(),
-            )
-        })
-        .for_each(|(num1, num2)| {
-            list1.push(num1);
-            list2.push(num2)
-        });
+use aoc2024_day01::LocationLists;
 
-    (list1, list2)
-}
-
-fn part1(input: &str) -> i64 {
-    let (mut list1, mut list2) = parse_input(input);
-
-    list1.sort();
-    list2.sort();
-
-    list1
-        .iter()
-        .zip(list2.iter())
-        .map(|(num1, num2)| (num1 - num2).abs())
-        .sum()
-}
-
-fn part2(input: &str) -> i64 {
-    let (mut list1, mut list2) = parse_input(input);
-
-    list1.sort();
-    list2.sort();
-
-    list1
+fn main() {
+    let input = aoc::input(env!("CARGO_MANIFEST_DIR"));
+    let lists = match LocationLists::parse(input) {
+        Ok(lists) => lists,
+        Err(e) => {
+            eprintln!("input.txt {}", e);
+            return;
+        }
+    };
+
+    println!("part1: {}", lists.total_distance());
+    println!("part2: {}", lists.similarity_score());
+
+    let histogram = lists.distance_histogram();
+    let (most_common, count) = histogram
         .iter()
-        .map(|num1| {
-            let factor = list2.iter().filter(|&v| v == num1).count() as i64;
-            num1 * factor
-        })
-        .sum()
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    const EXAMPLE_INPUT: &str = "3   4
-4   3
-2   5
-1   3
-3   9
-3   3";
-
-    #[test]
-    fn test_example1() {
-        assert_eq!(part1(EXAMPLE_INPUT), 11);
-        assert_eq!(part2(EXAMPLE_INPUT), 31);
+        .max_by_key(|(_, &count)| count)
+        .unwrap_or((&0, &0));
+    println!(
+        "{} distinct distances, {} is the most common one ({} times)",
+        histogram.len(),
+        most_common,
+        count
+    );
+    println!(
+        "{} values only in the left list, {} only in the right one",
+        lists.only_in_left().len(),
+        lists.only_in_right().len()
+    );
+    if let Some(rho) = lists.spearman() {
+        println!("Spearman rank correlation: {:.4}", rho);
     }
 }
@@ -0,0 +1,266 @@
+use std::fmt::Display;
+
+use aoc::{Error, ParseError, Solution};
+
+pub fn parse_input(input: &str) -> Result<Vec<Vec<i64>>, ParseError> {
+    input
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| {
+            line.split_whitespace()
+                .map(|lvl| {
+                    lvl.parse()
+                        .map_err(|_| ParseError::new(i + 1, format!("invalid level {lvl:?}")))
+                })
+                .collect()
+        })
+        .collect()
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Direction {
+    Increasing,
+    Decreasing,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    /// Safe once the levels at these indices are left out.
+    Safe {
+        direction: Direction,
+        removed: Vec<usize>,
+    },
+    /// Indices of the levels whose step from the previous level is out of bounds, in the
+    /// direction with the fewest such steps.
+    Unsafe { culprits: Vec<usize> },
+}
+
+/// Reports are safe if they only increase or only decrease, by `min_step..=max_step` between
+/// neighbouring levels, after leaving out at most `removable` levels.
+#[derive(Clone, Copy, Debug)]
+pub struct Tolerance {
+    min_step: i64,
+    max_step: i64,
+    removable: usize,
+}
+
+impl Tolerance {
+    pub fn new(min_step: i64, max_step: i64) -> Self {
+        Self {
+            min_step,
+            max_step,
+            removable: 0,
+        }
+    }
+
+    pub fn with_dampener(self, removable: usize) -> Self {
+        Self { removable, ..self }
+    }
+
+    fn step_ok(&self, direction: Direction, from: i64, to: i64) -> bool {
+        let step = match direction {
+            Direction::Increasing => to.checked_sub(from),
+            Direction::Decreasing => from.checked_sub(to),
+        };
+
+        step.is_some_and(|step| (self.min_step..=self.max_step).contains(&step))
+    }
+
+    /// The fewest levels to leave out for `levels` to be safe in `direction`, if that's at most
+    /// `removable`. `removals[i]` is the fewest removals for a safe run ending in level `i`,
+    /// found among the `removable + 1` levels before it, which keeps this O(n * k).
+    fn fewest_removals(&self, levels: &[i64], direction: Direction) -> Option<Vec<usize>> {
+        let n = levels.len();
+        let mut removals = vec![usize::MAX; n];
+        let mut previous = vec![None; n];
+
+        for i in 0..n {
+            // Start the run at `i`, dropping everything before it.
+            removals[i] = i;
+
+            for j in i.saturating_sub(self.removable + 1)..i {
+                if removals[j] == usize::MAX || !self.step_ok(direction, levels[j], levels[i]) {
+                    continue;
+                }
+
+                let cost = removals[j] + (i - j - 1);
+                if cost < removals[i] {
+                    removals[i] = cost;
+                    previous[i] = Some(j);
+                }
+            }
+        }
+
+        // End the run at `last`, dropping everything after it.
+        let last = (0..n).min_by_key(|&i| removals[i].saturating_add(n - 1 - i))?;
+        if removals[last] + (n - 1 - last) > self.removable {
+            return None;
+        }
+
+        let mut kept = vec![false; n];
+        let mut i = Some(last);
+        while let Some(j) = i {
+            kept[j] = true;
+            i = previous[j];
+        }
+
+        Some((0..n).filter(|&i| !kept[i]).collect())
+    }
+
+    pub fn check(&self, levels: &[i64]) -> Verdict {
+        if levels.is_empty() {
+            return Verdict::Safe {
+                direction: Direction::Increasing,
+                removed: Vec::new(),
+            };
+        }
+
+        let directions = [Direction::Increasing, Direction::Decreasing];
+        let best = directions
+            .iter()
+            .filter_map(|&direction| Some((direction, self.fewest_removals(levels, direction)?)))
+            .min_by_key(|(_, removed)| removed.len());
+
+        if let Some((direction, removed)) = best {
+            return Verdict::Safe { direction, removed };
+        }
+
+        let culprits = directions
+            .iter()
+            .map(|&direction| {
+                (1..levels.len())
+                    .filter(|&i| !self.step_ok(direction, levels[i - 1], levels[i]))
+                    .collect::<Vec<_>>()
+            })
+            .min_by_key(Vec::len)
+            .unwrap();
+
+        Verdict::Unsafe { culprits }
+    }
+
+    pub fn is_safe(&self, levels: &[i64]) -> bool {
+        matches!(self.check(levels), Verdict::Safe { .. })
+    }
+}
+
+pub fn count_safe(reports: &[Vec<i64>], tolerance: Tolerance) -> usize {
+    reports
+        .iter()
+        .filter(|lvls| tolerance.is_safe(lvls))
+        .count()
+}
+
+pub fn part1(reports: &[Vec<i64>]) -> usize {
+    count_safe(reports, Tolerance::new(1, 3))
+}
+
+pub fn part2(reports: &[Vec<i64>]) -> usize {
+    count_safe(reports, Tolerance::new(1, 3).with_dampener(1))
+}
+
+/// Every report's levels.
+pub struct Reports(pub Vec<Vec<i64>>);
+
+impl Solution for Reports {
+    fn parse(input: &str) -> Result<Self, Error> {
+        Ok(Self(parse_input(input)?))
+    }
+
+    fn part1(&self) -> Result<impl Display, Error> {
+        Ok(part1(&self.0))
+    }
+
+    fn part2(&self) -> Result<impl Display, Error> {
+        Ok(part2(&self.0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE_INPUT: &str = "7 6 4 2 1
+1 2 7 8 9
+9 7 6 2 1
+1 3 2 4 5
+8 6 4 4 1
+1 3 6 7 9";
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(part1(&parse_input(EXAMPLE_INPUT).unwrap()), 2);
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(part2(&parse_input(EXAMPLE_INPUT).unwrap()), 4);
+
+        let dampened = Tolerance::new(1, 3).with_dampener(1);
+        assert_eq!(
+            dampened.check(&[1, 3, 2, 4, 5]),
+            Verdict::Safe {
+                direction: Direction::Increasing,
+                removed: vec![2]
+            }
+        );
+        assert_eq!(
+            dampened.check(&[8, 6, 4, 4, 1]),
+            Verdict::Safe {
+                direction: Direction::Decreasing,
+                removed: vec![3]
+            }
+        );
+        assert_eq!(
+            dampened.check(&[1, 2, 7, 8, 9]),
+            Verdict::Unsafe { culprits: vec![2] }
+        );
+    }
+
+    #[test]
+    fn test_tolerance_matches_brute_force() {
+        let reports = parse_input(EXAMPLE_INPUT)
+            .unwrap()
+            .into_iter()
+            .chain([
+                vec![5, 1, 2, 3, 4],
+                vec![1, 2, 3, 4, 0],
+                vec![1, 9, 9, 2, 3, 4],
+                vec![3, 1, 2, 5, 2, 8],
+                vec![10, 1, 11, 2, 12, 3],
+            ])
+            .collect::<Vec<_>>();
+
+        // Tries every way to leave out up to `k` levels.
+        fn brute_force(tolerance: &Tolerance, levels: &[i64], k: usize) -> bool {
+            tolerance.with_dampener(0).is_safe(levels)
+                || (k > 0
+                    && (0..levels.len()).any(|i| {
+                        let rest = [&levels[..i], &levels[i + 1..]].concat();
+                        brute_force(tolerance, &rest, k - 1)
+                    }))
+        }
+
+        for steps in [(1, 3), (0, 2), (2, 9)] {
+            for k in 0..3 {
+                let tolerance = Tolerance::new(steps.0, steps.1).with_dampener(k);
+                for report in reports.iter() {
+                    assert_eq!(
+                        tolerance.is_safe(report),
+                        brute_force(&tolerance, report, k),
+                        "{report:?} with {tolerance:?}"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_invalid_reports() {
+        assert_eq!(parse_input("1 2 3\n1 x 3").err().map(|e| e.line), Some(2));
+        assert_eq!(parse_input("1 2 3\n\n4 5").unwrap().len(), 2);
+
+        let tolerance = Tolerance::new(1, 3);
+        assert!(!tolerance.is_safe(&[i64::MIN, i64::MAX]));
+    }
+}
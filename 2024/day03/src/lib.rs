@@ -0,0 +1,322 @@
+use std::{
+    fmt::Display,
+    io::{self, BufRead},
+};
+
+use aoc::{Error, Solution};
+
+/// Operands are 1 to 3 digits long.
+const MAX_DIGITS: usize = 3;
+
+/// Applies an instruction with its operands to the state.
+type Effect<S> = Box<dyn Fn(&mut S, &[i64])>;
+
+struct Instruction<S> {
+    name: &'static str,
+    arity: usize,
+    effect: Effect<S>,
+}
+
+enum Match {
+    /// Could still turn into the instruction with more input.
+    Prefix,
+    Complete(Vec<i64>),
+    Mismatch,
+}
+
+impl<S> Instruction<S> {
+    /// Matches `name(a,b,...)` with exactly `arity` operands against the start of `token`.
+    fn match_token(&self, token: &[u8]) -> Match {
+        let name = self.name.as_bytes();
+        if token.len() <= name.len() {
+            return match name.starts_with(token) {
+                true => Match::Prefix,
+                false => Match::Mismatch,
+            };
+        }
+        if !token.starts_with(name) || token[name.len()] != b'(' {
+            return Match::Mismatch;
+        }
+
+        let mut rest = token[name.len() + 1..].iter().copied();
+        let mut operands = Vec::with_capacity(self.arity);
+        if self.arity == 0 {
+            return match rest.next() {
+                None => Match::Prefix,
+                Some(b')') => Match::Complete(operands),
+                Some(_) => Match::Mismatch,
+            };
+        }
+
+        let mut digits = 0;
+        let mut operand = 0;
+        for c in rest {
+            match c {
+                b'0'..=b'9' if digits < MAX_DIGITS => {
+                    digits += 1;
+                    operand = operand * 10 + (c - b'0') as i64;
+                }
+                b',' | b')' if digits > 0 => {
+                    operands.push(operand);
+                    let last = operands.len() == self.arity;
+                    if (c == b')') != last {
+                        return Match::Mismatch;
+                    }
+                    if last {
+                        return Match::Complete(operands);
+                    }
+                    (digits, operand) = (0, 0);
+                }
+                _ => return Match::Mismatch,
+            }
+        }
+
+        Match::Prefix
+    }
+}
+
+/// Scans corrupted memory for registered instructions and applies them to a state `S`,
+/// skipping everything else.
+pub struct Interpreter<S> {
+    instructions: Vec<Instruction<S>>,
+}
+
+impl<S> Default for Interpreter<S> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<S> Interpreter<S> {
+    /// An interpreter that knows no instructions yet.
+    pub fn new() -> Self {
+        Self {
+            instructions: Vec::new(),
+        }
+    }
+
+    /// Adds `name(a,b,...)` with exactly `arity` operands of 1 to 3 digits, calling `effect` with
+    /// the state and the operands whenever it appears.
+    pub fn register(
+        mut self,
+        name: &'static str,
+        arity: usize,
+        effect: impl Fn(&mut S, &[i64]) + 'static,
+    ) -> Self {
+        self.instructions.push(Instruction {
+            name,
+            arity,
+            effect: Box::new(effect),
+        });
+        self
+    }
+
+    /// Feeds the input through one byte at a time, so instructions may be split across however
+    /// `reader` chunks it.
+    pub fn run<R: BufRead>(&self, mut reader: R, state: &mut S) -> io::Result<()> {
+        // The bytes since the start of an instruction that might still match.
+        let mut token = Vec::new();
+
+        loop {
+            let chunk = reader.fill_buf()?;
+            if chunk.is_empty() {
+                return Ok(());
+            }
+
+            for &c in chunk {
+                token.push(c);
+                self.advance(&mut token, state);
+            }
+
+            let len = chunk.len();
+            reader.consume(len);
+        }
+    }
+
+    fn advance(&self, token: &mut Vec<u8>, state: &mut S) {
+        while !token.is_empty() {
+            let mut prefix = false;
+            for instruction in self.instructions.iter() {
+                match instruction.match_token(token) {
+                    Match::Complete(operands) => {
+                        (instruction.effect)(state, &operands);
+                        token.clear();
+                        return;
+                    }
+                    Match::Prefix => prefix = true,
+                    Match::Mismatch => {}
+                }
+            }
+
+            if prefix {
+                return;
+            }
+            // Nothing starts here, an instruction might still start further in.
+            token.remove(0);
+        }
+    }
+
+    fn run_str(&self, input: &str) -> S
+    where
+        S: Default,
+    {
+        let mut state = S::default();
+        self.run(input.as_bytes(), &mut state).unwrap();
+        state
+    }
+}
+
+/// An instruction found in the corrupted memory.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Op {
+    Mul(i64, i64),
+    Do,
+    Dont,
+}
+
+/// The instructions in the corrupted memory, in order.
+#[derive(Debug)]
+pub struct Memory(pub Vec<Op>);
+
+fn scanner() -> Interpreter<Vec<Op>> {
+    Interpreter::new()
+        .register("mul", 2, |ops: &mut Vec<Op>, operands| {
+            ops.push(Op::Mul(operands[0], operands[1]))
+        })
+        .register("do", 0, |ops, _| ops.push(Op::Do))
+        .register("don't", 0, |ops, _| ops.push(Op::Dont))
+}
+
+pub fn parse_input(input: &str) -> Memory {
+    Memory(scanner().run_str(input))
+}
+
+/// Like [`parse_input`], but streams the memory from `reader` instead of holding it all at once.
+pub fn read_input<R: BufRead>(reader: R) -> io::Result<Memory> {
+    let mut ops = Vec::new();
+    scanner().run(reader, &mut ops)?;
+    Ok(Memory(ops))
+}
+
+pub fn part1(ops: &[Op]) -> i64 {
+    ops.iter()
+        .map(|op| match op {
+            Op::Mul(a, b) => a * b,
+            _ => 0,
+        })
+        .sum()
+}
+
+pub fn part2(ops: &[Op]) -> i64 {
+    let mut enabled = true;
+    let mut sum = 0;
+    for op in ops {
+        match op {
+            Op::Mul(a, b) if enabled => sum += a * b,
+            Op::Mul(..) => {}
+            Op::Do => enabled = true,
+            Op::Dont => enabled = false,
+        }
+    }
+
+    sum
+}
+
+impl Solution for Memory {
+    /// Anything that isn't an instruction is corruption, so there's nothing to reject.
+    fn parse(input: &str) -> Result<Self, Error> {
+        Ok(parse_input(input))
+    }
+
+    fn part1(&self) -> Result<impl Display, Error> {
+        Ok(part1(&self.0))
+    }
+
+    fn part2(&self) -> Result<impl Display, Error> {
+        Ok(part2(&self.0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::BufReader;
+
+    const EXAMPLE_INPUT: &str =
+        "xmul(2,4)%&mul[3,7]!@^do_not_mul(5,5)+mul(32,64]then(mul(11,8)mul(8,5))";
+
+    const EXAMPLE_INPUT2: &str =
+        "xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))";
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(part1(&parse_input(EXAMPLE_INPUT).0), 161);
+    }
+
+    #[test]
+    fn test_part2() {
+        let memory = parse_input(EXAMPLE_INPUT2);
+        assert_eq!(
+            memory.0,
+            [
+                Op::Mul(2, 4),
+                Op::Dont,
+                Op::Mul(5, 5),
+                Op::Mul(11, 8),
+                Op::Do,
+                Op::Mul(8, 5)
+            ]
+        );
+        assert_eq!(part2(&memory.0), 48);
+    }
+
+    #[test]
+    fn test_operands() {
+        assert_eq!(
+            parse_input("mul(123,4)mul(1234,5)mul(,5)mul(2,3,4)mmul(2,3)").0,
+            [Op::Mul(123, 4), Op::Mul(2, 3)]
+        );
+        assert_eq!(parse_input("mul(2 ,3)mul(-2,3)mul(2,3").0, []);
+    }
+
+    #[test]
+    fn test_chunk_boundaries() {
+        let interpreter =
+            Interpreter::new().register("mul", 2, |sum: &mut i64, ops| *sum += ops[0] * ops[1]);
+
+        for capacity in 1..8 {
+            let reader = BufReader::with_capacity(capacity, EXAMPLE_INPUT.as_bytes());
+            let mut sum = 0;
+            interpreter.run(reader, &mut sum).unwrap();
+            assert_eq!(sum, 161);
+
+            let reader = BufReader::with_capacity(capacity, EXAMPLE_INPUT2.as_bytes());
+            assert_eq!(part2(&read_input(reader).unwrap().0), 48);
+        }
+    }
+
+    #[test]
+    fn test_custom_instructions() {
+        // `off()` and `on()` nest, `add` counts only outside of any `off()`.
+        #[derive(Default)]
+        struct Nested {
+            depth: usize,
+            sum: i64,
+        }
+
+        let interpreter = Interpreter::new()
+            .register("off", 0, |state: &mut Nested, _| state.depth += 1)
+            .register("on", 0, |state, _| {
+                state.depth = state.depth.saturating_sub(1)
+            })
+            .register("add", 2, |state, ops| {
+                if state.depth == 0 {
+                    state.sum += ops[0] + ops[1];
+                }
+            });
+
+        let state = interpreter.run_str("add(1,2)off()add(3,4)off()on()add(5,6)on()xadd(7,8)");
+        assert_eq!(state.sum, 3 + 15);
+        assert_eq!(state.depth, 0);
+    }
+}
@@ -0,0 +1,254 @@
+//! What every day has in common: the [`Solution`] trait, reading the input and timing the
+//! parts. The `aoc` runner and the day binaries both go through [`solve`].
+
+use std::{
+    fmt::{self, Display},
+    fs,
+    path::Path,
+    process,
+    str::FromStr,
+    time::{Duration, Instant},
+};
+
+pub type Error = Box<dyn std::error::Error>;
+
+/// A day's puzzle, parsed once and then asked for both answers.
+pub trait Solution: Sized {
+    fn parse(input: &str) -> Result<Self, Error>;
+
+    fn part1(&self) -> Result<impl Display, Error>;
+
+    fn part2(&self) -> Result<impl Display, Error> {
+        Err::<String, _>(Unsolved.into())
+    }
+}
+
+/// The answer to a part that hasn't been solved yet.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Unsolved;
+
+impl Display for Unsolved {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "not solved yet")
+    }
+}
+
+impl std::error::Error for Unsolved {}
+
+/// Input that isn't in the shape a day expects, for days without an error type of their own.
+/// `line` is 1-based.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub message: String,
+}
+
+impl ParseError {
+    pub fn new(line: usize, message: impl Into<String>) -> Self {
+        Self {
+            line,
+            message: message.into(),
+        }
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub const ALL: [Part; 2] = [Part::One, Part::Two];
+}
+
+impl Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Part::One => write!(f, "1"),
+            Part::Two => write!(f, "2"),
+        }
+    }
+}
+
+impl FromStr for Part {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "1" => Ok(Part::One),
+            "2" => Ok(Part::Two),
+            _ => Err(format!("there's no part {s:?}")),
+        }
+    }
+}
+
+pub struct PartReport {
+    pub part: Part,
+    pub answer: Result<String, Error>,
+    pub time: Duration,
+}
+
+impl PartReport {
+    /// Whether the part has an answer or was just never solved.
+    pub fn is_ok(&self) -> bool {
+        match &self.answer {
+            Ok(_) => true,
+            Err(e) => e.is::<Unsolved>(),
+        }
+    }
+}
+
+impl Display for PartReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.answer {
+            // Answers drawn as ASCII art go below the label.
+            Ok(answer) if answer.contains('\n') => {
+                write!(f, "part {} ({:.1?}):\n{}", self.part, self.time, answer)
+            }
+            Ok(answer) => write!(f, "part {}: {} ({:.1?})", self.part, answer, self.time),
+            Err(e) if e.is::<Unsolved>() => write!(f, "part {}: {}", self.part, e),
+            Err(e) => write!(f, "part {} failed: {}", self.part, e),
+        }
+    }
+}
+
+pub struct Report {
+    pub parse_time: Duration,
+    pub parts: Vec<PartReport>,
+}
+
+impl Report {
+    pub fn total_time(&self) -> Duration {
+        self.parse_time + self.parts.iter().map(|part| part.time).sum::<Duration>()
+    }
+}
+
+impl Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "parsed in {:.1?}", self.parse_time)?;
+        for part in self.parts.iter() {
+            writeln!(f, "{}", part)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Parses `input` and answers the requested `parts`, timing each step. Only failing to parse
+/// is an error, a part that fails is reported with the others.
+pub fn solve<S: Solution>(input: &str, parts: &[Part]) -> Result<Report, Error> {
+    let start = Instant::now();
+    let solution = S::parse(input)?;
+    let parse_time = start.elapsed();
+
+    let parts = parts
+        .iter()
+        .map(|&part| {
+            let start = Instant::now();
+            let answer = match part {
+                Part::One => solution
+                    .part1()
+                    .map(|answer| (start.elapsed(), answer.to_string())),
+                Part::Two => solution
+                    .part2()
+                    .map(|answer| (start.elapsed(), answer.to_string())),
+            };
+            match answer {
+                Ok((time, answer)) => PartReport {
+                    part,
+                    answer: Ok(answer),
+                    time,
+                },
+                Err(e) => PartReport {
+                    part,
+                    answer: Err(e),
+                    time: start.elapsed(),
+                },
+            }
+        })
+        .collect();
+
+    Ok(Report { parse_time, parts })
+}
+
+/// Reads the `input.txt` of the day in `day_dir`, exiting with a message if there's none.
+/// Day binaries pass `env!("CARGO_MANIFEST_DIR")`. The input lives as long as the program,
+/// like it did with `include_str!`.
+pub fn input(day_dir: impl AsRef<Path>) -> &'static str {
+    let path = day_dir.as_ref().join("input.txt");
+    match fs::read_to_string(&path) {
+        Ok(input) => input.leak(),
+        Err(e) => {
+            eprintln!("{}: {}", path.display(), e);
+            process::exit(1);
+        }
+    }
+}
+
+/// The whole `main` of a day binary with nothing to show besides the answers.
+pub fn run<S: Solution>(day_dir: impl AsRef<Path>) {
+    match solve::<S>(input(day_dir), &Part::ALL) {
+        Ok(report) => print!("{}", report),
+        Err(e) => {
+            eprintln!("input.txt: {}", e);
+            process::exit(1);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Sums the numbers, part 2 is left unsolved.
+    struct Sum(Vec<u64>);
+
+    impl Solution for Sum {
+        fn parse(input: &str) -> Result<Self, Error> {
+            let numbers = input
+                .split_whitespace()
+                .map(str::parse)
+                .collect::<Result<_, _>>()?;
+            Ok(Self(numbers))
+        }
+
+        fn part1(&self) -> Result<impl Display, Error> {
+            Ok(self.0.iter().sum::<u64>())
+        }
+    }
+
+    #[test]
+    fn test_solve() {
+        let report = solve::<Sum>("1 2 3", &Part::ALL).unwrap();
+        assert_eq!(report.parts.len(), 2);
+        assert_eq!(report.parts[0].answer.as_ref().unwrap(), "6");
+        assert!(report.parts[0].is_ok());
+
+        let unsolved = &report.parts[1];
+        assert!(unsolved.answer.as_ref().unwrap_err().is::<Unsolved>());
+        assert!(unsolved.is_ok());
+        assert_eq!(unsolved.to_string(), "part 2: not solved yet");
+
+        let report = solve::<Sum>("1 2 3", &[Part::Two]).unwrap();
+        assert_eq!(report.parts.len(), 1);
+        assert_eq!(report.parts[0].part, Part::Two);
+
+        assert!(solve::<Sum>("1 x", &Part::ALL).is_err());
+    }
+
+    #[test]
+    fn test_parts() {
+        assert_eq!("2".parse(), Ok(Part::Two));
+        assert!("3".parse::<Part>().is_err());
+        assert_eq!(Part::ALL.map(|part| part.to_string()), ["1", "2"]);
+    }
+}
@@ -0,0 +1,292 @@
+//! Runs the solutions of every day there is, or just the ones picked with `--year`, `--day`
+//! and `--part`, and times them.
+
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    process,
+    time::Duration,
+};
+
+use aoc::{Error, Part, Report};
+
+const USAGE: &str = "usage: aoc [--year <year>] [--day <day>] [--part <1|2>] [--input <file>]
+
+Runs every day matching the options, all of them if there are none. Each day reads the
+input.txt in its directory, --input picks another file when running a single day.";
+
+struct Day {
+    year: u16,
+    day: u8,
+    solve: fn(&str, &[Part]) -> Result<Report, Error>,
+}
+
+impl Day {
+    fn input_path(&self) -> PathBuf {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap();
+        root.join(self.year.to_string())
+            .join(format!("day{:02}", self.day))
+            .join("input.txt")
+    }
+}
+
+macro_rules! days {
+    ($($year:literal $day:literal $solution:ty),* $(,)?) => {
+        &[$(Day {
+            year: $year,
+            day: $day,
+            solve: aoc::solve::<$solution>,
+        }),*]
+    };
+}
+
+const DAYS: &[Day] = days![
+    2021 1 aoc2021_day01::SonarSweep,
+    2021 2 aoc2021_day02::Script,
+    2021 3 aoc2021_day03::DiagnosticsReport,
+    2021 4 aoc2021_day04::Bingo,
+    2021 5 aoc2021_day05::VentField,
+    2021 6 aoc2021_day06::LanternFishSim,
+    2021 7 aoc2021_day07::CrabPositions,
+    2021 8 aoc2021_day08::Outputs,
+    2021 9 aoc2021_day09::HeightMap,
+    2021 10 aoc2021_day10::NavigationSubsystem,
+    2021 11 aoc2021_day11::OctopusGrid,
+    2021 13 aoc2021_day13::Manual,
+    2021 14 aoc2021_day14::PolymerEngine,
+    2021 15 aoc2021_day15::RiskMap,
+    2021 16 aoc2021_day16::Packet,
+    2021 17 aoc2021_day17::TargetArea,
+    2022 1 aoc2022_day01::CalorieAggregator,
+    2022 2 aoc2022_day02::StrategyGuide,
+    2022 3 aoc2022_day03::Rucksacks,
+    2022 4 aoc2022_day04::SectionAssignments,
+    2022 5 aoc2022_day05::Procedure,
+    2022 6 aoc2022_day06::Datastream,
+    2022 7 aoc2022_day07::DirectorySizes,
+    2022 8 aoc2022_day08::TreeMap,
+    2022 9 aoc2022_day09::Motions,
+    2022 10 aoc2022_day10::Program,
+    2022 11 aoc2022_day11::Monkeys,
+    2022 12 aoc2022_day12::Map,
+    2022 13 aoc2022_day13::Signals,
+    2022 14 aoc2022_day14::Walls,
+    2022 15 aoc2022_day15::Sensors,
+    2022 16 aoc2022_day16::Scan,
+    2022 17 aoc2022_day17::JetPattern,
+    2022 18 aoc2022_day18::Droplet,
+    2022 19 aoc2022_day19::Blueprints,
+    2022 20 aoc2022_day20::EncryptedFile,
+    2022 21 aoc2022_day21::Riddle,
+    2022 22 aoc2022_day22::Map,
+    2023 1 aoc2023_day01::CalibrationDocument,
+    2023 2 aoc2023_day02::CubeGames,
+    2024 1 aoc2024_day01::LocationLists,
+    2024 2 aoc2024_day02::Reports,
+    2024 3 aoc2024_day03::Memory,
+];
+
+#[derive(Debug, Default, PartialEq, Eq)]
+struct Options {
+    year: Option<u16>,
+    day: Option<u8>,
+    part: Option<Part>,
+    input: Option<PathBuf>,
+}
+
+impl Options {
+    fn parse(mut args: impl Iterator<Item = String>) -> Result<Self, String> {
+        let mut options = Self::default();
+
+        while let Some(arg) = args.next() {
+            let value = args.next().ok_or(format!("{arg} needs a value"))?;
+            let invalid = |_| format!("invalid value for {arg}: {value:?}");
+
+            match arg.as_str() {
+                "--year" => options.year = Some(value.parse().map_err(invalid)?),
+                "--day" => match value.parse().map_err(invalid)? {
+                    day @ 1..=25 => options.day = Some(day),
+                    _ => return Err(format!("there's no day {value}")),
+                },
+                "--part" => options.part = Some(value.parse()?),
+                "--input" => options.input = Some(PathBuf::from(value)),
+                _ => return Err(format!("unknown option {arg}")),
+            }
+        }
+
+        Ok(options)
+    }
+
+    fn matches(&self, day: &Day) -> bool {
+        self.year.is_none_or(|year| year == day.year) && self.day.is_none_or(|d| d == day.day)
+    }
+
+    fn parts(&self) -> Vec<Part> {
+        match self.part {
+            Some(part) => vec![part],
+            None => Part::ALL.to_vec(),
+        }
+    }
+}
+
+fn main() {
+    let args = env::args().skip(1).collect::<Vec<_>>();
+    if args.iter().any(|arg| arg == "-h" || arg == "--help") {
+        println!("{USAGE}");
+        return;
+    }
+
+    let options = match Options::parse(args.into_iter()) {
+        Ok(options) => options,
+        Err(e) => {
+            eprintln!("{e}\n\n{USAGE}");
+            process::exit(2);
+        }
+    };
+
+    let days = DAYS
+        .iter()
+        .filter(|day| options.matches(day))
+        .collect::<Vec<_>>();
+    if days.is_empty() {
+        eprintln!("no solution matches");
+        process::exit(1);
+    }
+    if options.input.is_some() && days.len() > 1 {
+        eprintln!("--input only works for a single day, pick one with --year and --day");
+        process::exit(2);
+    }
+
+    let parts = options.parts();
+    let (mut solved, mut failed) = (0, 0);
+    let mut total_time = Duration::ZERO;
+
+    for day in days {
+        let path = options.input.clone().unwrap_or_else(|| day.input_path());
+        let input = match fs::read_to_string(&path) {
+            Ok(input) => input,
+            Err(e) => {
+                println!(
+                    "{} day {}: no input, {}: {}",
+                    day.year,
+                    day.day,
+                    path.display(),
+                    e
+                );
+                continue;
+            }
+        };
+
+        let report = match (day.solve)(&input, &parts) {
+            Ok(report) => report,
+            Err(e) => {
+                println!("{} day {}: invalid input, {}", day.year, day.day, e);
+                failed += 1;
+                continue;
+            }
+        };
+
+        println!(
+            "{} day {} (parsed in {:.1?})",
+            day.year, day.day, report.parse_time
+        );
+        for part in report.parts.iter() {
+            println!("  {}", part);
+        }
+
+        if report.parts.iter().all(|part| part.is_ok()) {
+            solved += 1;
+        } else {
+            failed += 1;
+        }
+        total_time += report.total_time();
+    }
+
+    println!();
+    println!(
+        "{} days solved in {:.1?}, {} failed",
+        solved, total_time, failed
+    );
+    if failed > 0 || solved == 0 {
+        process::exit(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<Options, String> {
+        Options::parse(args.iter().map(|arg| arg.to_string()))
+    }
+
+    #[test]
+    fn test_options() {
+        assert_eq!(parse(&[]), Ok(Options::default()));
+        assert_eq!(
+            parse(&["--year", "2022", "--day", "13", "--part", "2"]),
+            Ok(Options {
+                year: Some(2022),
+                day: Some(13),
+                part: Some(Part::Two),
+                input: None,
+            })
+        );
+        assert_eq!(
+            parse(&["--input", "example.txt"]).unwrap().input,
+            Some(PathBuf::from("example.txt"))
+        );
+
+        assert!(parse(&["--day", "26"]).is_err());
+        assert!(parse(&["--part", "3"]).is_err());
+        assert!(parse(&["--year", "twenty"]).is_err());
+        assert!(parse(&["--year"]).is_err());
+        assert!(parse(&["--verbose", "1"]).is_err());
+    }
+
+    #[test]
+    fn test_selection() {
+        let selected = |args: &[&str]| {
+            let options = parse(args).unwrap();
+            DAYS.iter()
+                .filter(|day| options.matches(day))
+                .map(|day| (day.year, day.day))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(selected(&[]).len(), DAYS.len());
+        assert_eq!(
+            selected(&["--year", "2022", "--day", "13"]),
+            vec![(2022, 13)]
+        );
+        assert_eq!(selected(&["--year", "2023"]), vec![(2023, 1), (2023, 2)]);
+        assert_eq!(selected(&["--day", "17"]), vec![(2021, 17), (2022, 17)]);
+        assert!(selected(&["--year", "2021", "--day", "12"]).is_empty());
+    }
+
+    #[test]
+    fn test_days_are_sorted_and_unique() {
+        assert!(DAYS
+            .windows(2)
+            .all(|pair| (pair[0].year, pair[0].day) < (pair[1].year, pair[1].day)));
+    }
+
+    #[test]
+    fn test_example_through_runner() {
+        let day = DAYS
+            .iter()
+            .find(|day| (day.year, day.day) == (2021, 1))
+            .unwrap();
+        let report = (day.solve)(
+            "199\n200\n208\n210\n200\n207\n240\n269\n260\n263",
+            &Part::ALL,
+        )
+        .unwrap();
+        let answers = report
+            .parts
+            .iter()
+            .map(|part| part.answer.as_ref().unwrap().as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(answers, ["7", "5"]);
+    }
+}